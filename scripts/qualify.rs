@@ -57,10 +57,34 @@ fn main() {
     // Clippy in important variants
     run_command!("cargo clippy -- -D warnings");
     run_command!("cargo +nightly clippy --all-targets -- -D warnings");
+    run_command!("cargo +nightly clippy --all-targets --all-features -- -D warnings");
 
     // Run tests in important variants
     run_command!("cargo +1.70.0 test");
     run_command!("cargo test --release");
+    run_command!("cargo test --no-default-features");
+    run_command!("cargo test --all-features");
+    for feature in [
+        "generations",
+        "notifications",
+        "optimistic_reads",
+        "observers",
+        "async",
+        "debounce",
+        "jitter",
+        "tags",
+        "readiness",
+        "interceptors",
+        "stats",
+        "snapshots",
+        "diagnostics",
+        "lock_order",
+        "testing",
+        "bench",
+    ] {
+        let command = format!("cargo test --doc --no-default-features --features {feature}");
+        run_command!(&command);
+    }
 
     // doc
     run_command!("cargo +nightly test --doc");
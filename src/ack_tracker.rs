@@ -0,0 +1,123 @@
+use crate::{CondSync, Other, PoisonedError, Reason};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Tracks up to which sequence number each of a fixed set of followers has acknowledged
+/// the updates of a leader.
+///
+/// Followers report their progress with [`AckTracker::ack`]; the leader waits with
+/// [`AckTracker::wait_acked_up_to`] until all followers have acknowledged a sequence number.
+///
+/// Clones share the same tracker.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::AckTracker;
+/// use std::{thread, time::Duration};
+///
+/// let tracker = AckTracker::new(["replica-1", "replica-2"]);
+/// for name in ["replica-1", "replica-2"] {
+///     let tracker_t = tracker.clone();
+///     thread::spawn(move || {
+///         for seq in 1..=10 {
+///             // ...apply update `seq`...
+///             tracker_t.ack(name, seq).unwrap();
+///         }
+///     });
+/// }
+/// let reason = tracker
+///     .wait_acked_up_to(10, Duration::from_secs(5))
+///     .unwrap();
+/// assert!(reason.is_condition());
+/// assert_eq!(tracker.acked_up_to().unwrap(), 10);
+/// ```
+#[derive(Clone)]
+pub struct AckTracker(CondSync<BTreeMap<String, u64>>);
+
+impl AckTracker {
+    /// Construct a new instance for the given followers, none of which has acknowledged
+    /// anything yet.
+    #[must_use]
+    pub fn new<I, S>(followers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(CondSync::new(
+            followers.into_iter().map(|f| (f.into(), 0)).collect(),
+        ))
+    }
+
+    /// Records that the given follower has acknowledged all updates up to `seq`.
+    ///
+    /// Acknowledgments never go back: a `seq` below the follower's current progress
+    /// is ignored.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `follower` is not one of the followers that were given to
+    /// [`AckTracker::new`].
+    pub fn ack(&self, follower: &str, seq: u64) -> Result<(), PoisonedError> {
+        let mut known = false;
+        self.0.modify_and_notify(
+            |progress| {
+                if let Some(acked) = progress.get_mut(follower) {
+                    known = true;
+                    *acked = seq.max(*acked);
+                }
+            },
+            Other::All,
+        )?;
+        // panicking only now does not poison the mutex
+        assert!(known, "unknown follower {follower:?}");
+        Ok(())
+    }
+
+    /// Blocks the current thread until all followers have acknowledged `seq`,
+    /// but no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_acked_up_to(&self, seq: u64, timeout: Duration) -> Result<Reason, PoisonedError> {
+        self.0
+            .wait_until_or_timeout(|progress| min_acked(progress) >= seq, timeout)
+    }
+
+    /// Returns the highest sequence number that all followers have acknowledged.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn acked_up_to(&self) -> Result<u64, PoisonedError> {
+        Ok(min_acked(&*self.0 .0.lock()?))
+    }
+
+    /// Returns for each follower the highest sequence number it has acknowledged,
+    /// ordered by the names of the followers.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn progress(&self) -> Result<Vec<(String, u64)>, PoisonedError> {
+        Ok(self
+            .0
+             .0
+            .lock()?
+            .iter()
+            .map(|(follower, acked)| (follower.clone(), *acked))
+            .collect())
+    }
+}
+
+// without followers, everything counts as acknowledged
+fn min_acked(progress: &BTreeMap<String, u64>) -> u64 {
+    progress.values().copied().min().unwrap_or(u64::MAX)
+}
@@ -0,0 +1,150 @@
+use crate::{remaining_until, CondSync, OperationKind, PoisonedError, Reason};
+use std::time::{Duration, Instant};
+
+/// A schedule of growing timeouts, for [`CondSync::wait_until_with_backoff`].
+///
+/// The first interval has the duration `initial`, each further interval is `multiplier`
+/// times as long as its predecessor, but no longer than `cap`.
+/// The intervals end when their total reaches `max_total`.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    initial: Duration,
+    multiplier: f64,
+    cap: Duration,
+    max_total: Duration,
+}
+impl Backoff {
+    /// Construct a new backoff policy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `initial` is zero, or if `multiplier` is smaller than 1 or not finite.
+    #[must_use]
+    pub fn new(initial: Duration, multiplier: f64, cap: Duration, max_total: Duration) -> Self {
+        assert!(!initial.is_zero(), "the initial interval must not be zero");
+        assert!(
+            multiplier.is_finite() && multiplier >= 1.0,
+            "the multiplier must be finite and at least 1"
+        );
+        Self {
+            initial,
+            multiplier,
+            cap,
+            max_total,
+        }
+    }
+
+    fn next_interval(&self, interval: Duration) -> Duration {
+        Duration::try_from_secs_f64(interval.as_secs_f64() * self.multiplier)
+            .map_or(self.cap, |next| next.min(self.cap))
+    }
+}
+
+/// The result of [`CondSync::wait_until_with_backoff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackoffOutcome {
+    reason: Reason,
+    intervals: usize,
+    elapsed: Duration,
+}
+impl BackoffOutcome {
+    /// [`Reason::Condition`] if the condition was fulfilled, and [`Reason::Timeout`]
+    /// if the total time of the [`Backoff`] was used up.
+    #[must_use]
+    pub fn reason(&self) -> Reason {
+        self.reason
+    }
+
+    /// The number of intervals that were started.
+    #[must_use]
+    pub fn intervals(&self) -> usize {
+        self.intervals
+    }
+
+    /// The total time waited.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<T> CondSync<T> {
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// with timeouts that follow the given [`Backoff`].
+    ///
+    /// The condition is evaluated on each wakeup, and additionally at the end of each
+    /// interval, so it can include polling an external system, which is then polled with
+    /// decreasing frequency.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{Backoff, CondSync};
+    /// use std::time::Duration;
+    ///
+    /// let cond_sync = CondSync::new(());
+    /// let backoff = Backoff::new(
+    ///     Duration::from_millis(1),
+    ///     2.0,
+    ///     Duration::from_millis(8),
+    ///     Duration::from_millis(30),
+    /// );
+    /// let mut polls = 0;
+    /// let outcome = cond_sync
+    ///     .wait_until_with_backoff(
+    ///         |_| {
+    ///             polls += 1; // e.g. ask an external system
+    ///             polls == 4
+    ///         },
+    ///         &backoff,
+    ///     )
+    ///     .unwrap();
+    /// assert!(outcome.reason().is_condition());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_with_backoff<F>(
+        &self,
+        mut condition: F,
+        backoff: &Backoff,
+    ) -> Result<BackoffOutcome, PoisonedError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let start = Instant::now();
+        let (intervals, reason) =
+            self.0
+                .intercept(OperationKind::Wait, "wait_until_with_backoff", || {
+                    let mut interval = backoff.initial;
+                    let mut intervals = 0;
+                    loop {
+                        let remaining = backoff.max_total.saturating_sub(start.elapsed());
+                        if remaining.is_zero() {
+                            return Ok((intervals, Reason::Timeout));
+                        }
+                        intervals += 1;
+                        let mtx_guard = self.0.lock()?;
+                        let waited = self.0.wait_for(
+                            mtx_guard,
+                            |v| condition(v).then_some(()),
+                            remaining_until(Instant::now().checked_add(interval.min(remaining))),
+                        )?;
+                        if waited.into_reason().is_condition() {
+                            return Ok((intervals, Reason::Condition));
+                        }
+                        interval = backoff.next_interval(interval);
+                    }
+                })?;
+        Ok(BackoffOutcome {
+            reason,
+            intervals,
+            elapsed: start.elapsed(),
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{CondSync, OperationKind, Other, PoisonedError, Reason};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// A reusable barrier that lets a fixed number of threads wait for each other.
+///
+/// With [`BarrierTopology::Flat`], all threads wait on one condvar.
+/// With very many threads, this causes a thundering herd when the last thread arrives,
+/// so that [`BarrierTopology::Tree`] can be used instead:
+/// the threads are split into groups of `fan_in` threads, the last arriving thread of each
+/// group proceeds to the next level of the tree, and on release each group is woken up
+/// separately.
+///
+/// Clones refer to the same barrier.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{BarrierTopology, CondBarrier};
+/// use std::thread;
+///
+/// let barrier = CondBarrier::new(8, BarrierTopology::Tree { fan_in: 2 });
+///
+/// let handles: Vec<_> = (0..8)
+///     .map(|i| {
+///         let barrier_t = barrier.clone();
+///         thread::spawn(move || {
+///             println!("Thread {i}: phase 1");
+///             barrier_t.wait().unwrap();
+///             println!("Thread {i}: phase 2");
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CondBarrier(Arc<Inner>);
+
+/// Describes how the threads waiting on a [`CondBarrier`] are organized.
+#[derive(Copy, Clone, Debug)]
+pub enum BarrierTopology {
+    /// All threads wait on a single condvar.
+    Flat,
+    /// Groups of `fan_in` threads wait on separate condvars, which are combined in a tree.
+    Tree {
+        /// The number of threads or sub-groups per group; must be at least 2.
+        fan_in: usize,
+    },
+}
+
+struct Inner {
+    n: usize,
+    group_size: usize,
+    tickets: AtomicU64,
+    // the leaves come first, the root is the last node
+    nodes: Vec<Node>,
+}
+
+struct Node {
+    parent: Option<usize>,
+    expected: usize,
+    cond_sync: CondSync<NodeState>,
+}
+
+struct NodeState {
+    arrived: usize,
+    released_phase: u64,
+}
+
+impl CondBarrier {
+    /// Construct a barrier for `n` threads.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `n` is 0, or if a tree topology with a `fan_in` below 2 is requested.
+    #[must_use]
+    pub fn new(n: usize, topology: BarrierTopology) -> Self {
+        assert!(n > 0, "a barrier needs at least one thread");
+        let group_size = match topology {
+            BarrierTopology::Flat => n,
+            BarrierTopology::Tree { fan_in } => {
+                assert!(fan_in >= 2, "fan_in must be at least 2");
+                fan_in
+            }
+        };
+
+        let mut nodes = Vec::new();
+        let mut level_start = 0;
+        let mut level_members = n;
+        loop {
+            let level_len = (level_members + group_size - 1) / group_size;
+            for i in 0..level_len {
+                nodes.push(Node {
+                    parent: None,
+                    expected: group_size.min(level_members - i * group_size),
+                    cond_sync: CondSync::new(NodeState {
+                        arrived: 0,
+                        released_phase: 0,
+                    }),
+                });
+            }
+            if level_len == 1 {
+                break;
+            }
+            let next_level_start = level_start + level_len;
+            for i in 0..level_len {
+                nodes[level_start + i].parent = Some(next_level_start + i / group_size);
+            }
+            level_start = next_level_start;
+            level_members = level_len;
+        }
+
+        Self(Arc::new(Inner {
+            n,
+            group_size,
+            tickets: AtomicU64::new(0),
+            nodes,
+        }))
+    }
+
+    /// Blocks the current thread until all threads have reached the barrier.
+    ///
+    /// The barrier can then be used again for the next phase.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `true` for exactly one of the threads of each phase.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if one of the internally used mutexes is poisoned.
+    #[track_caller]
+    pub fn wait(&self) -> Result<bool, PoisonedError> {
+        let inner = &*self.0;
+        let n = inner.n as u64;
+        let ticket = inner.tickets.fetch_add(1, Ordering::SeqCst);
+        let phase = ticket / n;
+        #[allow(clippy::cast_possible_truncation)]
+        let mut node_idx = (ticket % n) as usize / inner.group_size;
+
+        // the nodes at which this thread arrived last, and which it thus has to release
+        let mut to_release = Vec::new();
+        let mut is_leader = false;
+        loop {
+            if !inner.arrive(node_idx, phase)? {
+                break;
+            }
+            to_release.push(node_idx);
+            if let Some(parent) = inner.nodes[node_idx].parent {
+                node_idx = parent;
+            } else {
+                is_leader = true;
+                break;
+            }
+        }
+
+        for node_idx in to_release.into_iter().rev() {
+            inner.nodes[node_idx]
+                .cond_sync
+                .modify_and_notify(|state| state.released_phase = phase + 1, Other::All)?;
+        }
+        Ok(is_leader)
+    }
+}
+
+impl Inner {
+    // Returns `true` if the thread is the last one arriving at the node,
+    // and otherwise blocks until the node is released.
+    #[track_caller]
+    fn arrive(&self, node_idx: usize, phase: u64) -> Result<bool, PoisonedError> {
+        let node = &self.nodes[node_idx];
+        let i = &node.cond_sync.0;
+        let (last, _) = i.intercept(OperationKind::Wait, "wait", || {
+            let mut mtx_guard = i.lock()?;
+            let mut last = false;
+            let generation = i.apply(&mut mtx_guard, |state| {
+                state.arrived += 1;
+                if state.arrived == node.expected {
+                    state.arrived = 0;
+                    last = true;
+                }
+            });
+            drop(mtx_guard);
+            i.modified(generation, None);
+            if last {
+                return Ok((true, Reason::Condition));
+            }
+            // the release is checked first, so it can't be missed while the lock was released
+            let mtx_guard = i.lock()?;
+            let reason = i
+                .wait_for(
+                    mtx_guard,
+                    |state| (state.released_phase > phase).then_some(()),
+                    || None,
+                )?
+                .into_reason();
+            Ok((false, reason))
+        })?;
+        Ok(last)
+    }
+}
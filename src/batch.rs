@@ -0,0 +1,111 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError, Reason, Waited};
+use std::time::{Duration, Instant};
+
+// Helpers for the common use of accumulating items until a batch is complete.
+impl<T> CondSync<Vec<T>> {
+    /// Appends the item to the wrapped vector and notifies waiting threads.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn push_and_notify(&self, item: T, other: Other) -> Result<(), PoisonedError> {
+        self.0
+            .intercept(OperationKind::Modify, "push_and_notify", || {
+                let mut mtx_guard = self.0.lock()?;
+                let generation = self.0.apply(&mut mtx_guard, |v| v.push(item));
+                self.0.notify(other);
+                drop(mtx_guard);
+                self.0.modified(generation, other);
+                Ok(())
+            })
+    }
+
+    /// Blocks the current thread until the wrapped vector contains at least `n` items,
+    /// then takes the first `n` items out.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::thread;
+    ///
+    /// let batcher = CondSync::new(Vec::new());
+    /// let batcher_t = batcher.clone();
+    /// thread::spawn(move || {
+    ///     for i in 0..10 {
+    ///         batcher_t.push_and_notify(i, Other::One).unwrap();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(batcher.wait_batch(4).unwrap(), vec![0, 1, 2, 3]);
+    /// assert_eq!(batcher.wait_batch(4).unwrap(), vec![4, 5, 6, 7]);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_batch(&self, n: usize) -> Result<Vec<T>, PoisonedError> {
+        let (batch, _) = self.0.intercept(OperationKind::Wait, "wait_batch", || {
+            let mtx_guard = self.0.lock()?;
+            let waited = self
+                .0
+                .wait_for(mtx_guard, |v| (v.len() >= n).then_some(()), || None)?;
+            Ok(self.take_batch(waited, n))
+        })?;
+        Ok(batch)
+    }
+
+    /// Like [`CondSync::wait_batch`], but waits no longer than the given duration;
+    /// then the items that were accumulated so far are taken out, which can be fewer than `n`,
+    /// or none at all.
+    ///
+    /// This allows flushing incomplete batches after a maximum latency.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_batch_or_timeout(
+        &self,
+        n: usize,
+        max_wait: Duration,
+    ) -> Result<Vec<T>, PoisonedError> {
+        let (batch, _) = self
+            .0
+            .intercept(OperationKind::Wait, "wait_batch_or_timeout", || {
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| (v.len() >= n).then_some(()),
+                    remaining_until(Instant::now().checked_add(max_wait)),
+                )?;
+                Ok(self.take_batch(waited, n))
+            })?;
+        Ok(batch)
+    }
+
+    // takes up to `n` items out, notifies if something was taken, and releases the lock
+    fn take_batch(&self, mut waited: Waited<'_, Vec<T>, ()>, n: usize) -> (Vec<T>, Reason) {
+        let reason = waited.reason();
+        let mut batch = Vec::new();
+        let generation = (!waited.mtx_guard.is_empty()).then(|| {
+            self.0.apply(&mut waited.mtx_guard, |v| {
+                batch = v.drain(..n.min(v.len())).collect();
+            })
+        });
+        if generation.is_some() {
+            self.0.notify(Other::All);
+        }
+        waited.finish();
+        if let Some(generation) = generation {
+            self.0.modified(generation, Other::All);
+        }
+        (batch, reason)
+    }
+}
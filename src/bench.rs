@@ -0,0 +1,224 @@
+use crate::{CondSync, CondSyncBuilder, Other};
+use std::{
+    fmt,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+type Configure = Arc<dyn Fn(CondSyncBuilder<u64>) -> CondSyncBuilder<u64> + Send + Sync>;
+
+/// Describes a benchmark run with [`bench_ping_pong`] or [`bench_fan_in`].
+///
+/// The benchmarks run on the machine where they are called, so that the options of
+/// [`CondSyncBuilder`] can be compared under realistic conditions
+/// before one of them is chosen for production.
+///
+/// Only available with feature `bench`.
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub struct BenchOptions {
+    rounds: u64,
+    threads: usize,
+    configure: Configure,
+}
+impl BenchOptions {
+    /// Construct a new instance with 10,000 rounds, 4 threads, and the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rounds: 10_000,
+            threads: 4,
+            configure: Arc::new(|builder| builder),
+        }
+    }
+
+    /// Sets the number of rounds; what a round is depends on the benchmark.
+    #[must_use]
+    pub fn rounds(mut self, rounds: u64) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Sets the number of concurrent threads, for benchmarks where it can be chosen.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Configures the benchmarked instance.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{bench_ping_pong, BenchOptions};
+    ///
+    /// let plain = bench_ping_pong(&BenchOptions::new().rounds(1_000));
+    /// let counting = bench_ping_pong(
+    ///     &BenchOptions::new()
+    ///         .rounds(1_000)
+    ///         .configure(|builder| builder.counting_notifications(true)),
+    /// );
+    /// println!("plain: {plain}\ncounting: {counting}");
+    /// ```
+    #[must_use]
+    pub fn configure<F>(mut self, configure: F) -> Self
+    where
+        F: Fn(CondSyncBuilder<u64>) -> CondSyncBuilder<u64> + Send + Sync + 'static,
+    {
+        self.configure = Arc::new(configure);
+        self
+    }
+
+    fn build(&self) -> CondSync<u64> {
+        (self.configure)(CondSync::builder(0)).build()
+    }
+}
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl fmt::Debug for BenchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BenchOptions")
+            .field("rounds", &self.rounds)
+            .field("threads", &self.threads)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The result of a benchmark run.
+///
+/// Only available with feature `bench`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub struct BenchReport {
+    operations: u64,
+    elapsed: Duration,
+}
+impl BenchReport {
+    /// The number of measured operations; what an operation is depends on the benchmark.
+    #[must_use]
+    pub fn operations(&self) -> u64 {
+        self.operations
+    }
+
+    /// The duration of the whole run.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The average duration of an operation.
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        u32::try_from(self.operations)
+            .ok()
+            .filter(|operations| *operations > 0)
+            .map_or(Duration::ZERO, |operations| self.elapsed / operations)
+    }
+
+    /// The number of operations per second.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.operations as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} operations in {:?} ({:?} each, {:.0}/s)",
+            self.operations,
+            self.elapsed,
+            self.mean(),
+            self.per_second()
+        )
+    }
+}
+
+/// Measures the latency of handing the turn back and forth between two threads.
+///
+/// Each round consists of two handovers, which are the measured operations;
+/// the number of threads is ignored.
+///
+/// Only available with feature `bench`.
+///
+/// ## Panics
+///
+/// Panics if the internally used mutex is poisoned.
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub fn bench_ping_pong(options: &BenchOptions) -> BenchReport {
+    let cond_sync = options.build();
+    let handovers = 2 * options.rounds;
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for parity in 0..2 {
+            let cond_sync = &cond_sync;
+            scope.spawn(move || {
+                for _ in 0..options.rounds {
+                    cond_sync
+                        .wait_until_and_modify(|v| *v % 2 == parity, |v| *v += 1, Other::One)
+                        .expect("the mutex of the benchmarked CondSync is poisoned");
+                }
+            });
+        }
+    });
+    BenchReport {
+        operations: handovers,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Measures how fast a single consumer is woken up by many producers.
+///
+/// Each of the given number of threads does the given number of modifications,
+/// which are the measured operations, while one additional thread waits for each change.
+///
+/// Only available with feature `bench`.
+///
+/// ## Panics
+///
+/// Panics if the internally used mutex is poisoned.
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub fn bench_fan_in(options: &BenchOptions) -> BenchReport {
+    let cond_sync = options.build();
+    let total = options.rounds * options.threads as u64;
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| {
+                for _ in 0..options.rounds {
+                    cond_sync
+                        .modify_and_notify(|v| *v += 1, Other::One)
+                        .expect("the mutex of the benchmarked CondSync is poisoned");
+                }
+            });
+        }
+        scope.spawn(|| {
+            let mut seen = 0;
+            while seen < total {
+                cond_sync
+                    .wait_until(|v| *v != seen)
+                    .expect("the mutex of the benchmarked CondSync is poisoned");
+                seen = cond_sync
+                    .try_clone_inner()
+                    .expect("the mutex of the benchmarked CondSync is poisoned");
+            }
+        });
+    });
+    BenchReport {
+        operations: total,
+        elapsed: start.elapsed(),
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{remaining_until, CondSync, Other, PoisonedError};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Distributes every published version of a value to all subscribers.
+///
+/// Unlike waiting on a [`CondSync`] directly, where a slow waiter only sees the latest value,
+/// each [`Subscriber`] receives every version, in order.
+/// Each subscriber has its own buffer of the given capacity; if a subscriber falls behind
+/// by more than that, the oldest versions are dropped for it, and it is told so explicitly
+/// with [`BroadcastError::Lagged`].
+///
+/// Clones share the same broadcast.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Broadcast;
+/// use std::thread;
+///
+/// let bids = Broadcast::new(16);
+/// let subscriber = bids.subscribe().unwrap();
+///
+/// let bids_t = bids.clone();
+/// let auctioneer = thread::spawn(move || {
+///     for bid in [100, 120, 150] {
+///         bids_t.publish(bid).unwrap();
+///     }
+/// });
+///
+/// let seen: Vec<u32> = (0..3).map(|_| subscriber.next_version().unwrap().1).collect();
+/// assert_eq!(seen, vec![100, 120, 150]);
+/// auctioneer.join().unwrap();
+/// ```
+pub struct Broadcast<T>(CondSync<State<T>>);
+
+struct State<T> {
+    capacity: usize,
+    last_version: u64,
+    next_subscriber: u64,
+    buffers: HashMap<u64, Buffer<T>>,
+}
+
+struct Buffer<T> {
+    versions: VecDeque<(u64, T)>,
+    missed: u64,
+}
+
+impl<T> Broadcast<T> {
+    /// Construct a new instance, where each subscriber buffers at most `capacity` versions.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        Self(CondSync::new(State {
+            capacity,
+            last_version: 0,
+            next_subscriber: 0,
+            buffers: HashMap::new(),
+        }))
+    }
+
+    /// Returns a new subscriber, which receives all versions that are published from now on.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn subscribe(&self) -> Result<Subscriber<T>, PoisonedError> {
+        let mut id = 0;
+        self.0 .0.modify(
+            "subscribe",
+            |state| {
+                id = state.next_subscriber;
+                state.next_subscriber += 1;
+                state.buffers.insert(
+                    id,
+                    Buffer {
+                        versions: VecDeque::with_capacity(state.capacity),
+                        missed: 0,
+                    },
+                );
+            },
+            None,
+        )?;
+        Ok(Subscriber {
+            broadcast: self.clone(),
+            id,
+        })
+    }
+}
+
+impl<T: Clone> Broadcast<T> {
+    /// Hands a copy of the value to each subscriber, and returns its version number.
+    ///
+    /// Version numbers start with 1 and increase by 1 with each publication.
+    /// Never blocks; subscribers whose buffer is full lose their oldest version.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn publish(&self, value: T) -> Result<u64, PoisonedError> {
+        let mut version = 0;
+        self.0.modify_and_notify(
+            |state| {
+                state.last_version += 1;
+                version = state.last_version;
+                let capacity = state.capacity;
+                for buffer in state.buffers.values_mut() {
+                    if buffer.versions.len() >= capacity {
+                        buffer.versions.pop_front();
+                        buffer.missed += 1;
+                    }
+                    buffer.versions.push_back((version, value.clone()));
+                }
+            },
+            Other::All,
+        )?;
+        Ok(version)
+    }
+}
+
+impl<T> Clone for Broadcast<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Receives the versions that are published with a [`Broadcast`];
+/// see [`Broadcast::subscribe`].
+///
+/// Dropping the subscriber releases its buffer.
+pub struct Subscriber<T> {
+    broadcast: Broadcast<T>,
+    id: u64,
+}
+
+impl<T> Subscriber<T> {
+    /// Returns the next version and its version number,
+    /// and blocks the current thread while there is none.
+    ///
+    /// ## Errors
+    ///
+    /// [`BroadcastError::Lagged`] if versions were dropped because the buffer was full;
+    /// the next call returns the oldest version that is still buffered.
+    ///
+    /// [`BroadcastError::Poisoned`] if the internally used mutex is poisoned.
+    pub fn next_version(&self) -> Result<(u64, T), BroadcastError> {
+        self.next_version_until(None)
+            .map(|next| next.unwrap_or_else(|| unreachable!("unbounded waits return a version")))
+    }
+
+    /// Like [`Subscriber::next_version`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The next version, or `None` if no version was published in time.
+    ///
+    /// ## Errors
+    ///
+    /// See [`Subscriber::next_version`].
+    pub fn next_version_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<Option<(u64, T)>, BroadcastError> {
+        self.next_version_until(Instant::now().checked_add(duration))
+    }
+
+    /// Returns the number of versions that are buffered for this subscriber.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn pending(&self) -> Result<usize, PoisonedError> {
+        Ok(self.broadcast.0 .0.lock()?.buffers[&self.id].versions.len())
+    }
+
+    fn next_version_until(&self, end: Option<Instant>) -> Result<Option<(u64, T)>, BroadcastError> {
+        let i = &self.broadcast.0 .0;
+        let mtx_guard = i.lock()?;
+        let mut waited = i.wait_for(
+            mtx_guard,
+            |state| {
+                let buffer = &state.buffers[&self.id];
+                (buffer.missed > 0 || !buffer.versions.is_empty()).then_some(())
+            },
+            remaining_until(end),
+        )?;
+        if waited.outcome.is_none() {
+            waited.finish();
+            return Ok(None);
+        }
+        let mut next = Ok(None);
+        i.apply(&mut waited.mtx_guard, |state| {
+            let buffer = state
+                .buffers
+                .get_mut(&self.id)
+                .unwrap_or_else(|| unreachable!("the buffer lives as long as the subscriber"));
+            next = if buffer.missed > 0 {
+                Err(BroadcastError::Lagged(std::mem::take(&mut buffer.missed)))
+            } else {
+                Ok(buffer.versions.pop_front())
+            };
+        });
+        waited.finish();
+        next
+    }
+}
+
+impl<T> Drop for Subscriber<T> {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.broadcast
+            .0
+             .0
+            .modify(
+                "unsubscribe",
+                |state| {
+                    state.buffers.remove(&id);
+                },
+                None,
+            )
+            .ok();
+    }
+}
+
+/// Error of [`Subscriber::next_version`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum BroadcastError {
+    /// The subscriber fell behind, and the given number of versions was dropped for it.
+    Lagged(u64),
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for BroadcastError {
+    fn from(e: PoisonedError) -> BroadcastError {
+        match e {
+            PoisonedError::Poisoned => BroadcastError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => BroadcastError::WouldDeadlock { instance },
+        }
+    }
+}
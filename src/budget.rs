@@ -0,0 +1,130 @@
+use crate::{remaining_until, CondSync, OperationKind, PoisonedError};
+use std::time::{Duration, Instant};
+
+/// A time budget that is shared by a sequence of waits, e.g. all waits of a request handler
+/// that has to respect an end-to-end deadline.
+///
+/// Each wait with [`CondSync::wait_until_within_budget`] consumes from the same remaining
+/// time, and the wait that runs out of it reports which step exhausted the budget.
+/// The waits can be on different [`CondSync`] instances.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{Budget, BudgetError, CondSync};
+/// use std::time::Duration;
+///
+/// let connected = CondSync::new(true);
+/// let authenticated = CondSync::new(false);
+///
+/// let budget = Budget::new(Duration::from_millis(20));
+/// connected
+///     .wait_until_within_budget("connect", |v| *v, &budget)
+///     .unwrap();
+/// let error = authenticated
+///     .wait_until_within_budget("authenticate", |v| *v, &budget)
+///     .unwrap_err();
+/// assert_eq!(error, BudgetError::Exhausted { step: "authenticate" });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    end: Option<Instant>,
+}
+impl Budget {
+    /// Construct a new budget, starting now.
+    #[must_use]
+    pub fn new(total: Duration) -> Self {
+        Self {
+            end: Instant::now().checked_add(total),
+        }
+    }
+
+    /// Returns the time that is left.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.end.map_or(Duration::MAX, |end| {
+            end.saturating_duration_since(Instant::now())
+        })
+    }
+
+    /// Returns `true` if no time is left.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+impl<T> CondSync<T> {
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// but no longer than the remaining time of the given [`Budget`].
+    ///
+    /// `step` names the wait; it is reported if the budget is exhausted,
+    /// and it is used as the label of the wait, like with [`CondSync::wait_until_named`].
+    ///
+    /// ## Errors
+    ///
+    /// [`BudgetError::Exhausted`] if the budget was used up before the condition was fulfilled.
+    ///
+    /// [`BudgetError::Poisoned`] if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_within_budget<F>(
+        &self,
+        step: &'static str,
+        condition: F,
+        budget: &Budget,
+    ) -> Result<(), BudgetError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let reason = self.0.intercept_labeled(
+            OperationKind::Wait,
+            "wait_until_within_budget",
+            Some(step),
+            || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(budget.end),
+                    )?
+                    .into_reason())
+            },
+        )?;
+        if reason.is_condition() {
+            Ok(())
+        } else {
+            Err(BudgetError::Exhausted { step })
+        }
+    }
+}
+
+/// Error of [`CondSync::wait_until_within_budget`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum BudgetError {
+    /// The budget was used up.
+    Exhausted {
+        /// The step that was waiting when the budget was used up.
+        step: &'static str,
+    },
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for BudgetError {
+    fn from(e: PoisonedError) -> BudgetError {
+        match e {
+            PoisonedError::Poisoned => BudgetError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => BudgetError::WouldDeadlock { instance },
+        }
+    }
+}
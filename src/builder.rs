@@ -0,0 +1,253 @@
+use crate::CondSync;
+#[cfg(feature = "interceptors")]
+use crate::Interceptor;
+#[cfg(any(feature = "debounce", feature = "testing"))]
+use crate::Wake;
+#[cfg(feature = "optimistic_reads")]
+use crate::{
+    seqlock::{write_words, Mirror},
+    SeqLockable,
+};
+#[cfg(any(feature = "debounce", feature = "testing", feature = "interceptors"))]
+use std::sync::Arc;
+#[cfg(any(feature = "debounce", feature = "testing"))]
+use std::sync::Weak;
+#[cfg(any(feature = "debounce", feature = "jitter"))]
+use std::time::Duration;
+
+/// Allows configuring a [`CondSync`] before it is created.
+///
+/// Is obtained with [`CondSync::builder`].
+///
+/// ## Example
+///
+/// ```rust
+/// # #[cfg(feature = "notifications")] {
+/// use cond_sync::CondSync;
+///
+/// let cond_sync = CondSync::builder(0_usize)
+///     .sticky_notifications(true)
+///     .build();
+/// # }
+/// ```
+pub struct CondSyncBuilder<T> {
+    value: T,
+    config: Config,
+    // are applied to the new instance
+    init: Vec<fn(&CondSync<T>)>,
+}
+
+#[derive(Default)]
+pub(crate) struct Config {
+    #[cfg(feature = "notifications")]
+    pub(crate) sticky_notifications: bool,
+    #[cfg(feature = "notifications")]
+    pub(crate) counting_notifications: bool,
+    #[cfg(feature = "jitter")]
+    pub(crate) wakeup_jitter: Option<Duration>,
+    #[cfg(feature = "jitter")]
+    pub(crate) jitter_seed: Option<u64>,
+    #[cfg(feature = "interceptors")]
+    pub(crate) interceptor: Option<Arc<dyn Interceptor>>,
+    #[cfg(feature = "debounce")]
+    pub(crate) debounce: Option<(Duration, Duration)>,
+    #[cfg(feature = "testing")]
+    pub(crate) chaos: Option<crate::Chaos>,
+}
+
+impl<T> CondSyncBuilder<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            config: Config::default(),
+            init: Vec::new(),
+        }
+    }
+
+    /// Notifications that are sent while no thread is waiting in
+    /// [`CondSync::wait_timeout`] are by default lost.
+    ///
+    /// With sticky notifications, such a notification remains pending until it is consumed
+    /// by the next call to [`CondSync::wait_timeout`], which then returns immediately.
+    /// Several pending notifications are merged into one.
+    ///
+    /// Only available with feature `notifications`.
+    #[cfg(feature = "notifications")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notifications")))]
+    #[must_use]
+    pub fn sticky_notifications(mut self, sticky: bool) -> Self {
+        self.config.sticky_notifications = sticky;
+        self
+    }
+
+    /// Like [sticky notifications](CondSyncBuilder::sticky_notifications), but pending
+    /// notifications are counted, like the permits of a semaphore, rather than merged:
+    /// each notification is consumed by exactly one call to [`CondSync::wait_timeout`].
+    ///
+    /// Every notification counts as one, also with [`Other::All`](crate::Other::All).
+    /// This option takes precedence over sticky notifications.
+    ///
+    /// Only available with feature `notifications`.
+    #[cfg(feature = "notifications")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notifications")))]
+    #[must_use]
+    pub fn counting_notifications(mut self, counting: bool) -> Self {
+        self.config.counting_notifications = counting;
+        self
+    }
+
+    /// When many threads are woken up at once, e.g. with [`Other::All`](crate::Other::All),
+    /// and then immediately contend on some downstream resource, it can help to let them
+    /// continue not exactly at the same time.
+    ///
+    /// With this option, each waiting method that was woken up sleeps for a random duration
+    /// of up to `max_jitter` after its condition was found to be fulfilled,
+    /// and before it returns. The lock is not held during that time.
+    ///
+    /// Only available with feature `jitter`.
+    #[cfg(feature = "jitter")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jitter")))]
+    #[must_use]
+    pub fn wakeup_jitter(mut self, max_jitter: Duration) -> Self {
+        self.config.wakeup_jitter = Some(max_jitter);
+        self
+    }
+
+    /// Sets the seed for the random numbers that are used for the
+    /// [wakeup jitter](CondSyncBuilder::wakeup_jitter), to make tests reproducible.
+    ///
+    /// By default, a random seed is used.
+    ///
+    /// Only available with feature `jitter`.
+    #[cfg(feature = "jitter")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jitter")))]
+    #[must_use]
+    pub fn jitter_seed(mut self, seed: u64) -> Self {
+        self.config.jitter_seed = Some(seed);
+        self
+    }
+
+    /// Sets an [`Interceptor`] that is called before and after every waiting and
+    /// modifying operation on this instance.
+    ///
+    /// Only available with feature `interceptors`.
+    #[cfg(feature = "interceptors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "interceptors")))]
+    #[must_use]
+    pub fn interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.config.interceptor = Some(interceptor);
+        self
+    }
+
+    /// Creates the configured [`CondSync`].
+    #[must_use]
+    pub fn build(self) -> CondSync<T> {
+        let cond_sync = CondSync::with_config(self.value, self.config);
+        for init in self.init {
+            init(&cond_sync);
+        }
+        cond_sync
+    }
+}
+
+#[cfg(feature = "debounce")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debounce")))]
+impl<T> CondSyncBuilder<T>
+where
+    T: Send + 'static,
+{
+    /// When the wrapped variable is modified very frequently, waking up the waiting threads
+    /// with each modification can waste a lot of CPU.
+    ///
+    /// With this option, notifications are not sent immediately, but merged into one
+    /// delayed notification, which is sent when no further notification happened within
+    /// `window`, but at the latest `max_latency` after the first merged notification.
+    /// If any of the merged notifications was meant for all threads, all threads are notified.
+    ///
+    /// The delayed notifications are sent by the crate's internal timer thread.
+    ///
+    /// Only available with feature `debounce`.
+    #[must_use]
+    pub fn debounce(mut self, window: Duration, max_latency: Duration) -> Self {
+        self.config.debounce = Some((window, max_latency));
+        self.init.push(|cond_sync| {
+            if let Some(ref debouncer) = cond_sync.0.debouncer {
+                debouncer.set_waker(Arc::downgrade(&cond_sync.0) as Weak<dyn Wake>);
+            }
+        });
+        self
+    }
+}
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+impl<T> CondSyncBuilder<T>
+where
+    T: Send + 'static,
+{
+    /// Disturbs the notifications of the new instance randomly, as described by
+    /// the given [`Chaos`](crate::Chaos), to explore different interleavings in tests.
+    ///
+    /// Only available with feature `testing`.
+    #[must_use]
+    pub fn chaos(mut self, chaos: crate::Chaos) -> Self {
+        self.config.chaos = Some(chaos);
+        self.init.push(|cond_sync| {
+            if let Some(ref chaos) = cond_sync.0.chaos {
+                chaos.set_waker(Arc::downgrade(&cond_sync.0) as Weak<dyn Wake>);
+            }
+        });
+        self
+    }
+}
+
+#[cfg(feature = "snapshots")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshots")))]
+impl<T> CondSyncBuilder<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// For large states that are read far more often than they are modified:
+    /// maintains an [`Arc`] snapshot of the wrapped variable that
+    /// [`CondSync::latest`] returns without touching the mutex.
+    ///
+    /// Every modification creates a new snapshot, i.e., it clones the variable,
+    /// while the lock is held.
+    ///
+    /// Only available with feature `snapshots`.
+    #[must_use]
+    pub fn snapshots(mut self) -> Self {
+        self.init.push(|cond_sync| {
+            let value = cond_sync.0.lock_unpoisoned();
+            let slot = crate::snapshot::SnapshotSlot::new(&*value);
+            cond_sync.0.snapshot.set(slot).ok();
+        });
+        self
+    }
+}
+
+#[cfg(feature = "optimistic_reads")]
+#[cfg_attr(docsrs, doc(cfg(feature = "optimistic_reads")))]
+impl<T> CondSyncBuilder<T>
+where
+    T: SeqLockable,
+{
+    /// For small states that are read far more often than they are modified:
+    /// maintains a copy of the wrapped variable that
+    /// [`CondSync::read_optimistic`] can read without touching the mutex.
+    ///
+    /// The copy is updated with every modification, while the lock is held,
+    /// so waiting and notifying work as before.
+    ///
+    /// Only available with feature `optimistic_reads`.
+    #[must_use]
+    pub fn optimistic_reads(mut self) -> Self {
+        self.init.push(|cond_sync| {
+            let value = cond_sync.0.lock_unpoisoned();
+            let mirror = Mirror::new(T::WORDS, write_words::<T>);
+            mirror.update(&value);
+            cond_sync.0.mirror.set(mirror).ok();
+        });
+        self
+    }
+}
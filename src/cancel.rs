@@ -0,0 +1,119 @@
+use crate::Wake;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, PoisonError, Weak,
+};
+
+/// A token for cooperative, hierarchical cancellation.
+///
+/// Cancelling a token also cancels all its [children](CancelToken::child), and their
+/// children, but not its parent.
+/// Threads that wait with
+/// [`CondSync::wait_until_or_cancelled`](crate::CondSync::wait_until_or_cancelled)
+/// return with [`Reason::Cancelled`](crate::Reason::Cancelled) when the token
+/// is cancelled.
+///
+/// Clones share the same cancellation state.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CancelToken, CondSync};
+/// use std::thread;
+///
+/// let root = CancelToken::new();
+/// let cond_sync = CondSync::new(false);
+///
+/// let handles: Vec<_> = (0..3)
+///     .map(|_| {
+///         let (token, cond_sync_t) = (root.child(), cond_sync.clone());
+///         thread::spawn(move || {
+///             let reason = cond_sync_t.wait_until_or_cancelled(|v| *v, &token).unwrap();
+///             assert!(reason.is_cancelled());
+///         })
+///     })
+///     .collect();
+///
+/// root.cancel();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Node>);
+
+#[derive(Default)]
+struct Node {
+    cancelled: AtomicBool,
+    children: Mutex<Vec<Weak<Node>>>,
+    // the instances that threads are waiting on with this token
+    wakers: Mutex<Vec<Weak<dyn Wake>>>,
+}
+
+impl CancelToken {
+    /// Construct a new, not cancelled instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a child token, which is cancelled when this token is cancelled.
+    ///
+    /// A child of a cancelled token is cancelled from the start.
+    #[must_use]
+    pub fn child(&self) -> Self {
+        let child = Self::new();
+        let mut children = self
+            .0
+            .children
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if self.is_cancelled() {
+            child.0.cancelled.store(true, Ordering::SeqCst);
+        } else {
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Arc::downgrade(&child.0));
+        }
+        child
+    }
+
+    /// Cancels this token and all its descendants,
+    /// and wakes up the threads that are waiting with them.
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    /// Returns `true` if this token was cancelled, directly or via an ancestor.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn register(&self, waker: Weak<dyn Wake>) {
+        let mut wakers = self.0.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        wakers.retain(|w| w.strong_count() > 0);
+        if !wakers.iter().any(|w| Weak::ptr_eq(w, &waker)) {
+            wakers.push(waker);
+        }
+    }
+}
+
+impl Node {
+    fn cancel(&self) {
+        let children = {
+            let mut children = self.children.lock().unwrap_or_else(PoisonError::into_inner);
+            if self.cancelled.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            std::mem::take(&mut *children)
+        };
+        let wakers =
+            std::mem::take(&mut *self.wakers.lock().unwrap_or_else(PoisonError::into_inner));
+        for waker in wakers.iter().filter_map(Weak::upgrade) {
+            waker.wake_all();
+        }
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
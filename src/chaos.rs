@@ -0,0 +1,187 @@
+use crate::{rng::Rng, timer, Other, Wake};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{OnceLock, Weak},
+    time::{Duration, Instant},
+};
+
+/// Random disturbances of the notifications of a [`CondSync`](crate::CondSync),
+/// to explore different interleavings of threads in tests.
+///
+/// Is applied with [`CondSyncBuilder::chaos`](crate::CondSyncBuilder::chaos).
+/// All decisions are derived from the seed, so combined with
+/// [`explore_interleavings`], a failing run can be reproduced by its seed
+/// (as far as the scheduling of the threads allows).
+///
+/// Only available with feature `testing`.
+#[derive(Clone, Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub struct Chaos {
+    seed: u64,
+    max_notify_delay: Duration,
+    notify_all_probability: f64,
+    lost_notify_one_probability: f64,
+    max_spurious_interval: Option<Duration>,
+}
+impl Chaos {
+    /// Construct a new instance that does not disturb anything yet.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            max_notify_delay: Duration::ZERO,
+            notify_all_probability: 0.0,
+            lost_notify_one_probability: 0.0,
+            max_spurious_interval: None,
+        }
+    }
+
+    /// Delays each notification by a random duration of up to `max`.
+    ///
+    /// The delayed notifications are sent by the crate's internal timer thread.
+    #[must_use]
+    pub fn notify_delay(mut self, max: Duration) -> Self {
+        self.max_notify_delay = max;
+        self
+    }
+
+    /// Turns notifications for [`Other::One`] with the given probability
+    /// into notifications for [`Other::All`].
+    #[must_use]
+    pub fn notify_all_probability(mut self, probability: f64) -> Self {
+        self.notify_all_probability = probability;
+        self
+    }
+
+    /// Drops notifications for [`Other::One`] with the given probability, to simulate
+    /// lost wakeups.
+    ///
+    /// Code that is robust against lost wakeups uses timed waits, or notifies all threads.
+    #[must_use]
+    pub fn lost_notify_one_probability(mut self, probability: f64) -> Self {
+        self.lost_notify_one_probability = probability;
+        self
+    }
+
+    /// Wakes up waiting threads spuriously, after random intervals of up to `max`.
+    ///
+    /// Such wakeups happen without any notification and without any modification;
+    /// they reveal code that relies on each wakeup reflecting a real change, e.g. code that
+    /// treats each [`Reason::Notification`](crate::Reason::Notification) of
+    /// [`CondSync::wait_timeout`](crate::CondSync::wait_timeout) as a modification,
+    /// or conditions that count their evaluations.
+    #[must_use]
+    pub fn spurious_wakeups(mut self, max: Duration) -> Self {
+        self.max_spurious_interval = Some(max);
+        self
+    }
+}
+
+pub(crate) struct ChaosState {
+    chaos: Chaos,
+    rng: Rng,
+    waker: OnceLock<Weak<dyn Wake>>,
+}
+impl ChaosState {
+    pub(crate) fn new(chaos: Chaos) -> Self {
+        Self {
+            rng: Rng::new(Some(chaos.seed)),
+            chaos,
+            waker: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn set_waker(&self, waker: Weak<dyn Wake>) {
+        self.waker.set(waker).ok();
+    }
+
+    // Returns the notification that is to be sent now, or None if it was deferred or dropped.
+    pub(crate) fn intervene(&self, other: Other) -> Option<Other> {
+        if other == Other::One && self.chance(self.chaos.lost_notify_one_probability) {
+            return None;
+        }
+        let other = if other == Other::One && self.chance(self.chaos.notify_all_probability) {
+            Other::All
+        } else {
+            other
+        };
+        let delay = self.rng.duration_up_to(self.chaos.max_notify_delay);
+        let (Some(waker), Some(at)) = (self.waker.get(), Instant::now().checked_add(delay)) else {
+            return Some(other);
+        };
+        if delay.is_zero() {
+            return Some(other);
+        }
+        let waker = Weak::clone(waker);
+        timer::schedule(
+            at,
+            Box::new(move || {
+                if let Some(waker) = waker.upgrade() {
+                    waker.wake(other);
+                }
+            }),
+        );
+        None
+    }
+
+    pub(crate) fn spurious_wakeup_after(&self) -> Option<Duration> {
+        self.chaos
+            .max_spurious_interval
+            .map(|max| self.rng.duration_up_to(max))
+    }
+
+    // returns true with the given probability
+    fn chance(&self, probability: f64) -> bool {
+        // the precision of f64 is good enough here
+        #[allow(clippy::cast_precision_loss)]
+        let sample = self.rng.next_u64() as f64 / u64::MAX as f64;
+        sample < probability
+    }
+}
+
+/// Runs the given test repeatedly, with the seeds `0..runs`, which are meant to be used
+/// for [`Chaos`].
+///
+/// If a run panics, this function panics with a message that contains the seed of the
+/// failing run, so that the failure can be reproduced.
+///
+/// Only available with feature `testing`.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{explore_interleavings, Chaos, CondSync, Other};
+/// use std::{thread, time::Duration};
+///
+/// explore_interleavings(10, |seed| {
+///     let cond_sync = CondSync::builder(0_usize)
+///         .chaos(Chaos::new(seed).notify_delay(Duration::from_millis(1)))
+///         .build();
+///     let cond_sync_t = cond_sync.clone();
+///     let handle = thread::spawn(move || {
+///         cond_sync_t.modify_and_notify(|v| *v += 1, Other::One).unwrap();
+///     });
+///     cond_sync.wait_until(|v| *v == 1).unwrap();
+///     handle.join().unwrap();
+/// });
+/// ```
+///
+/// ## Panics
+///
+/// Panics if a run of the test panicked.
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub fn explore_interleavings<F>(runs: u64, test: F)
+where
+    F: Fn(u64),
+{
+    for seed in 0..runs {
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| test(seed))) {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            panic!("run with seed {seed} failed: {message}");
+        }
+    }
+}
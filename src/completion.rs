@@ -0,0 +1,157 @@
+use crate::{CondSync, Other, PoisonedError, Reason};
+use std::{cell::Cell, sync::Arc, time::Duration};
+
+/// Tracks the completion of a task that can consist of sub-tasks.
+///
+/// A `Completion` is complete when it was marked with [`Completion::complete`]
+/// and all its children (see [`Completion::child`]) are complete.
+/// Waiting on a node thus covers its whole subtree.
+///
+/// Clones refer to the same node.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Completion;
+/// use std::thread;
+///
+/// let stage = Completion::new();
+/// for i in 0..3 {
+///     let sub_task = stage.child().unwrap();
+///     thread::spawn(move || {
+///         println!("Sub-task {i}: working ...");
+///         sub_task.complete().unwrap();
+///     });
+/// }
+/// stage.complete().unwrap(); // <- the stage itself has nothing more to do
+///
+/// stage.wait_until_complete().unwrap(); // <- returns when all sub-tasks are done
+/// ```
+#[derive(Clone)]
+pub struct Completion(Arc<Node>);
+
+struct Node {
+    cond_sync: CondSync<State>,
+    parent: Option<Completion>,
+}
+
+#[derive(Default)]
+struct State {
+    done: bool,
+    open_children: usize,
+}
+impl State {
+    fn is_complete(&self) -> bool {
+        self.done && self.open_children == 0
+    }
+}
+
+impl Completion {
+    /// Construct a new root node.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_parent(None)
+    }
+
+    fn with_parent(parent: Option<Completion>) -> Self {
+        Self(Arc::new(Node {
+            cond_sync: CondSync::new(State::default()),
+            parent,
+        }))
+    }
+
+    /// Creates a child node; this node is not complete before the child is complete.
+    ///
+    /// Children should be created before the node itself is complete;
+    /// a child of an already complete node does not affect the node anymore.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn child(&self) -> Result<Completion, PoisonedError> {
+        let registered = Cell::new(false);
+        self.0.cond_sync.modify_and_notify(
+            |state| {
+                if !state.is_complete() {
+                    state.open_children += 1;
+                    registered.set(true);
+                }
+            },
+            Other::All,
+        )?;
+        Ok(Self::with_parent(if registered.get() {
+            Some(self.clone())
+        } else {
+            None
+        }))
+    }
+
+    /// Marks the node's own work as done.
+    ///
+    /// If all children are complete as well, the node becomes complete,
+    /// which is propagated to its parent.
+    /// Calling this method more than once has no further effect.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if one of the internally used mutexes is poisoned.
+    pub fn complete(&self) -> Result<(), PoisonedError> {
+        self.update(|state| state.done = true)
+    }
+
+    fn update(&self, f: fn(&mut State)) -> Result<(), PoisonedError> {
+        let became_complete = Cell::new(false);
+        self.0.cond_sync.modify_and_notify(
+            |state| {
+                let was_complete = state.is_complete();
+                f(state);
+                became_complete.set(!was_complete && state.is_complete());
+            },
+            Other::All,
+        )?;
+        match self.0.parent {
+            // the own lock is released at this point
+            Some(ref parent) if became_complete.get() => {
+                parent.update(|state| state.open_children -= 1)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the node and all its children are complete.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.0.cond_sync.0.lock_unpoisoned().is_complete()
+    }
+
+    /// Blocks the current thread until the node and all its children are complete.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn wait_until_complete(&self) -> Result<(), PoisonedError> {
+        self.0.cond_sync.wait_until(State::is_complete)?;
+        Ok(())
+    }
+
+    /// Blocks the current thread until the node and all its children are complete,
+    /// but no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn wait_until_complete_or_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<Reason, PoisonedError> {
+        self.0
+            .cond_sync
+            .wait_until_or_timeout(State::is_complete, duration)
+    }
+}
+
+impl Default for Completion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{remaining_until, CondSync, Memoized, OperationKind, PoisonedError, Reason};
+use std::{
+    fmt,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
+};
+
+type Predicate<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// A named condition that is registered once on a [`CondSync`], and can then be waited for
+/// by many threads; see [`CondSync::condition`].
+///
+/// Defining the condition in one place keeps the call sites that wait for it consistent.
+/// Its name is used as the label of its waits, which shows up in the interceptors
+/// (see [`Operation::label`](crate::Operation::label)) and,
+/// with feature `diagnostics`, in the information about waiting threads.
+///
+/// Like a [`Memoized`] condition, it is evaluated at most once per
+/// [generation](CondSync::generation) of the wrapped variable: when many threads wait for it
+/// and are woken up together, e.g. with [`Other::All`](crate::Other::All),
+/// the first of them evaluates it, and the others reuse its verdict.
+///
+/// Clones share the same condition and its cached verdict.
+///
+/// Only available with feature `generations`.
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+pub struct Condition<T> {
+    cond_sync: CondSync<T>,
+    shared: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    name: &'static str,
+    predicate: Memoized<Predicate<T>>,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+impl<T> CondSync<T> {
+    /// Registers a named condition that many threads can wait for, see [`Condition`].
+    ///
+    /// The condition must only depend on the wrapped variable,
+    /// because its verdict is reused until the next modification.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::thread;
+    ///
+    /// let cond_sync = CondSync::new(0_usize);
+    /// let batch_ready = cond_sync.condition("batch ready", |v| *v >= 3);
+    ///
+    /// let handles: Vec<_> = (0..10)
+    ///     .map(|_| {
+    ///         let batch_ready = batch_ready.clone();
+    ///         thread::spawn(move || batch_ready.wait().unwrap())
+    ///     })
+    ///     .collect();
+    /// for _ in 0..3 {
+    ///     cond_sync.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    /// }
+    /// for handle in handles {
+    ///     assert!(handle.join().unwrap().is_condition());
+    /// }
+    /// ```
+    ///
+    /// Only available with feature `generations`.
+    pub fn condition<F>(&self, name: &'static str, predicate: F) -> Condition<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        Condition {
+            cond_sync: self.clone(),
+            shared: Arc::new(Shared {
+                name,
+                predicate: self.memoized(Box::new(predicate)),
+            }),
+        }
+    }
+}
+
+impl<T> Condition<T> {
+    /// Returns the name of the condition.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.shared.name
+    }
+
+    /// Returns whether the condition is currently fulfilled, without blocking.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn is_met(&self) -> Result<bool, PoisonedError> {
+        let i = &self.cond_sync.0;
+        let mtx_guard = i.lock()?;
+        Ok(i.holding(|| self.is_met_locked(&mtx_guard)))
+    }
+
+    /// Blocks the current thread until the condition is fulfilled.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait(&self) -> Result<Reason, PoisonedError> {
+        self.wait_until("wait", None)
+    }
+
+    /// Blocks the current thread until the condition is fulfilled,
+    /// but no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_timeout(&self, duration: Duration) -> Result<Reason, PoisonedError> {
+        self.wait_until("wait_timeout", Instant::now().checked_add(duration))
+    }
+
+    #[track_caller]
+    fn wait_until(
+        &self,
+        method: &'static str,
+        end: Option<Instant>,
+    ) -> Result<Reason, PoisonedError> {
+        let i = &self.cond_sync.0;
+        i.intercept_labeled(OperationKind::Wait, method, Some(self.shared.name), || {
+            let mtx_guard = i.lock()?;
+            Ok(i.wait_for(
+                mtx_guard,
+                |v| self.is_met_locked(v).then_some(()),
+                remaining_until(end),
+            )?
+            .into_reason())
+        })
+    }
+
+    // must be called while holding the lock of the instance
+    fn is_met_locked(&self, value: &T) -> bool {
+        self.shared
+            .predicate
+            .evaluate(value, self.cond_sync.0.generation.load(Ordering::SeqCst))
+    }
+}
+
+impl<T> fmt::Debug for Condition<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Condition")
+            .field("name", &self.shared.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for Condition<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cond_sync: self.cond_sync.clone(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A scope for named worker threads, which are all joined when the scope ends,
+/// and whose panics are reported to the caller as errors.
+///
+/// Is created with [`CoordScope::run`], which is based on [`std::thread::scope`],
+/// so the workers can borrow from the caller's stack.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CoordScope, ScopeError};
+/// use std::{sync::Mutex, time::Duration};
+///
+/// let results = Mutex::new(Vec::new());
+/// let outcome = CoordScope::run(|scope| {
+///     for i in 0..3 {
+///         let results = &results;
+///         scope.spawn(format!("worker-{i}"), move || {
+///             if i == 2 {
+///                 panic!("out of luck");
+///             }
+///             results.lock().unwrap().push(i);
+///         })?;
+///     }
+///     scope.wait_all(Duration::from_secs(10))
+/// });
+///
+/// match outcome {
+///     Err(ScopeError::Panicked { worker, message }) => {
+///         assert_eq!(worker, "worker-2");
+///         assert_eq!(message, "out of luck");
+///     }
+///     _ => panic!("the panic of worker-2 was not reported"),
+/// }
+/// assert_eq!(results.lock().unwrap().len(), 2);
+/// ```
+pub struct CoordScope<'scope, 'env: 'scope> {
+    scope: &'scope thread::Scope<'scope, 'env>,
+    cond_sync: CondSync<State>,
+}
+
+#[derive(Default)]
+struct State {
+    running: usize,
+    // the first panic of a worker
+    panicked: Option<ScopeError>,
+}
+
+impl<'scope, 'env> CoordScope<'scope, 'env> {
+    /// Runs the given closure with a new scope, and returns its result
+    /// once all workers that were spawned in the scope have finished.
+    ///
+    /// ## Errors
+    ///
+    /// [`ScopeError::Panicked`] if a worker panicked; this takes precedence over the result
+    /// of the closure.
+    ///
+    /// Otherwise, the result of the closure.
+    pub fn run<F, R>(f: F) -> Result<R, ScopeError>
+    where
+        F: for<'s> FnOnce(&CoordScope<'s, 'env>) -> Result<R, ScopeError>,
+    {
+        let cond_sync = CondSync::new(State::default());
+        let result = thread::scope(|scope| {
+            f(&CoordScope {
+                scope,
+                cond_sync: cond_sync.clone(),
+            })
+        });
+        // all workers are joined at this point
+        let panicked = cond_sync.0.lock()?.panicked.take();
+        match panicked {
+            Some(panicked) => Err(panicked),
+            None => result,
+        }
+    }
+
+    /// Spawns a worker thread with the given name.
+    ///
+    /// A panic of the worker is caught, and reported by [`CoordScope::wait_all`]
+    /// and [`CoordScope::run`].
+    ///
+    /// ## Errors
+    ///
+    /// [`ScopeError::Poisoned`] if the internally used mutex is poisoned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    pub fn spawn<F>(&self, name: impl Into<String>, f: F) -> Result<(), ScopeError>
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        let name = name.into();
+        self.cond_sync
+            .modify_and_notify(|state| state.running += 1, Other::All)?;
+        let cond_sync = self.cond_sync.clone();
+        thread::Builder::new()
+            .name(name.clone())
+            .spawn_scoped(self.scope, move || {
+                let outcome = catch_unwind(AssertUnwindSafe(f));
+                let mut panicked = outcome.err().map(|payload| ScopeError::Panicked {
+                    worker: name,
+                    message: payload
+                        .downcast_ref::<&str>()
+                        .map(ToString::to_string)
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default(),
+                });
+                cond_sync
+                    .modify_and_notify(
+                        |state| {
+                            state.running -= 1;
+                            if state.panicked.is_none() {
+                                state.panicked = panicked.take();
+                            }
+                        },
+                        Other::All,
+                    )
+                    .ok();
+            })
+            .expect("failed to spawn thread");
+        Ok(())
+    }
+
+    /// Blocks the current thread until all workers that were spawned so far have finished,
+    /// or one of them panicked, but no longer than the given duration.
+    ///
+    /// Workers that are still running when this method returns are joined at the end of
+    /// the scope nevertheless.
+    ///
+    /// ## Errors
+    ///
+    /// [`ScopeError::Panicked`] if a worker panicked.
+    ///
+    /// [`ScopeError::Timeout`] if not all workers finished in time.
+    ///
+    /// [`ScopeError::Poisoned`] if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn wait_all(&self, timeout: Duration) -> Result<(), ScopeError> {
+        let i = &self.cond_sync.0;
+        let (result, _) = i.intercept(OperationKind::Wait, "wait_all", || {
+            let mtx_guard = i.lock()?;
+            let waited = i.wait_for(
+                mtx_guard,
+                |state| (state.panicked.is_some() || state.running == 0).then_some(()),
+                remaining_until(Instant::now().checked_add(timeout)),
+            )?;
+            let reason = waited.reason();
+            let result = match waited.mtx_guard.panicked {
+                Some(ref panicked) => Err(panicked.clone()),
+                None if waited.outcome.is_none() => Err(ScopeError::Timeout {
+                    running: waited.mtx_guard.running,
+                }),
+                None => Ok(()),
+            };
+            waited.finish();
+            Ok((result, reason))
+        })?;
+        result
+    }
+}
+
+/// Error of [`CoordScope`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScopeError {
+    /// A worker panicked.
+    Panicked {
+        /// The name of the worker.
+        worker: String,
+        /// The panic message, or an empty string if the panic payload was not a string.
+        message: String,
+    },
+    /// Not all workers finished in time.
+    Timeout {
+        /// The number of workers that were still running.
+        running: usize,
+    },
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for ScopeError {
+    fn from(e: PoisonedError) -> ScopeError {
+        match e {
+            PoisonedError::Poisoned => ScopeError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => ScopeError::WouldDeadlock { instance },
+        }
+    }
+}
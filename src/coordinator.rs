@@ -0,0 +1,439 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Collects the success or failure of a set of workers, e.g. during initialization.
+///
+/// Each worker gets a [`Participant`] with [`Coordinator::add_participant`],
+/// and reports either [`Participant::done`] or [`Participant::fail`].
+/// The coordinating thread waits with [`Coordinator::wait_until_all_ok`] until
+/// all participants succeeded, or until the first one failed;
+/// or it waits with [`Coordinator::wait_until_all_done`] until all participants reported,
+/// and gets all failures.
+/// A participant that is dropped without reporting counts as failed, see
+/// [`CoordinationError::Abandoned`].
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Coordinator;
+///
+/// let coordinator = Coordinator::<String>::new();
+///
+/// for i in 0..5 {
+///     let participant = coordinator.add_participant();
+///     std::thread::spawn(move || {
+///         // ...initialize...
+///         participant.done();
+///         // ...do real work...
+///     });
+/// }
+/// // wait until all threads are initialized
+/// coordinator.wait_until_all_ok().unwrap();
+/// ```
+pub struct Coordinator<E>(CondSync<State<E>>);
+
+struct State<E> {
+    participants: Vec<ParticipantId>,
+    done: Vec<bool>,
+    // in the order of reporting
+    failures: Vec<Failure<E>>,
+}
+// A failure without an error is an abandoned participant.
+struct Failure<E> {
+    participant: ParticipantId,
+    error: Option<E>,
+}
+impl<E> State<E> {
+    fn outstanding(&self) -> Vec<ParticipantId> {
+        (self.participants.iter())
+            .zip(&self.done)
+            .filter(|(_, done)| !**done)
+            .map(|(participant, _)| participant.clone())
+            .collect()
+    }
+}
+
+impl<E> Coordinator<E> {
+    /// Construct a new instance without participants.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CondSync::new(State {
+            participants: Vec::new(),
+            done: Vec::new(),
+            failures: Vec::new(),
+        }))
+    }
+
+    /// Registers a new participant.
+    ///
+    /// Participants should be registered before the coordinating thread starts waiting.
+    #[must_use]
+    pub fn add_participant(&self) -> Participant<E> {
+        self.register(None)
+    }
+
+    /// Registers a new participant with a name, which is reported
+    /// in [`CoordinationError`]s.
+    ///
+    /// Participants should be registered before the coordinating thread starts waiting.
+    #[must_use]
+    pub fn add_named_participant<S: Into<String>>(&self, name: S) -> Participant<E> {
+        self.register(Some(name.into()))
+    }
+
+    fn register(&self, name: Option<String>) -> Participant<E> {
+        let mut id = None;
+        self.0
+            .modify_and_notify(
+                |state| {
+                    let participant_id = ParticipantId {
+                        index: state.participants.len(),
+                        name,
+                    };
+                    state.participants.push(participant_id.clone());
+                    state.done.push(false);
+                    id = Some(participant_id);
+                },
+                Other::All,
+            )
+            .ok();
+        Participant {
+            cond_sync: self.0.clone(),
+            reported: false,
+            // if the mutex is poisoned, reporting has no effect anyway
+            id: id.unwrap_or(ParticipantId {
+                index: usize::MAX,
+                name: None,
+            }),
+        }
+    }
+
+    /// Returns the participants that did not report yet.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn outstanding(&self) -> Result<Vec<ParticipantId>, PoisonedError> {
+        Ok(self.0 .0.lock()?.outstanding())
+    }
+}
+
+impl<E: Clone> Coordinator<E> {
+    /// Blocks the current thread until all participants reported success,
+    /// or one of them reported a failure.
+    ///
+    /// ## Errors
+    ///
+    /// [`CoordinationError::Failed`] or [`CoordinationError::Abandoned`] with the first
+    /// reported failure.
+    ///
+    /// [`CoordinationError::Poisoned`] if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn wait_until_all_ok(&self) -> Result<(), CoordinationError<E>> {
+        self.wait("wait_until_all_ok", None)
+    }
+
+    /// Like [`Coordinator::wait_until_all_ok`], but waits no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// [`CoordinationError::Failed`] or [`CoordinationError::Abandoned`] with the first
+    /// reported failure.
+    ///
+    /// [`CoordinationError::Timeout`] with the participants that did not report in time.
+    ///
+    /// [`CoordinationError::Poisoned`] if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn wait_until_all_ok_or_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<(), CoordinationError<E>> {
+        self.wait(
+            "wait_until_all_ok_or_timeout",
+            Instant::now().checked_add(duration),
+        )
+    }
+
+    #[track_caller]
+    fn wait(&self, method: &'static str, end: Option<Instant>) -> Result<(), CoordinationError<E>> {
+        let i = &self.0 .0;
+        let (result, _) = i.intercept(OperationKind::Wait, method, || {
+            let mtx_guard = i.lock()?;
+            let waited = i.wait_for(
+                mtx_guard,
+                |state| (!state.failures.is_empty() || state.done.iter().all(|d| *d)).then_some(()),
+                remaining_until(end),
+            )?;
+            let reason = waited.reason();
+            let result = if let Some(failure) = waited.mtx_guard.failures.first() {
+                let participant = failure.participant.clone();
+                Err(match failure.error {
+                    Some(ref error) => CoordinationError::Failed {
+                        participant,
+                        error: error.clone(),
+                    },
+                    None => CoordinationError::Abandoned { participant },
+                })
+            } else if waited.outcome.is_none() {
+                Err(CoordinationError::Timeout {
+                    outstanding: waited.mtx_guard.outstanding(),
+                })
+            } else {
+                Ok(())
+            };
+            waited.finish();
+            Ok((result, reason))
+        })?;
+        result
+    }
+
+    /// Blocks the current thread until all participants reported, successfully or not.
+    ///
+    /// ## Returns
+    ///
+    /// A report with all failures that were reported so far.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn wait_until_all_done(&self) -> Result<CoordinationReport<E>, PoisonedError> {
+        self.wait_done("wait_until_all_done", None)
+    }
+
+    /// Like [`Coordinator::wait_until_all_done`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// A report with all failures that were reported so far,
+    /// and with the participants that did not report in time.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn wait_until_all_done_or_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<CoordinationReport<E>, PoisonedError> {
+        self.wait_done(
+            "wait_until_all_done_or_timeout",
+            Instant::now().checked_add(duration),
+        )
+    }
+
+    #[track_caller]
+    fn wait_done(
+        &self,
+        method: &'static str,
+        end: Option<Instant>,
+    ) -> Result<CoordinationReport<E>, PoisonedError> {
+        let i = &self.0 .0;
+        let (report, _) = i.intercept(OperationKind::Wait, method, || {
+            let mtx_guard = i.lock()?;
+            let waited = i.wait_for(
+                mtx_guard,
+                |state| state.done.iter().all(|d| *d).then_some(()),
+                remaining_until(end),
+            )?;
+            let reason = waited.reason();
+            let mut report = CoordinationReport {
+                failures: Vec::new(),
+                abandoned: Vec::new(),
+                outstanding: waited.mtx_guard.outstanding(),
+            };
+            for failure in &waited.mtx_guard.failures {
+                let participant = failure.participant.clone();
+                match failure.error {
+                    Some(ref error) => report.failures.push((participant, error.clone())),
+                    None => report.abandoned.push(participant),
+                }
+            }
+            waited.finish();
+            Ok((report, reason))
+        })?;
+        Ok(report)
+    }
+}
+
+impl<E> Clone for Coordinator<E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<E> Default for Coordinator<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A worker's handle for reporting to a [`Coordinator`].
+///
+/// Dropping it without reporting, e.g. when the worker panics,
+/// is reported as [`CoordinationError::Abandoned`].
+pub struct Participant<E> {
+    cond_sync: CondSync<State<E>>,
+    id: ParticipantId,
+    reported: bool,
+}
+
+impl<E> Participant<E> {
+    /// The identification of the participant.
+    #[must_use]
+    pub fn id(&self) -> &ParticipantId {
+        &self.id
+    }
+
+    /// Reports success.
+    pub fn done(mut self) {
+        self.report(None, false);
+    }
+
+    /// Reports a failure.
+    pub fn fail(mut self, error: E) {
+        self.report(Some(error), true);
+    }
+
+    fn report(&mut self, error: Option<E>, failed: bool) {
+        self.reported = true;
+        let id = &self.id;
+        self.cond_sync
+            .modify_and_notify(
+                |state| {
+                    let Some(done) = state.done.get_mut(id.index) else {
+                        return;
+                    };
+                    *done = true;
+                    if failed {
+                        state.failures.push(Failure {
+                            participant: id.clone(),
+                            error,
+                        });
+                    }
+                },
+                Other::All,
+            )
+            .ok();
+    }
+}
+
+impl<E> Drop for Participant<E> {
+    fn drop(&mut self) {
+        if !self.reported {
+            self.report(None, true);
+        }
+    }
+}
+
+/// The result of [`Coordinator::wait_until_all_done`] and
+/// [`Coordinator::wait_until_all_done_or_timeout`].
+#[derive(Debug)]
+pub struct CoordinationReport<E> {
+    failures: Vec<(ParticipantId, E)>,
+    abandoned: Vec<ParticipantId>,
+    outstanding: Vec<ParticipantId>,
+}
+impl<E> CoordinationReport<E> {
+    /// Returns `true` if all participants reported success.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty() && self.abandoned.is_empty() && self.outstanding.is_empty()
+    }
+
+    /// The reported failures, in the order of reporting.
+    #[must_use]
+    pub fn failures(&self) -> &[(ParticipantId, E)] {
+        &self.failures
+    }
+
+    /// The participants that were dropped without reporting.
+    #[must_use]
+    pub fn abandoned(&self) -> &[ParticipantId] {
+        &self.abandoned
+    }
+
+    /// The participants that did not report in time.
+    #[must_use]
+    pub fn outstanding(&self) -> &[ParticipantId] {
+        &self.outstanding
+    }
+
+    /// Consumes the report and returns the reported failures.
+    #[must_use]
+    pub fn into_failures(self) -> Vec<(ParticipantId, E)> {
+        self.failures
+    }
+}
+
+/// Identifies a [`Participant`] of a [`Coordinator`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParticipantId {
+    index: usize,
+    name: Option<String>,
+}
+impl ParticipantId {
+    /// The index of the participant, in the order of registration.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the participant, if it was registered with a name.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+impl fmt::Display for ParticipantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(f, "{name}"),
+            None => write!(f, "participant #{}", self.index),
+        }
+    }
+}
+
+/// Error of [`Coordinator::wait_until_all_ok`] and
+/// [`Coordinator::wait_until_all_ok_or_timeout`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum CoordinationError<E> {
+    /// A participant reported a failure.
+    Failed {
+        /// The participant that failed.
+        participant: ParticipantId,
+        /// The reported error.
+        error: E,
+    },
+    /// A participant was dropped without reporting, e.g. because its thread panicked.
+    Abandoned {
+        /// The participant that was dropped.
+        participant: ParticipantId,
+    },
+    /// Not all participants reported in time.
+    Timeout {
+        /// The participants that did not report.
+        outstanding: Vec<ParticipantId>,
+    },
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl<E> From<PoisonedError> for CoordinationError<E> {
+    fn from(e: PoisonedError) -> CoordinationError<E> {
+        match e {
+            PoisonedError::Poisoned => CoordinationError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => {
+                CoordinationError::WouldDeadlock { instance }
+            }
+        }
+    }
+}
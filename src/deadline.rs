@@ -0,0 +1,83 @@
+use crate::Wake;
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// A deadline for a timed wait that can be changed while the wait is in progress.
+///
+/// Created with [`CondSync::deadline`](crate::CondSync::deadline) and used with
+/// [`CondSync::wait_until_or_deadline`](crate::CondSync::wait_until_or_deadline).
+/// Clones share the same deadline, so e.g. a supervisor thread can
+/// [`extend`](Deadline::extend) it or [`expire_now`](Deadline::expire_now) it
+/// while another thread is blocked.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::CondSync;
+/// use std::{thread, time::Duration};
+///
+/// let cond_sync = CondSync::new(false);
+/// let deadline = cond_sync.deadline(Duration::from_secs(60));
+///
+/// let deadline_t = deadline.clone();
+/// thread::spawn(move || {
+///     // we learned that there is no point in waiting any longer
+///     deadline_t.expire_now();
+/// });
+///
+/// let reason = cond_sync.wait_until_or_deadline(|v| *v, &deadline).unwrap();
+/// assert!(reason.is_timeout());
+/// ```
+#[derive(Clone)]
+pub struct Deadline(Arc<DeadlineInner>);
+
+struct DeadlineInner {
+    // `None` if the deadline lies too far in the future to be represented
+    end: Mutex<Option<Instant>>,
+    waker: Arc<dyn Wake>,
+}
+
+impl Deadline {
+    pub(crate) fn new(duration: Duration, waker: Arc<dyn Wake>) -> Self {
+        Self(Arc::new(DeadlineInner {
+            end: Mutex::new(Instant::now().checked_add(duration)),
+            waker,
+        }))
+    }
+
+    /// Moves the deadline further into the future by the given duration.
+    pub fn extend(&self, duration: Duration) {
+        self.modify(|end| *end = end.and_then(|end| end.checked_add(duration)));
+    }
+
+    /// Lets the deadline expire immediately.
+    pub fn expire_now(&self) {
+        self.modify(|end| *end = Some(Instant::now()));
+    }
+
+    /// Returns the time that is left until the deadline is reached.
+    ///
+    /// Returns [`Duration::MAX`] if the deadline lies too far in the future to be represented.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.remaining_if_any().unwrap_or(Duration::MAX)
+    }
+
+    // `None` if the deadline never expires
+    pub(crate) fn remaining_if_any(&self) -> Option<Duration> {
+        self.end()
+            .map(|end| end.saturating_duration_since(Instant::now()))
+    }
+
+    fn end(&self) -> Option<Instant> {
+        *self.0.end.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn modify<F: FnOnce(&mut Option<Instant>)>(&self, f: F) {
+        f(&mut self.0.end.lock().unwrap_or_else(PoisonError::into_inner));
+        // the lock on `end` must be released before the waiters are woken up
+        self.0.waker.wake_all();
+    }
+}
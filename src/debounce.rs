@@ -0,0 +1,94 @@
+use crate::{
+    timer::{self, TimerHandle},
+    Other, Wake,
+};
+use std::{
+    sync::{Arc, Mutex, OnceLock, PoisonError, Weak},
+    time::{Duration, Instant},
+};
+
+// Merges notifications that follow each other closely into one delayed wakeup.
+//
+// The first deferred notification opens a window; each further notification extends it,
+// but the wakeup happens at the latest `max_latency` after the first deferred notification.
+pub(crate) struct Debouncer {
+    waker: OnceLock<Weak<dyn Wake>>,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    window: Duration,
+    max_latency: Duration,
+    first: Option<Instant>,
+    notify_all: bool,
+    timer_handle: Option<TimerHandle>,
+}
+
+impl Debouncer {
+    pub(crate) fn new(window: Duration, max_latency: Duration) -> Self {
+        Self {
+            waker: OnceLock::new(),
+            state: Arc::new(Mutex::new(State {
+                window,
+                max_latency,
+                first: None,
+                notify_all: false,
+                timer_handle: None,
+            })),
+        }
+    }
+
+    // Applies to windows that are opened from now on.
+    pub(crate) fn set_timings(&self, window: Duration, max_latency: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.window = window;
+        state.max_latency = max_latency;
+    }
+
+    pub(crate) fn set_waker(&self, waker: Weak<dyn Wake>) {
+        self.waker.set(waker).ok();
+    }
+
+    // Defers the notification; returns false if that is not possible,
+    // so that the caller has to notify immediately.
+    pub(crate) fn defer(&self, other: Other) -> bool {
+        let Some(waker) = self.waker.get() else {
+            return false;
+        };
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.notify_all |= other == Other::All;
+        let first = *state.first.get_or_insert(now);
+        if let Some(ref timer_handle) = state.timer_handle {
+            if !timer_handle.cancel() {
+                // the wakeup is already running and will take our notification along
+                return true;
+            }
+        }
+        let Some(at) = now
+            .checked_add(state.window)
+            .into_iter()
+            .chain(first.checked_add(state.max_latency))
+            .min()
+        else {
+            return false;
+        };
+
+        let (state_t, waker_t) = (Arc::clone(&self.state), Weak::clone(waker));
+        state.timer_handle = Some(timer::schedule(
+            at,
+            Box::new(move || {
+                let notify_all = {
+                    let mut state = state_t.lock().unwrap_or_else(PoisonError::into_inner);
+                    state.first = None;
+                    state.timer_handle = None;
+                    std::mem::take(&mut state.notify_all)
+                };
+                if let Some(waker) = waker_t.upgrade() {
+                    waker.wake(if notify_all { Other::All } else { Other::One });
+                }
+            }),
+        ));
+        true
+    }
+}
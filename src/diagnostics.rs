@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    panic::Location,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, PoisonError,
+    },
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
+
+// All waits that are currently in progress, in all instances.
+static REGISTRY: Mutex<BTreeMap<u64, WaitInfo>> = Mutex::new(BTreeMap::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Information about a thread that is currently waiting.
+///
+/// Only available with feature `diagnostics`.
+#[derive(Clone, Debug)]
+pub struct WaitInfo {
+    instance: usize,
+    method: &'static str,
+    label: Option<&'static str>,
+    location: &'static Location<'static>,
+    since: Instant,
+    thread_name: Option<String>,
+    thread_id: ThreadId,
+}
+impl WaitInfo {
+    /// Identifies the [`CondSync`](crate::CondSync) instance that is waited on.
+    #[must_use]
+    pub fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// The name of the waiting method, like `"wait_until"`.
+    #[must_use]
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The label that was given to the wait, e.g. with
+    /// [`CondSync::wait_until_named`](crate::CondSync::wait_until_named).
+    #[must_use]
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// The call site of the waiting method.
+    #[must_use]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The name of the waiting thread, if it has one.
+    #[must_use]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread_name.as_deref()
+    }
+
+    /// The id of the waiting thread.
+    #[must_use]
+    pub fn thread_id(&self) -> ThreadId {
+        self.thread_id
+    }
+
+    /// How long the thread is waiting already.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.since.elapsed()
+    }
+}
+impl fmt::Display for WaitInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.thread_name {
+            Some(ref name) => write!(f, "thread '{name}'")?,
+            None => write!(f, "thread {:?}", self.thread_id)?,
+        }
+        write!(f, " waiting {:?} in {}", self.elapsed(), self.method)?;
+        if let Some(label) = self.label {
+            write!(f, " (\"{label}\")")?;
+        }
+        write!(f, " at {}", self.location)
+    }
+}
+
+/// Returns information about all threads that are currently waiting on any
+/// [`CondSync`](crate::CondSync), ordered by the start of their wait.
+///
+/// Only available with feature `diagnostics`.
+#[must_use]
+pub fn dump_all() -> Vec<WaitInfo> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .cloned()
+        .collect()
+}
+
+// Removes the wait from the registry when dropped.
+pub(crate) struct Registration(u64);
+impl Drop for Registration {
+    fn drop(&mut self) {
+        REGISTRY
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.0);
+    }
+}
+
+pub(crate) fn register(
+    instance: usize,
+    method: &'static str,
+    label: Option<&'static str>,
+    location: &'static Location<'static>,
+) -> Registration {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let wait_info = WaitInfo {
+        instance,
+        method,
+        label,
+        location,
+        since: Instant::now(),
+        thread_name: thread::current().name().map(ToString::to_string),
+        thread_id: thread::current().id(),
+    };
+    REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(id, wait_info);
+    Registration(id)
+}
@@ -0,0 +1,209 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::{
+    collections::VecDeque,
+    sync::mpsc,
+    thread::{self, ThreadId},
+    time::Duration,
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Runs closures on a dedicated thread, e.g. the main thread of a GUI application.
+///
+/// The dispatcher belongs to the thread that created it.
+/// Any thread can hand over closures with [`Dispatcher::invoke`] or
+/// [`Dispatcher::invoke_and_wait`];
+/// the owning thread runs them with [`Dispatcher::run_pending`] or
+/// [`Dispatcher::wait_and_run`].
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Dispatcher;
+/// use std::thread;
+///
+/// let dispatcher = Dispatcher::new();
+///
+/// let dispatcher_t = dispatcher.clone();
+/// let handle = thread::spawn(move || {
+///     // is executed on the main thread:
+///     dispatcher_t.invoke_and_wait(|| 6 * 7).unwrap()
+/// });
+///
+/// // [main thread] run the closure
+/// dispatcher.wait_and_run().unwrap();
+/// assert_eq!(handle.join().unwrap(), 42);
+/// ```
+#[derive(Clone)]
+pub struct Dispatcher {
+    queue: CondSync<VecDeque<Job>>,
+    owner: ThreadId,
+}
+
+impl Dispatcher {
+    /// Construct a new instance that belongs to the current thread.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            queue: CondSync::new(VecDeque::new()),
+            owner: thread::current().id(),
+        }
+    }
+
+    /// Returns `true` if the current thread is the one that owns the dispatcher.
+    #[must_use]
+    pub fn is_owner_thread(&self) -> bool {
+        thread::current().id() == self.owner
+    }
+
+    /// Hands over the given closure to the owning thread, without waiting for its execution.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn invoke<F>(&self, f: F) -> Result<(), PoisonedError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job: Job = Box::new(f);
+        self.queue
+            .modify_and_notify(|queue| queue.push_back(job), Other::One)
+    }
+
+    /// Hands over the given closure to the owning thread, and blocks the current thread
+    /// until it was executed.
+    ///
+    /// If called on the owning thread, the closure is executed directly.
+    ///
+    /// ## Errors
+    ///
+    /// `DispatchError::Poisoned` if the internally used mutex is poisoned.
+    ///
+    /// `DispatchError::Abandoned` if the closure was dropped without being executed,
+    /// or if it panicked.
+    pub fn invoke_and_wait<F, R>(&self, f: F) -> Result<R, DispatchError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        if self.is_owner_thread() {
+            return Ok(f());
+        }
+        let (sender, receiver) = mpsc::sync_channel(1);
+        self.invoke(move || {
+            sender.send(f()).ok();
+        })?;
+        receiver.recv().map_err(|_| DispatchError::Abandoned)
+    }
+
+    /// Runs all closures that are currently pending.
+    ///
+    /// ## Returns
+    ///
+    /// The number of closures that were run.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called on a thread that does not own the dispatcher.
+    pub fn run_pending(&self) -> Result<usize, PoisonedError> {
+        self.run(None)
+    }
+
+    /// Blocks the current thread until at least one closure is pending,
+    /// and then runs all pending closures.
+    ///
+    /// ## Returns
+    ///
+    /// The number of closures that were run.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called on a thread that does not own the dispatcher.
+    pub fn wait_and_run(&self) -> Result<usize, PoisonedError> {
+        self.assert_owner_thread();
+        self.queue.wait_until(|queue| !queue.is_empty())?;
+        self.run(None)
+    }
+
+    /// Like [`Dispatcher::wait_and_run`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The number of closures that were run, which is 0 if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called on a thread that does not own the dispatcher.
+    pub fn wait_and_run_or_timeout(&self, duration: Duration) -> Result<usize, PoisonedError> {
+        self.run(Some(duration))
+    }
+
+    fn assert_owner_thread(&self) {
+        assert!(
+            self.is_owner_thread(),
+            "the dispatcher can only be run on the thread that owns it"
+        );
+    }
+
+    fn run(&self, duration: Option<Duration>) -> Result<usize, PoisonedError> {
+        self.assert_owner_thread();
+        if let Some(duration) = duration {
+            self.queue
+                .wait_until_or_timeout(|queue| !queue.is_empty(), duration)?;
+        }
+        let mut jobs = VecDeque::new();
+        self.queue
+            .modify_and_notify(|queue| std::mem::swap(queue, &mut jobs), Other::One)?;
+        let count = jobs.len();
+        // the closures run without holding the lock, so they can invoke further closures
+        for job in jobs {
+            job();
+        }
+        Ok(count)
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error of [`Dispatcher::invoke_and_wait`].
+#[derive(Debug)]
+pub enum DispatchError {
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+    /// The closure was dropped without being executed, or it panicked.
+    Abandoned,
+}
+impl From<PoisonedError> for DispatchError {
+    fn from(e: PoisonedError) -> DispatchError {
+        match e {
+            PoisonedError::Poisoned => DispatchError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => DispatchError::WouldDeadlock { instance },
+        }
+    }
+}
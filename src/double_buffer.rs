@@ -0,0 +1,187 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::{
+    mem,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// The producer side of a double buffer, owns the back buffer.
+///
+/// The producer fills the back buffer, using [`DoubleBuffer::back_mut`],
+/// and then swaps it in as the new front buffer with [`DoubleBuffer::publish`].
+/// The consumer side, a [`DoubleBufferReader`], blocks in [`DoubleBufferReader::wait_fresh`]
+/// until a new front buffer is available.
+///
+/// Only two buffers exist; they are handed over between producer and consumer
+/// and never copied.
+/// If the consumer did not yet take the last published buffer, a new publish replaces it.
+/// If the consumer is still reading the previous front buffer, [`DoubleBuffer::publish`]
+/// blocks until it is done.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::DoubleBuffer;
+/// use std::thread;
+///
+/// let (mut producer, mut reader) = DoubleBuffer::new(Vec::<usize>::new(), Vec::new());
+///
+/// let consumer = thread::spawn(move || {
+///     let frame = reader.wait_fresh().unwrap();
+///     frame.len()
+/// });
+///
+/// let back = producer.back_mut();
+/// back.clear();
+/// back.extend(0..10);
+/// producer.publish().unwrap();
+///
+/// assert_eq!(consumer.join().unwrap(), 10);
+/// ```
+pub struct DoubleBuffer<T> {
+    back: T,
+    cond_sync: CondSync<Shared<T>>,
+}
+
+struct Shared<T> {
+    // published and not yet taken by the reader
+    front: Option<T>,
+    // released by the reader, can be reused by the producer
+    spare: Option<T>,
+}
+
+impl<T> DoubleBuffer<T> {
+    /// Creates the producer and the consumer side of a double buffer.
+    ///
+    /// `front` is the initial front buffer, which is not considered fresh;
+    /// `back` is the buffer the producer starts filling.
+    #[must_use]
+    pub fn new(front: T, back: T) -> (Self, DoubleBufferReader<T>) {
+        let cond_sync = CondSync::new(Shared {
+            front: None,
+            spare: Some(front),
+        });
+        (
+            Self {
+                back,
+                cond_sync: cond_sync.clone(),
+            },
+            DoubleBufferReader(cond_sync),
+        )
+    }
+
+    /// Gives access to the back buffer.
+    ///
+    /// Note that the back buffer contains whatever was in it when it was handed back,
+    /// so it usually needs to be cleared or overwritten completely.
+    pub fn back_mut(&mut self) -> &mut T {
+        &mut self.back
+    }
+
+    /// Swaps the back buffer in as the new front buffer and wakes up the reader.
+    ///
+    /// Blocks while the reader is still holding the previous front buffer.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn publish(&mut self) -> Result<(), PoisonedError> {
+        let back = &mut self.back;
+        self.cond_sync.wait_until_and_modify(
+            |shared| shared.front.is_some() || shared.spare.is_some(),
+            |shared| {
+                if let Some(ref mut front) = shared.front {
+                    // the reader did not take the last front buffer, so we replace it
+                    mem::swap(front, back);
+                } else {
+                    let spare = shared.spare.take().unwrap_or_else(|| unreachable!());
+                    shared.front = Some(mem::replace(back, spare));
+                }
+            },
+            Other::All,
+        )
+    }
+}
+
+/// The consumer side of a double buffer, see [`DoubleBuffer`].
+pub struct DoubleBufferReader<T>(CondSync<Shared<T>>);
+
+impl<T> DoubleBufferReader<T> {
+    /// Blocks the current thread until a new front buffer was published,
+    /// and gives access to it.
+    ///
+    /// The front buffer is handed back to the producer when the returned guard is dropped.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn wait_fresh(&mut self) -> Result<FrontGuard<'_, T>, PoisonedError> {
+        let mut taken = None;
+        self.0.wait_until_and_modify(
+            |shared| shared.front.is_some(),
+            |shared| taken = shared.front.take(),
+            Other::All,
+        )?;
+        Ok(self.guard(taken))
+    }
+
+    /// Like [`DoubleBufferReader::wait_fresh`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The guard for the new front buffer, or `None` if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn wait_fresh_or_timeout(
+        &mut self,
+        duration: Duration,
+    ) -> Result<Option<FrontGuard<'_, T>>, PoisonedError> {
+        if self
+            .0
+            .wait_until_or_timeout(|shared| shared.front.is_some(), duration)?
+            .is_timeout()
+        {
+            return Ok(None);
+        }
+        // we are the only consumer, and the producer never removes a front buffer
+        let mut taken = None;
+        self.0
+            .modify_and_notify(|shared| taken = shared.front.take(), Other::All)?;
+        Ok(Some(self.guard(taken)))
+    }
+
+    fn guard(&self, taken: Option<T>) -> FrontGuard<'_, T> {
+        FrontGuard {
+            buffer: Some(taken.unwrap_or_else(|| unreachable!("front buffer was checked"))),
+            cond_sync: &self.0,
+        }
+    }
+}
+
+/// Gives access to a fresh front buffer, hands it back to the producer when dropped.
+#[must_use = "the buffer is handed back immediately if the guard is not kept"]
+pub struct FrontGuard<'a, T> {
+    buffer: Option<T>,
+    cond_sync: &'a CondSync<Shared<T>>,
+}
+impl<T> Deref for FrontGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.buffer.as_ref().unwrap_or_else(|| unreachable!())
+    }
+}
+impl<T> DerefMut for FrontGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.buffer.as_mut().unwrap_or_else(|| unreachable!())
+    }
+}
+impl<T> Drop for FrontGuard<'_, T> {
+    fn drop(&mut self) {
+        let buffer = self.buffer.take();
+        self.cond_sync
+            .modify_and_notify(|shared| shared.spare = buffer, Other::All)
+            .ok();
+    }
+}
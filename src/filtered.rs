@@ -0,0 +1,104 @@
+use crate::{remaining_until, CondSync, OperationKind};
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+type StopCondition<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+
+/// A blocking iterator over the states of a [`CondSync`] that satisfy a predicate.
+///
+/// Is created with [`CondSync::filtered`].
+///
+/// Each call to `next()` blocks until the wrapped variable was modified after the last
+/// yielded state (or after the creation of the iterator), and the predicate holds,
+/// and then yields a clone of the variable.
+///
+/// The iterator ends (i.e., `next()` returns `None`)
+/// - if the stop condition that was set with [`Filtered::until`] holds,
+/// - if no matching state was found within the duration that was set with
+///   [`Filtered::timeout`],
+/// - or if the internal mutex is poisoned.
+///
+/// Only available with feature `generations`.
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+pub struct Filtered<'a, T, F> {
+    cond_sync: &'a CondSync<T>,
+    predicate: F,
+    seen_generation: u64,
+    timeout: Option<Duration>,
+    stop: Option<StopCondition<'a, T>>,
+}
+impl<'a, T, F> Filtered<'a, T, F> {
+    pub(crate) fn new(cond_sync: &'a CondSync<T>, predicate: F) -> Self {
+        Self {
+            cond_sync,
+            predicate,
+            seen_generation: cond_sync.0.generation.load(Ordering::SeqCst),
+            timeout: None,
+            stop: None,
+        }
+    }
+
+    /// Lets the iterator end if a single call to `next()` did not find a matching state
+    /// within the given duration.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Lets the iterator end as soon as the given stop condition holds.
+    ///
+    /// The stop condition is evaluated before the predicate.
+    #[must_use]
+    pub fn until<S>(mut self, stop: S) -> Self
+    where
+        S: Fn(&T) -> bool + 'a,
+    {
+        self.stop = Some(Box::new(stop));
+        self
+    }
+}
+impl<T, F> Iterator for Filtered<'_, T, F>
+where
+    T: Clone,
+    F: Fn(&T) -> bool,
+{
+    type Item = T;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<T> {
+        let i = &self.cond_sync.0;
+        let (predicate, stop, seen_generation, timeout) = (
+            &self.predicate,
+            &self.stop,
+            self.seen_generation,
+            self.timeout,
+        );
+        let (outcome, _) = i
+            .intercept(OperationKind::Wait, "next", || {
+                let mtx_guard = i.lock()?;
+                let waited = i.wait_for(
+                    mtx_guard,
+                    |v| {
+                        if stop.as_ref().is_some_and(|stop| stop(v)) {
+                            return Some(None);
+                        }
+                        let generation = i.generation.load(Ordering::SeqCst);
+                        (generation > seen_generation && predicate(v))
+                            .then(|| Some((v.clone(), generation)))
+                    },
+                    remaining_until(
+                        timeout.and_then(|timeout| Instant::now().checked_add(timeout)),
+                    ),
+                )?;
+                let reason = waited.reason();
+                Ok((waited.finish(), reason))
+            })
+            .ok()?;
+        let (value, generation) = outcome??;
+        self.seen_generation = generation;
+        Some(value)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{timer, CondSync, PoisonedError, Reason};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+
+/// A future that resolves when a condition on the wrapped variable of a [`CondSync`] holds,
+/// or when its timeout is reached.
+///
+/// Is created with [`CondSync::wait_until_async`] or
+/// [`CondSync::wait_until_or_timeout_async`].
+///
+/// The future does not block any thread, and it works with any executor.
+/// Timeouts are driven by the crate's internal timer thread.
+/// Dropping the future cancels the wait, but not everything it set up goes away at once:
+/// the entry of its timeout stays with the timer thread until the timeout would have been
+/// reached (and then does nothing), and a waker that it registered with the [`CondSync`]
+/// is only discarded with the next notification. Neither keeps a thread busy.
+///
+/// Only available with feature `async`.
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitUntil<T, F> {
+    cond_sync: CondSync<T>,
+    condition: F,
+    end: Option<Instant>,
+    // the waker that the timer wakes when the timeout is reached
+    timeout_waker: Option<Arc<Mutex<Option<Waker>>>>,
+}
+
+impl<T, F> WaitUntil<T, F> {
+    pub(crate) fn new(cond_sync: CondSync<T>, condition: F, end: Option<Instant>) -> Self {
+        Self {
+            cond_sync,
+            condition,
+            end,
+            timeout_waker: None,
+        }
+    }
+}
+
+impl<T, F> Future for WaitUntil<T, F>
+where
+    F: Fn(&T) -> bool + Unpin,
+{
+    type Output = Result<Reason, PoisonedError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match this.cond_sync.poll_until(cx, &this.condition) {
+            Poll::Ready(result) => return Poll::Ready(result.map(|()| Reason::Condition)),
+            Poll::Pending => {}
+        }
+        let Some(end) = this.end else {
+            return Poll::Pending;
+        };
+        if Instant::now() >= end {
+            return Poll::Ready(Ok(Reason::Timeout));
+        }
+        if let Some(ref timeout_waker) = this.timeout_waker {
+            // the task may have moved to another waker since the last poll
+            *timeout_waker.lock().unwrap_or_else(PoisonError::into_inner) =
+                Some(cx.waker().clone());
+        } else {
+            let timeout_waker = Arc::new(Mutex::new(Some(cx.waker().clone())));
+            let timeout_waker_t = Arc::clone(&timeout_waker);
+            let _ = timer::schedule(
+                end,
+                Box::new(move || {
+                    let waker = timeout_waker_t
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .take();
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }),
+            );
+            this.timeout_waker = Some(timeout_waker);
+        }
+        Poll::Pending
+    }
+}
+
+impl<T, F> Drop for WaitUntil<T, F> {
+    fn drop(&mut self) {
+        // the timer must not wake a task that is no longer interested
+        if let Some(ref timeout_waker) = self.timeout_waker {
+            timeout_waker
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take();
+        }
+    }
+}
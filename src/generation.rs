@@ -0,0 +1,212 @@
+use crate::{remaining_until, CondSync, OperationKind, PoisonedError, Reason};
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+/// Identifies a state of the wrapped variable by the number of modifications that led to it.
+///
+/// Is returned by [`CondSync::generation`], and allows catching up later
+/// with all modifications that happened in the meantime,
+/// see [`CondSync::wait_changed_since`].
+///
+/// Only available with feature `generations`.
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Generation(pub(crate) u64);
+impl Generation {
+    /// The number of modifications of the wrapped variable.
+    #[must_use]
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+impl<T> CondSync<T> {
+    /// Returns the current generation of the wrapped variable.
+    ///
+    /// Every modification advances the generation, so a caller can take the generation,
+    /// release all locks while it does other work,
+    /// and later find out with [`CondSync::wait_changed_since`] if it missed a modification.
+    ///
+    /// Only available with feature `generations`.
+    #[must_use]
+    pub fn generation(&self) -> Generation {
+        Generation(self.0.generation.load(Ordering::SeqCst))
+    }
+
+    /// Blocks the current thread until the wrapped variable was modified after the given
+    /// generation was taken, and returns the new generation.
+    ///
+    /// Returns immediately if a modification happened in the meantime,
+    /// so that, unlike with a check followed by a wait, no modification can be missed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    ///
+    /// let cond_sync = CondSync::new(0);
+    /// let token = cond_sync.generation();
+    ///
+    /// // ... do other work, while another thread modifies the value ...
+    /// cond_sync.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    ///
+    /// let generation = cond_sync.wait_changed_since(token).unwrap();
+    /// assert!(generation > token);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// Only available with feature `generations`.
+    #[track_caller]
+    pub fn wait_changed_since(&self, token: Generation) -> Result<Generation, PoisonedError> {
+        self.0
+            .intercept(OperationKind::Wait, "wait_changed_since", || {
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |_| {
+                        let generation = self.0.generation.load(Ordering::SeqCst);
+                        (generation > token.0).then_some(Generation(generation))
+                    },
+                    || None,
+                )?;
+                Ok(waited
+                    .finish()
+                    .unwrap_or_else(|| unreachable!("unbounded waits only end with the condition")))
+            })
+    }
+
+    /// Blocks the current thread until the wrapped variable was modified at least `k` times
+    /// after the given generation was taken, but no longer than the given duration.
+    ///
+    /// Like [`CondSync::wait_changed_since`], this does not miss modifications that happened
+    /// before the call. It allows e.g. an observer to process modifications in batches.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::time::Duration;
+    ///
+    /// let cond_sync = CondSync::new(0);
+    /// let token = cond_sync.generation();
+    /// for _ in 0..3 {
+    ///     cond_sync.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    /// }
+    ///
+    /// let generation = cond_sync
+    ///     .wait_updates_since(token, 3, Duration::from_secs(1))
+    ///     .unwrap()
+    ///     .expect("too few updates");
+    /// assert_eq!(generation.value() - token.value(), 3);
+    /// ```
+    ///
+    /// ## Returns
+    ///
+    /// The new generation, or `None` if the timeout was reached first.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// Only available with feature `generations`.
+    #[track_caller]
+    pub fn wait_updates_since(
+        &self,
+        token: Generation,
+        k: u64,
+        timeout: Duration,
+    ) -> Result<Option<Generation>, PoisonedError> {
+        let (generation, _) =
+            self.0
+                .intercept(OperationKind::Wait, "wait_updates_since", || {
+                    let mtx_guard = self.0.lock()?;
+                    let waited = self.0.wait_for(
+                        mtx_guard,
+                        |_| {
+                            let generation = self.0.generation.load(Ordering::SeqCst);
+                            (generation.saturating_sub(token.0) >= k)
+                                .then_some(Generation(generation))
+                        },
+                        remaining_until(Instant::now().checked_add(timeout)),
+                    )?;
+                    let reason = waited.reason();
+                    Ok((waited.finish(), reason))
+                })?;
+        Ok(generation)
+    }
+
+    /// Blocks the current thread until the wrapped variable was not modified for the given
+    /// quiet period, but no longer than the given overall timeout.
+    ///
+    /// The quiet period is measured from the call on, and starts over with each modification,
+    /// so earlier modifications are not taken into account.
+    /// This is useful e.g. before a teardown or a flush, to let a burst of activity settle.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::CondSync;
+    /// use std::time::Duration;
+    ///
+    /// let cond_sync = CondSync::new(0);
+    /// let reason = cond_sync
+    ///     .wait_idle(Duration::from_millis(20), Duration::from_secs(1))
+    ///     .unwrap();
+    /// assert!(reason.is_condition());
+    /// ```
+    ///
+    /// ## Returns
+    ///
+    /// [`Reason::Condition`] if the quiet period was reached, and [`Reason::Timeout`] if the
+    /// overall timeout was reached first.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// Only available with feature `generations`.
+    #[track_caller]
+    pub fn wait_idle(
+        &self,
+        quiet_period: Duration,
+        overall_timeout: Duration,
+    ) -> Result<Reason, PoisonedError> {
+        self.0.intercept(OperationKind::Wait, "wait_idle", || {
+            let end = Instant::now().checked_add(overall_timeout);
+            loop {
+                let mtx_guard = self.0.lock()?;
+                let token = self.0.generation.load(Ordering::SeqCst);
+                let quiet_end = Instant::now().checked_add(quiet_period);
+                let (until, reason) = match (quiet_end, end) {
+                    (Some(quiet_end), Some(end)) if end < quiet_end => (Some(end), Reason::Timeout),
+                    (None, Some(end)) => (Some(end), Reason::Timeout),
+                    _ => (quiet_end, Reason::Condition),
+                };
+                let modified = self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |_| (self.0.generation.load(Ordering::SeqCst) != token).then_some(()),
+                        remaining_until(until),
+                    )?
+                    .finish()
+                    .is_some();
+                if !modified {
+                    return Ok(reason);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{CoordinationError, Coordinator, Participant, ParticipantId};
+use std::{convert::Infallible, time::Duration};
+
+/// Creates the handles for the classic use case of waiting until a set of worker threads
+/// is initialized.
+///
+/// Each of the `workers` [`InitWorker`]s is handed to a worker thread, which calls
+/// [`InitWorker::ready`] once it is initialized.
+/// The main thread waits on the [`InitBarrier`] until all workers are ready.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::init_barrier;
+/// use std::{thread, time::Duration};
+///
+/// let (barrier, workers) = init_barrier(5);
+/// for (i, worker) in workers.into_iter().enumerate() {
+///     thread::spawn(move || {
+///         println!("Thread {i}: initializing ...");
+///         worker.ready();
+///         println!("Thread {i}: work on phase 1");
+///     });
+/// }
+/// barrier.wait_all_or_timeout(Duration::from_secs(10)).unwrap();
+/// println!("Main: All threads initialized");
+/// ```
+#[must_use]
+pub fn init_barrier(workers: usize) -> (InitBarrier, Vec<InitWorker>) {
+    let coordinator = Coordinator::new();
+    let workers = (0..workers)
+        .map(|_| InitWorker(coordinator.add_participant()))
+        .collect();
+    (InitBarrier(coordinator), workers)
+}
+
+/// The main thread's handle of an [`init_barrier`].
+pub struct InitBarrier(Coordinator<Infallible>);
+
+impl InitBarrier {
+    /// Blocks the current thread until all workers are ready.
+    ///
+    /// ## Errors
+    ///
+    /// [`CoordinationError::Abandoned`] if a worker was dropped without being ready.
+    ///
+    /// [`CoordinationError::Poisoned`] if the internally used mutex is poisoned.
+    pub fn wait_all(&self) -> Result<(), CoordinationError<Infallible>> {
+        self.0.wait_until_all_ok()
+    }
+
+    /// Blocks the current thread until all workers are ready,
+    /// but no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// [`CoordinationError::Timeout`] with the workers that were not ready in time.
+    ///
+    /// [`CoordinationError::Abandoned`] if a worker was dropped without being ready.
+    ///
+    /// [`CoordinationError::Poisoned`] if the internally used mutex is poisoned.
+    pub fn wait_all_or_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<(), CoordinationError<Infallible>> {
+        self.0.wait_until_all_ok_or_timeout(duration)
+    }
+}
+
+/// A worker's handle of an [`init_barrier`].
+pub struct InitWorker(Participant<Infallible>);
+
+impl InitWorker {
+    /// The identification of the worker, as reported in [`CoordinationError::Timeout`].
+    #[must_use]
+    pub fn id(&self) -> &ParticipantId {
+        self.0.id()
+    }
+
+    /// Reports that the worker is initialized.
+    pub fn ready(self) {
+        self.0.done();
+    }
+}
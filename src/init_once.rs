@@ -0,0 +1,193 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError};
+use std::time::{Duration, Instant};
+
+/// A value that is initialized exactly once, by the first thread that asks for it,
+/// while the other threads that ask for it in the meantime block until it is ready.
+///
+/// The waiting threads block on the condition variable rather than spinning.
+/// If the initializer panics, the value stays uninitialized, and one of the waiting threads,
+/// or the next caller, runs its own initializer instead.
+///
+/// Clones share the same value.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::InitOnce;
+/// use std::thread;
+///
+/// let config = InitOnce::new();
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             let value = config
+///                 .get_or_init_blocking(|| "expensively loaded".to_string())
+///                 .unwrap();
+///             assert_eq!(value, "expensively loaded");
+///         });
+///     }
+/// });
+/// ```
+pub struct InitOnce<T>(CondSync<State<T>>);
+
+enum State<T> {
+    Uninitialized,
+    Initializing,
+    Ready(T),
+}
+
+impl<T> InitOnce<T> {
+    /// Construct a new, uninitialized instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CondSync::new(State::Uninitialized))
+    }
+}
+
+impl<T: Clone> InitOnce<T> {
+    /// Returns the value if it is initialized, without blocking.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn get(&self) -> Result<Option<T>, PoisonedError> {
+        Ok(match &*self.0 .0.lock()? {
+            State::Ready(value) => Some(value.clone()),
+            State::Uninitialized | State::Initializing => None,
+        })
+    }
+
+    /// Returns the value, and initializes it with the given initializer if this has not yet
+    /// happened.
+    ///
+    /// If another thread is initializing the value, the current thread blocks until it is
+    /// done; the given initializer is then not called.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn get_or_init_blocking<F>(&self, init: F) -> Result<T, PoisonedError>
+    where
+        F: FnOnce() -> T,
+    {
+        self.get_or_init_until("get_or_init_blocking", init, None)
+            .map(|value| {
+                value.unwrap_or_else(|| unreachable!("unbounded waits only end with the condition"))
+            })
+    }
+
+    /// Like [`InitOnce::get_or_init_blocking`], but waits no longer than the given duration
+    /// for another thread to initialize the value.
+    ///
+    /// The timeout does not limit the duration of the initializer of the current thread.
+    ///
+    /// ## Returns
+    ///
+    /// The value, or `None` if the timeout was reached while another thread was
+    /// initializing it.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn get_or_init_blocking_timeout<F>(
+        &self,
+        init: F,
+        timeout: Duration,
+    ) -> Result<Option<T>, PoisonedError>
+    where
+        F: FnOnce() -> T,
+    {
+        self.get_or_init_until(
+            "get_or_init_blocking_timeout",
+            init,
+            Instant::now().checked_add(timeout),
+        )
+    }
+
+    #[track_caller]
+    fn get_or_init_until<F>(
+        &self,
+        method: &'static str,
+        init: F,
+        end: Option<Instant>,
+    ) -> Result<Option<T>, PoisonedError>
+    where
+        F: FnOnce() -> T,
+    {
+        let i = &self.0 .0;
+        let (initializing, _) = i.intercept(OperationKind::Wait, method, || {
+            let mtx_guard = i.lock()?;
+            let mut waited = i.wait_for(
+                mtx_guard,
+                |state| match state {
+                    State::Initializing => None,
+                    State::Ready(value) => Some(Some(value.clone())),
+                    State::Uninitialized => Some(None),
+                },
+                remaining_until(end),
+            )?;
+            let reason = waited.reason();
+            let initializing = match waited.outcome.take() {
+                Some(Some(value)) => Err(Some(value)),
+                Some(None) => {
+                    i.apply(&mut waited.mtx_guard, |state| {
+                        *state = State::Initializing;
+                    });
+                    Ok(())
+                }
+                None => Err(None),
+            };
+            waited.finish();
+            Ok((initializing, reason))
+        })?;
+        if let Err(value) = initializing {
+            return Ok(value);
+        }
+
+        // the current thread is responsible for the initialization
+        let mut initializing = Initializing {
+            init_once: self,
+            done: false,
+        };
+        let value = init();
+        initializing.done = true;
+        let ready = value.clone();
+        self.0
+            .modify_and_notify(|state| *state = State::Ready(ready), Other::All)?;
+        Ok(Some(value))
+    }
+}
+
+// Resets the state if the initializer panics, and lets a waiting thread take over.
+struct Initializing<'a, T> {
+    init_once: &'a InitOnce<T>,
+    done: bool,
+}
+impl<T> Drop for Initializing<'_, T> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.init_once
+                .0
+                .modify_and_notify(|state| *state = State::Uninitialized, Other::All)
+                .ok();
+        }
+    }
+}
+
+impl<T> Default for InitOnce<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for InitOnce<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::Reason;
+#[cfg(feature = "generations")]
+use crate::{Generation, WaitOutcome};
+#[cfg(feature = "interceptors")]
+use std::{
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, PoisonError, RwLock,
+    },
+    time::Duration,
+};
+
+/// Allows wrapping all waiting and modifying operations of [`CondSync`](crate::CondSync)
+/// instances with own logic, e.g. for metrics, tracing, or alerts on slow waits.
+///
+/// An interceptor can be set globally, with [`set_global_interceptor`], and per instance, with
+/// [`CondSyncBuilder::interceptor`](crate::CondSyncBuilder::interceptor).
+/// If both are set, the global interceptor is called first in `before`,
+/// and last in `after`.
+///
+/// Interceptors are called without holding the lock.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CondSync, Interceptor, Operation, Other, Outcome};
+/// use std::{sync::Arc, time::Duration};
+///
+/// struct SlowWaitAlert;
+/// impl Interceptor for SlowWaitAlert {
+///     fn after(&self, operation: &Operation, outcome: &Outcome) {
+///         if outcome.elapsed() > Duration::from_secs(1) {
+///             eprintln!("slow {}: {:?}", operation.method(), outcome.elapsed());
+///         }
+///     }
+/// }
+///
+/// let cond_sync = CondSync::builder(0_usize)
+///     .interceptor(Arc::new(SlowWaitAlert))
+///     .build();
+/// cond_sync.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+/// ```
+///
+/// Only available with feature `interceptors`.
+#[cfg(feature = "interceptors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "interceptors")))]
+pub trait Interceptor: Send + Sync {
+    /// Is called before the operation starts.
+    fn before(&self, _operation: &Operation) {}
+
+    /// Is called after the operation has finished.
+    fn after(&self, _operation: &Operation, _outcome: &Outcome) {}
+
+    /// Is called when a lock-order inversion is detected, see
+    /// [`lock_order_inversions`](crate::lock_order_inversions).
+    ///
+    /// Only the global interceptor is called, because an inversion concerns two instances.
+    /// It is called by the thread that inverted the order, while that thread holds the lock of
+    /// one of the instances.
+    ///
+    /// Only available with feature `lock_order`.
+    #[cfg(feature = "lock_order")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "lock_order")))]
+    fn lock_order_inversion(&self, _inversion: &crate::LockOrderInversion) {}
+}
+
+/// Describes the operation that is intercepted.
+#[cfg(feature = "interceptors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "interceptors")))]
+#[derive(Clone, Copy, Debug)]
+pub struct Operation {
+    kind: OperationKind,
+    method: &'static str,
+    label: Option<&'static str>,
+    location: &'static Location<'static>,
+}
+#[cfg(feature = "interceptors")]
+impl Operation {
+    pub(crate) fn new(
+        kind: OperationKind,
+        method: &'static str,
+        label: Option<&'static str>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        Self {
+            kind,
+            method,
+            label,
+            location,
+        }
+    }
+    /// The kind of operation.
+    #[must_use]
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+    /// The name of the called method, like `"wait_until"`.
+    #[must_use]
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+    /// The label that was given to the call, e.g. with
+    /// [`CondSync::wait_until_named`](crate::CondSync::wait_until_named).
+    #[must_use]
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+    /// The call site of the method.
+    #[must_use]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+/// Distinguishes waiting from modifying operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationKind {
+    /// The operation waits for a condition or a notification.
+    Wait,
+    /// The operation modifies the wrapped variable and notifies waiting threads.
+    Modify,
+}
+
+/// Describes how an intercepted operation ended.
+#[cfg(feature = "interceptors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "interceptors")))]
+#[derive(Clone, Copy, Debug)]
+pub struct Outcome {
+    elapsed: Duration,
+    reason: Option<Reason>,
+    poisoned: bool,
+}
+#[cfg(feature = "interceptors")]
+impl Outcome {
+    pub(crate) fn new(elapsed: Duration, reason: Option<Reason>, poisoned: bool) -> Self {
+        Self {
+            elapsed,
+            reason,
+            poisoned,
+        }
+    }
+    /// How long the operation took.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+    /// Why a waiting operation returned; is `None` for modifying operations and errors.
+    #[must_use]
+    pub fn reason(&self) -> Option<Reason> {
+        self.reason
+    }
+    /// Is `true` if the operation failed because the internal mutex is poisoned.
+    #[must_use]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+}
+
+#[cfg(feature = "interceptors")]
+static HAS_GLOBAL_INTERCEPTOR: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "interceptors")]
+static GLOBAL_INTERCEPTOR: RwLock<Option<Arc<dyn Interceptor>>> = RwLock::new(None);
+
+/// Sets (or, with `None`, removes) the interceptor that is used for all
+/// [`CondSync`](crate::CondSync) instances.
+///
+/// Only available with feature `interceptors`.
+#[cfg(feature = "interceptors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "interceptors")))]
+pub fn set_global_interceptor(interceptor: Option<Arc<dyn Interceptor>>) {
+    let mut global = GLOBAL_INTERCEPTOR
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    HAS_GLOBAL_INTERCEPTOR.store(interceptor.is_some(), Ordering::SeqCst);
+    *global = interceptor;
+}
+
+#[cfg(feature = "interceptors")]
+pub(crate) fn global() -> Option<Arc<dyn Interceptor>> {
+    if HAS_GLOBAL_INTERCEPTOR.load(Ordering::SeqCst) {
+        GLOBAL_INTERCEPTOR
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    } else {
+        None
+    }
+}
+
+// Extracts the reason from the results of intercepted operations.
+pub(crate) trait Intercepted {
+    #[cfg_attr(
+        not(any(feature = "interceptors", feature = "stats")),
+        allow(dead_code)
+    )]
+    fn reason(&self) -> Option<Reason>;
+}
+impl Intercepted for () {
+    fn reason(&self) -> Option<Reason> {
+        None
+    }
+}
+impl Intercepted for Reason {
+    fn reason(&self) -> Option<Reason> {
+        Some(*self)
+    }
+}
+#[cfg(feature = "generations")]
+impl Intercepted for Generation {
+    fn reason(&self) -> Option<Reason> {
+        Some(Reason::Condition)
+    }
+}
+#[cfg(feature = "generations")]
+impl Intercepted for WaitOutcome {
+    fn reason(&self) -> Option<Reason> {
+        Some(self.reason)
+    }
+}
+impl<I> Intercepted for (I, Reason) {
+    fn reason(&self) -> Option<Reason> {
+        Some(self.1)
+    }
+}
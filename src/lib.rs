@@ -10,10 +10,194 @@
 //! The struct [`CondSync`] is a thin wrapper around
 //! [`Arc`]`<(`[`Mutex`]`<T>, `[`Condvar`]`)>` and hides boiler plate code
 //! that is needed when using `std::sync::Condvar` directly.
+mod ack_tracker;
+mod backoff;
+mod barrier;
+mod batch;
+#[cfg(feature = "bench")]
+mod bench;
+mod broadcast;
+mod budget;
+mod builder;
+mod cancel;
+#[cfg(feature = "testing")]
+mod chaos;
+mod completion;
+#[cfg(feature = "generations")]
+mod condition;
+mod coord_scope;
+mod coordinator;
+mod deadline;
+#[cfg(feature = "debounce")]
+mod debounce;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod dispatcher;
+mod double_buffer;
+#[cfg(feature = "generations")]
+mod filtered;
+#[cfg(feature = "async")]
+mod future;
+#[cfg(feature = "generations")]
+mod generation;
+mod init_barrier;
+mod init_once;
+mod interceptor;
+mod like;
+mod limiter;
+#[cfg(feature = "lock_order")]
+mod lock_order;
+mod mailbox;
+#[cfg(feature = "generations")]
+mod memoized;
+mod multi;
+#[cfg(feature = "observers")]
+mod observer;
+mod pool;
+mod progress;
+mod promise;
+mod queue;
+mod quorum;
+mod raw;
+#[cfg(all(unix, feature = "readiness"))]
+mod readiness;
+#[cfg(any(feature = "debounce", feature = "jitter"))]
+mod reconfigure;
+mod releasing;
+mod result_cell;
+mod retry;
+#[cfg(any(feature = "jitter", feature = "testing"))]
+mod rng;
+mod scatter_gather;
+#[cfg(feature = "optimistic_reads")]
+mod seqlock;
+mod single_flight;
+#[cfg(feature = "snapshots")]
+mod snapshot;
+mod static_cond_sync;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "tags")]
+mod tagged;
+#[cfg(feature = "testing")]
+mod testing;
+mod thread_pool;
+mod tick;
+mod timeout_error;
+mod timer;
+mod wait_map;
+#[cfg(feature = "generations")]
+mod wait_outcome;
+
+pub use ack_tracker::AckTracker;
+pub use backoff::{Backoff, BackoffOutcome};
+pub use barrier::{BarrierTopology, CondBarrier};
+#[cfg(feature = "bench")]
+pub use bench::{bench_fan_in, bench_ping_pong, BenchOptions, BenchReport};
+pub use broadcast::{Broadcast, BroadcastError, Subscriber};
+pub use budget::{Budget, BudgetError};
+pub use builder::CondSyncBuilder;
+pub use cancel::CancelToken;
+#[cfg(feature = "testing")]
+pub use chaos::{explore_interleavings, Chaos};
+pub use completion::Completion;
+#[cfg(feature = "generations")]
+pub use condition::Condition;
+pub use coord_scope::{CoordScope, ScopeError};
+pub use coordinator::{
+    CoordinationError, CoordinationReport, Coordinator, Participant, ParticipantId,
+};
+pub use deadline::Deadline;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::{dump_all, WaitInfo};
+pub use dispatcher::{DispatchError, Dispatcher};
+pub use double_buffer::{DoubleBuffer, DoubleBufferReader, FrontGuard};
+#[cfg(feature = "generations")]
+pub use filtered::Filtered;
+#[cfg(feature = "async")]
+pub use future::WaitUntil;
+#[cfg(feature = "generations")]
+pub use generation::Generation;
+pub use init_barrier::{init_barrier, InitBarrier, InitWorker};
+pub use init_once::InitOnce;
+pub use interceptor::OperationKind;
+#[cfg(feature = "interceptors")]
+pub use interceptor::{set_global_interceptor, Interceptor, Operation, Outcome};
+pub use like::CondSyncLike;
+pub use limiter::{Limiter, LimiterGuard};
+#[cfg(feature = "lock_order")]
+pub use lock_order::{lock_order_inversions, LockOrderInversion};
+pub use mailbox::PriorityMailbox;
+#[cfg(feature = "generations")]
+pub use memoized::Memoized;
+pub use multi::multi_modify;
+#[cfg(feature = "observers")]
+pub use observer::{Event, ObserverHandle};
+pub use pool::{CondSyncPool, PooledCondSync};
+pub use progress::{Progress, ProgressSnapshot, ProgressUpdates};
+pub use promise::{AlreadyCompleted, AwaitError, Awaiter, Completer};
+pub use queue::CondQueue;
+pub use quorum::Quorum;
+#[cfg(all(unix, feature = "readiness"))]
+pub use readiness::ReadinessHandle;
+#[cfg(any(feature = "debounce", feature = "jitter"))]
+pub use reconfigure::{Reconfiguration, ReconfigureError};
+pub use result_cell::{ResultCell, ResultCellError};
+pub use retry::RetryError;
+pub use scatter_gather::{scatter_gather, GatherError};
+#[cfg(feature = "optimistic_reads")]
+pub use seqlock::SeqLockable;
+pub use single_flight::SingleFlight;
+pub use static_cond_sync::StaticCondSync;
+#[cfg(feature = "stats")]
+pub use stats::{ContentionStats, DurationSummary, TimingStats};
+pub use thread_pool::ThreadPool;
+pub use tick::{Tick, TickBarrier, TickWorker};
+pub use timeout_error::{TimeoutError, WaitError};
+pub use wait_map::WaitMap;
+#[cfg(feature = "generations")]
+pub use wait_outcome::WaitOutcome;
+
+use builder::Config;
+#[cfg(feature = "debounce")]
+use debounce::Debouncer;
+use interceptor::Intercepted;
+#[cfg(feature = "observers")]
+use observer::Observers;
+#[cfg(feature = "jitter")]
+use rng::{MaxJitter, Rng};
+#[cfg(feature = "optimistic_reads")]
+use seqlock::Mirror;
+#[cfg(feature = "snapshots")]
+use snapshot::SnapshotSlot;
+#[cfg(feature = "tags")]
+use std::collections::HashMap;
+#[cfg(any(feature = "generations", feature = "notifications"))]
+use std::sync::atomic::AtomicU64;
+#[cfg(any(feature = "notifications", feature = "async"))]
+use std::sync::atomic::AtomicUsize;
+#[cfg(any(
+    feature = "optimistic_reads",
+    feature = "snapshots",
+    all(unix, feature = "readiness")
+))]
+use std::sync::OnceLock;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
 use std::{
-    sync::{Arc, Condvar, Mutex, PoisonError},
-    time::{Duration, Instant},
+    fmt::Debug,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, LockResult, Mutex, MutexGuard, PoisonError, Weak,
+    },
+    time::{Duration, Instant, SystemTime},
 };
+use timer::TimerHandle;
+
+// Upper bound for a single wait in `wait_until_system_time`,
+// so that adjustments of the wall clock are noticed in time.
+const WALL_CLOCK_SLICE: Duration = Duration::from_secs(1);
 
 /// A thin wrapper around [`Arc`]`<(`[`Mutex`]`<T>, `[`Condvar`]`)>`.
 ///
@@ -63,22 +247,191 @@ use std::{
 /// Thread 3: work on phase 1
 /// ```
 ///
+/// ## Example: Scoped threads
+///
+/// With [`std::thread::scope`], the threads can simply borrow the instance;
+/// no clones, and thus no reference counting, are needed.
+/// The shared state is still allocated once, by [`CondSync::new`];
+/// there is no variant of `CondSync` that avoids this allocation.
+///
+/// ```rust
+/// use cond_sync::{CondSync, Other};
+/// use std::thread;
+///
+/// let cond_sync = CondSync::new(0_usize);
+/// thread::scope(|scope| {
+///     for _ in 0..5 {
+///         scope.spawn(|| cond_sync.modify_and_notify(|v| *v += 1, Other::One).unwrap());
+///     }
+///     cond_sync.wait_until(|v| *v == 5).unwrap();
+/// });
+/// ```
+///
 pub struct CondSync<T>(Arc<I<T>>);
 
 struct I<T> {
     mtx: Mutex<T>,
     cvar: Condvar,
+    #[cfg_attr(
+        not(any(feature = "notifications", feature = "interceptors")),
+        allow(dead_code)
+    )]
+    config: Config,
+    #[cfg(feature = "notifications")]
+    pending_notifications: AtomicUsize,
+    #[cfg(feature = "notifications")]
+    raw_condvar: AtomicBool,
+    #[cfg(feature = "jitter")]
+    rng: Rng,
+    #[cfg(feature = "notifications")]
+    waiters: AtomicUsize,
+    #[cfg(feature = "generations")]
+    generation: AtomicU64,
+    #[cfg(feature = "notifications")]
+    notify_epoch: AtomicU64,
+    #[cfg(feature = "stats")]
+    stats: Mutex<ContentionStats>,
+    #[cfg(feature = "observers")]
+    observers: Arc<Observers>,
+    #[cfg(feature = "async")]
+    wakers: Mutex<Vec<Waker>>,
+    // allows skipping the lock of the wakers when none is registered
+    #[cfg(feature = "async")]
+    waker_count: AtomicUsize,
+    #[cfg(feature = "debounce")]
+    debouncer: Option<Debouncer>,
+    #[cfg(feature = "jitter")]
+    wakeup_jitter: MaxJitter,
+    #[cfg(feature = "optimistic_reads")]
+    mirror: OnceLock<Mirror<T>>,
+    #[cfg(all(unix, feature = "readiness"))]
+    readiness: OnceLock<readiness::Readiness>,
+    #[cfg(feature = "tags")]
+    tags: Mutex<HashMap<&'static str, Arc<Condvar>>>,
+    #[cfg(feature = "snapshots")]
+    snapshot: OnceLock<SnapshotSlot<T>>,
+    #[cfg(feature = "testing")]
+    chaos: Option<chaos::ChaosState>,
+    #[cfg(feature = "lock_order")]
+    lock_order: lock_order::Tracked,
 }
 
+// Without features, the shared state consists of just the mutex and the condvar,
+// like the `Arc<(Mutex<T>, Condvar)>` that the crate started from.
+#[cfg(not(any(
+    feature = "generations",
+    feature = "notifications",
+    feature = "optimistic_reads",
+    feature = "observers",
+    feature = "async",
+    feature = "debounce",
+    feature = "jitter",
+    feature = "tags",
+    feature = "readiness",
+    feature = "interceptors",
+    feature = "stats",
+    feature = "snapshots",
+    feature = "diagnostics",
+    feature = "lock_order",
+    feature = "testing",
+    feature = "bench"
+)))]
+const _: () = assert!(
+    std::mem::size_of::<I<u64>>() == std::mem::size_of::<(Mutex<u64>, Condvar)>(),
+    "the shared state must not grow without features"
+);
+
 impl<T> CondSync<T> {
     /// Construct a new instance, based on the variable you logically need to manage the synchronization.
     pub fn new(value: T) -> Self {
+        Self::with_config(value, Config::default())
+    }
+
+    /// Returns a builder that allows configuring the behavior of the new instance.
+    pub fn builder(value: T) -> CondSyncBuilder<T> {
+        CondSyncBuilder::new(value)
+    }
+
+    fn with_config(value: T, config: Config) -> Self {
+        Self::from_parts(Mutex::new(value), Condvar::new(), config)
+    }
+
+    fn from_parts(mtx: Mutex<T>, cvar: Condvar, config: Config) -> Self {
         Self(Arc::new(I {
-            mtx: Mutex::new(value),
-            cvar: Condvar::new(),
+            mtx,
+            cvar,
+            #[cfg(feature = "jitter")]
+            rng: Rng::new(config.jitter_seed),
+            #[cfg(feature = "notifications")]
+            pending_notifications: AtomicUsize::new(0),
+            #[cfg(feature = "notifications")]
+            raw_condvar: AtomicBool::new(false),
+            #[cfg(feature = "notifications")]
+            waiters: AtomicUsize::new(0),
+            #[cfg(feature = "generations")]
+            generation: AtomicU64::new(0),
+            #[cfg(feature = "notifications")]
+            notify_epoch: AtomicU64::new(0),
+            #[cfg(feature = "stats")]
+            stats: Mutex::new(ContentionStats::default()),
+            #[cfg(feature = "observers")]
+            observers: Arc::new(Observers::default()),
+            #[cfg(feature = "async")]
+            wakers: Mutex::new(Vec::new()),
+            #[cfg(feature = "async")]
+            waker_count: AtomicUsize::new(0),
+            #[cfg(feature = "debounce")]
+            debouncer: config
+                .debounce
+                .map(|(window, max_latency)| Debouncer::new(window, max_latency)),
+            #[cfg(feature = "jitter")]
+            wakeup_jitter: MaxJitter::new(config.wakeup_jitter),
+            #[cfg(feature = "optimistic_reads")]
+            mirror: OnceLock::new(),
+            #[cfg(all(unix, feature = "readiness"))]
+            readiness: OnceLock::new(),
+            #[cfg(feature = "tags")]
+            tags: Mutex::new(HashMap::new()),
+            #[cfg(feature = "snapshots")]
+            snapshot: OnceLock::new(),
+            #[cfg(feature = "testing")]
+            chaos: config.chaos.clone().map(chaos::ChaosState::new),
+            #[cfg(feature = "lock_order")]
+            lock_order: lock_order::Tracked::new(),
+            config,
         }))
     }
 
+    /// Registers a callback that is invoked after each modification of the wrapped variable.
+    ///
+    /// The callback is invoked without holding the lock, by the thread that did the
+    /// modification. It stays registered until the returned handle is dropped.
+    ///
+    /// Only available with feature `observers`.
+    #[cfg(feature = "observers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "observers")))]
+    pub fn on_modify<F>(&self, callback: F) -> ObserverHandle
+    where
+        F: Fn(&Event) + Send + Sync + 'static,
+    {
+        Observers::register(&self.0.observers, false, Arc::new(callback))
+    }
+
+    /// Registers a callback that is invoked after each notification of waiting threads.
+    ///
+    /// The callback is invoked without holding the lock, by the thread that sent the
+    /// notification. It stays registered until the returned handle is dropped.
+    ///
+    /// Only available with feature `observers`.
+    #[cfg(feature = "observers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "observers")))]
+    pub fn on_notify<F>(&self, callback: F) -> ObserverHandle
+    where
+        F: Fn(&Event) + Send + Sync + 'static,
+    {
+        Observers::register(&self.0.observers, true, Arc::new(callback))
+    }
+
     /// Blocks the current thread until the given condition,
     /// when called with the current value of the wrapped variable, returns `true`.
     ///
@@ -87,15 +440,233 @@ impl<T> CondSync<T> {
     /// This function will return an error if the internally used mutex being waited on is
     /// poisoned when this thread tries to re-acquire the lock.
     /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
     pub fn wait_until<F>(&self, condition: F) -> Result<Reason, PoisonedError>
     where
         F: Fn(&T) -> bool,
     {
-        let mut mtx_guard = self.0.mtx.lock()?;
-        while !condition(&*mtx_guard) {
-            mtx_guard = self.0.cvar.wait(mtx_guard)?;
+        self.0.intercept(OperationKind::Wait, "wait_until", || {
+            let mtx_guard = self.0.lock()?;
+            Ok(self
+                .0
+                .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?
+                .into_reason())
+        })
+    }
+
+    /// Like [`CondSync::wait_until`], with a label that describes the wait.
+    ///
+    /// The label is handed over to the interceptors, see [`Operation::label`],
+    /// and, with feature `diagnostics`, shows up in the information about waiting threads.
+    /// This helps distinguishing different waits on the same instance.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_named<F>(
+        &self,
+        label: &'static str,
+        condition: F,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0
+            .intercept_labeled(OperationKind::Wait, "wait_until", Some(label), || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?
+                    .into_reason())
+            })
+    }
+
+    /// Like [`CondSync::wait_until`], but the condition gets mutable access to the wrapped
+    /// variable, e.g. to record that this waiter has seen a certain state.
+    ///
+    /// Such bookkeeping is not a modification: it neither notifies other threads,
+    /// nor advances the generation.
+    /// The condition must therefore not change anything that it or any other condition tests,
+    /// otherwise other waiters will not notice the change.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::CondSync;
+    ///
+    /// struct State {
+    ///     ready: bool,
+    ///     checks: usize,
+    /// }
+    ///
+    /// let cond_sync = CondSync::new(State { ready: true, checks: 0 });
+    /// cond_sync
+    ///     .wait_until_mut(|state| {
+    ///         state.checks += 1;
+    ///         state.ready
+    ///     })
+    ///     .unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_mut<F>(&self, mut condition: F) -> Result<Reason, PoisonedError>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.0.intercept(OperationKind::Wait, "wait_until_mut", || {
+            let mtx_guard = self.0.lock()?;
+            Ok(self
+                .0
+                .wait_for(
+                    mtx_guard,
+                    |v| {
+                        let fulfilled = condition(v);
+                        self.0.refresh_copies(v);
+                        fulfilled.then_some(())
+                    },
+                    || None,
+                )?
+                .into_reason())
+        })
+    }
+
+    /// Registers a [`Waker`] that is woken with the next notification, i.e. after the next
+    /// modification of the wrapped variable.
+    ///
+    /// Like with futures in general, the registration is consumed by the wakeup;
+    /// a future that is polled again and still has to wait needs to register again.
+    /// Registering a waker that would wake the same task as an already registered one
+    /// has no effect.
+    ///
+    /// See [`CondSync::poll_until`] for a convenient combination of
+    /// evaluating a condition and registering a waker.
+    ///
+    /// Only available with feature `async`.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn register_waker(&self, waker: &Waker) {
+        let mut wakers = self.0.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+            self.0.waker_count.store(wakers.len(), Ordering::SeqCst);
+        }
+    }
+
+    /// Evaluates the given condition with the current value of the wrapped variable;
+    /// if it does not hold, the waker of the given context is registered,
+    /// so that the task is woken after the next modification.
+    ///
+    /// This allows hand-rolled futures and executors to wait for a condition
+    /// without blocking a thread.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, PoisonedError};
+    /// use std::{
+    ///     future::Future,
+    ///     pin::Pin,
+    ///     task::{Context, Poll},
+    /// };
+    ///
+    /// struct WaitUntilReady(CondSync<bool>);
+    /// impl Future for WaitUntilReady {
+    ///     type Output = Result<(), PoisonedError>;
+    ///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    ///         self.0.poll_until(cx, |ready| *ready)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    ///
+    /// Only available with feature `async`.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn poll_until<F>(
+        &self,
+        cx: &mut Context<'_>,
+        condition: F,
+    ) -> Poll<Result<(), PoisonedError>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mtx_guard = match self.0.lock() {
+            Ok(mtx_guard) => mtx_guard,
+            Err(e) => return Poll::Ready(Err(e)),
+        };
+        if self.0.holding(|| condition(&mtx_guard)) {
+            return Poll::Ready(Ok(()));
         }
-        Ok(Reason::Condition)
+        // registering while holding the lock ensures that the next modification wakes us
+        self.register_waker(cx.waker());
+        Poll::Pending
+    }
+
+    /// Returns a future that resolves when the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`.
+    ///
+    /// Unlike offloading a blocking wait to a separate thread, the future does not occupy
+    /// any thread while it is pending, and it can be dropped at any time.
+    /// It works with any executor.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use cond_sync::CondSync;
+    ///
+    /// async fn wait_for_ready(cond_sync: &CondSync<bool>) {
+    ///     cond_sync.wait_until_async(|ready| *ready).await.unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The future resolves to an error if the internally used mutex is poisoned.
+    ///
+    /// Only available with feature `async`.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn wait_until_async<F>(&self, condition: F) -> WaitUntil<T, F>
+    where
+        F: Fn(&T) -> bool + Unpin,
+    {
+        WaitUntil::new(self.clone(), condition, None)
+    }
+
+    /// Like [`CondSync::wait_until_async`], but the future resolves to
+    /// [`Reason::Timeout`] if the condition does not hold within the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// The future resolves to an error if the internally used mutex is poisoned.
+    ///
+    /// Only available with feature `async`.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn wait_until_or_timeout_async<F>(
+        &self,
+        condition: F,
+        duration: Duration,
+    ) -> WaitUntil<T, F>
+    where
+        F: Fn(&T) -> bool + Unpin,
+    {
+        WaitUntil::new(
+            self.clone(),
+            condition,
+            Instant::now().checked_add(duration),
+        )
     }
 
     /// Blocks the current thread until the given test method,
@@ -111,6 +682,7 @@ impl<T> CondSync<T> {
     /// This function will return an error if the internally used mutex being waited on is
     /// poisoned when this thread re-acquires the lock.
     /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
     pub fn wait_until_or_timeout<F>(
         &self,
         condition: F,
@@ -119,139 +691,1854 @@ impl<T> CondSync<T> {
     where
         F: Fn(&T) -> bool,
     {
-        let mut mtx_guard = self.0.mtx.lock()?;
-        let end = Instant::now() + duration;
-        while !condition(&*mtx_guard) {
-            let now = Instant::now();
-            match self.0.cvar.wait_timeout(mtx_guard, end - now) {
-                Ok((mtxg, wtr)) => {
-                    if wtr.timed_out() {
-                        return Ok(Reason::Timeout);
-                    }
-                    mtx_guard = mtxg;
-                }
-                Err(_) => return Err(PoisonedError),
-            }
-        }
-        Ok(Reason::Condition)
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_or_timeout", || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(Instant::now().checked_add(duration)),
+                    )?
+                    .into_reason())
+            })
     }
 
-    /// Blocks the current thread until a notification is received, but no longer
-    /// than the given duration.
-    ///
-    /// ## Returns
+    /// Like [`CondSync::wait_until_or_timeout`], with a label that describes the wait.
     ///
-    /// Returns `true` if the timeout was reached, and `false` otherwise.
+    /// See [`CondSync::wait_until_named`] for the use of the label.
     ///
     /// ## Errors
     ///
     /// This function will return an error if the internally used mutex being waited on is
     /// poisoned when this thread re-acquires the lock.
     /// For more information, see information about poisoning on the Mutex type.
-    pub fn wait_timeout(&self, duration: Duration) -> Result<Reason, PoisonedError> {
-        let mtx_guard = self.0.mtx.lock()?;
-        let end = Instant::now() + duration;
-
-        Ok(self
-            .0
-            .cvar
-            .wait_timeout(mtx_guard, end - Instant::now())
-            .map(|(_, wtr)| {
-                if wtr.timed_out() {
-                    Reason::Timeout
-                } else {
-                    Reason::Notification
-                }
-            })?)
+    #[track_caller]
+    pub fn wait_until_or_timeout_named<F>(
+        &self,
+        label: &'static str,
+        condition: F,
+        duration: Duration,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0.intercept_labeled(
+            OperationKind::Wait,
+            "wait_until_or_timeout",
+            Some(label),
+            || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(Instant::now().checked_add(duration)),
+                    )?
+                    .into_reason())
+            },
+        )
     }
 
-    /// Applies a change to the wrapped variable (by calling the given function `modify`) and
-    /// notifies one or all of the other affected threads, depending on the value of `other`.
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than the given duration; if the timeout is reached, the given `fallback` is applied to the
+    /// wrapped variable before the lock is released.
+    ///
+    /// This allows e.g. marking the state as failed without a race with other threads.
+    /// Since the fallback modifies the wrapped variable, all other waiting threads
+    /// are notified afterwards.
     ///
     /// ## Errors
     ///
     /// This function will return an error if the internally used mutex being waited on is
     /// poisoned when this thread re-acquires the lock.
     /// For more information, see information about poisoning on the Mutex type.
-    pub fn modify_and_notify<F>(&self, modify: F, other: Other) -> Result<(), PoisonedError>
+    #[track_caller]
+    pub fn wait_until_or_else<C, F>(
+        &self,
+        condition: C,
+        duration: Duration,
+        fallback: F,
+    ) -> Result<Reason, PoisonedError>
     where
-        F: Fn(&mut T),
+        C: Fn(&T) -> bool,
+        F: FnOnce(&mut T),
     {
-        let mut mtx_guard = self.0.mtx.lock()?;
-        modify(&mut *mtx_guard);
-        match other {
-            Other::One => self.0.cvar.notify_one(),
-            Other::All => self.0.cvar.notify_all(),
-        }
-        Ok(())
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_or_else", || {
+                let mtx_guard = self.0.lock()?;
+                let mut waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| condition(v).then_some(()),
+                    remaining_until(Instant::now().checked_add(duration)),
+                )?;
+                let reason = waited.reason();
+                let generation = reason
+                    .is_timeout()
+                    .then(|| self.0.apply(&mut waited.mtx_guard, fallback));
+                if generation.is_some() {
+                    self.0.notify(Other::All);
+                }
+                waited.finish();
+                if let Some(generation) = generation {
+                    self.0.modified(generation, Other::All);
+                }
+                Ok(reason)
+            })
     }
-}
 
-impl<T> Clone for CondSync<T> {
-    fn clone(&self) -> Self {
-        Self(Arc::clone(&self.0))
+    /// Wraps an expensive condition such that its result is cached until the wrapped variable
+    /// is modified the next time, for use with [`CondSync::wait_until_memoized`] and
+    /// [`CondSync::wait_until_memoized_or_timeout`].
+    ///
+    /// Share the returned value (e.g. in an `Arc`) between all threads that wait for the
+    /// same condition, so that the condition is evaluated only once per modification.
+    ///
+    /// Only available with feature `generations`.
+    #[cfg(feature = "generations")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+    pub fn memoized<F>(&self, condition: F) -> Memoized<F>
+    where
+        F: Fn(&T) -> bool,
+    {
+        Memoized::new(condition, self.id())
     }
-}
 
-impl<T> CondSync<T>
-where
-    T: Clone,
-{
-    /// Produces a detached clone of the contained variable.
-    #[must_use]
-    pub fn clone_inner(&self) -> T {
+    /// Blocks the current thread until the given memoized condition,
+    /// when called with the current value of the wrapped variable, returns `true`.
+    ///
+    /// The condition is only evaluated if its result is not yet known for the current
+    /// value of the wrapped variable.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the memoized condition was not created by this instance.
+    ///
+    /// Only available with feature `generations`.
+    #[cfg(feature = "generations")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+    #[track_caller]
+    pub fn wait_until_memoized<F>(&self, condition: &Memoized<F>) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
         self.0
-            .mtx
-            .lock()
-            .unwrap_or_else(PoisonError::into_inner)
-            .clone()
+            .intercept(OperationKind::Wait, "wait_until_memoized", || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| self.evaluate_memoized(condition, v).then_some(()),
+                        || None,
+                    )?
+                    .into_reason())
+            })
     }
-}
 
-/// Helper enum to decide if one or all of the other threads should be notified.
-#[derive(Copy, Clone)]
-pub enum Other {
-    /// One of the other threads should be notified.
-    One,
-    /// All other threads should be notified.
-    All,
-}
+    /// Blocks the current thread until the given memoized condition,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than the given duration.
+    ///
+    /// The condition is only evaluated if its result is not yet known for the current
+    /// value of the wrapped variable.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the memoized condition was not created by this instance.
+    ///
+    /// Only available with feature `generations`.
+    #[cfg(feature = "generations")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+    #[track_caller]
+    pub fn wait_until_memoized_or_timeout<F>(
+        &self,
+        condition: &Memoized<F>,
+        duration: Duration,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0.intercept(
+            OperationKind::Wait,
+            "wait_until_memoized_or_timeout",
+            || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| self.evaluate_memoized(condition, v).then_some(()),
+                        remaining_until(Instant::now().checked_add(duration)),
+                    )?
+                    .into_reason())
+            },
+        )
+    }
 
-/// Describes why the method returned (if it returned successfully).
-#[derive(Copy, Clone)]
-pub enum Reason {
-    /// The timeout was reached.
-    Timeout,
-    /// A notification was received and the condition is fulfilled.
-    Condition,
-    /// A notification was received.
-    Notification,
-}
-impl Reason {
-    /// Convenience method to check the concrete reason.
-    #[must_use]
-    pub fn is_timeout(&self) -> bool {
-        matches!(&self, Self::Timeout)
+    // must be called while holding the lock
+    #[cfg(feature = "generations")]
+    fn evaluate_memoized<F>(&self, condition: &Memoized<F>, value: &T) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        assert_eq!(
+            condition.owner(),
+            self.id(),
+            "the memoized condition belongs to another instance"
+        );
+        condition.evaluate(value, self.0.generation.load(Ordering::SeqCst))
     }
-    /// Convenience method to check the concrete reason.
-    #[must_use]
-    pub fn is_condition(&self) -> bool {
-        matches!(&self, Self::Condition)
+
+    // identifies the instance
+    #[cfg(feature = "generations")]
+    fn id(&self) -> usize {
+        Arc::as_ptr(&self.0).cast::<()>() as usize
     }
-    /// Convenience method to check the concrete reason.
-    #[must_use]
-    pub fn is_notification(&self) -> bool {
-        matches!(&self, Self::Notification)
+
+    /// Blocks the current thread until one of the given conditions,
+    /// when called with the current value of the wrapped variable, returns `true`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the index of the first fulfilled condition.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `conditions` is empty.
+    #[track_caller]
+    pub fn wait_any_indexed(
+        &self,
+        conditions: &[&dyn Fn(&T) -> bool],
+    ) -> Result<(usize, Reason), PoisonedError> {
+        self.0
+            .intercept(OperationKind::Wait, "wait_any_indexed", || {
+                assert!(!conditions.is_empty(), "no condition given");
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| conditions.iter().position(|c| c(v)),
+                    || None,
+                )?;
+                let reason = waited.reason();
+                Ok((waited.finish().unwrap_or_default(), reason))
+            })
     }
-}
 
-/// The inner mutex got poisoned.
-///
-/// This most likely happens if one of the provided closures panics.
-#[derive(Debug)]
-pub struct PoisonedError;
-impl<T> From<PoisonError<T>> for PoisonedError {
-    fn from(_e: PoisonError<T>) -> PoisonedError {
-        PoisonedError
+    /// Blocks the current thread until one of the given conditions,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the index of the first fulfilled condition, or `None` if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_any_indexed_or_timeout(
+        &self,
+        conditions: &[&dyn Fn(&T) -> bool],
+        duration: Duration,
+    ) -> Result<(Option<usize>, Reason), PoisonedError> {
+        self.0
+            .intercept(OperationKind::Wait, "wait_any_indexed_or_timeout", || {
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| conditions.iter().position(|c| c(v)),
+                    remaining_until(Instant::now().checked_add(duration)),
+                )?;
+                let reason = waited.reason();
+                Ok((waited.finish(), reason))
+            })
+    }
+
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than until the given [`Deadline`] is reached.
+    ///
+    /// Other threads can extend the deadline or let it expire immediately while this thread
+    /// is waiting; the deadline should have been created with [`CondSync::deadline`] on this
+    /// instance, otherwise such changes only take effect with the next wakeup.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_or_deadline<F>(
+        &self,
+        condition: F,
+        deadline: &Deadline,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_or_deadline", || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        || deadline.remaining_if_any(),
+                    )?
+                    .into_reason())
+            })
+    }
+
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than until the wall clock reaches the given point in time.
+    ///
+    /// The remaining time is re-calculated from the wall clock after every wakeup,
+    /// and the thread wakes up at least once per second for that purpose,
+    /// so that adjustments of the system clock are taken into account.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_system_time<F>(
+        &self,
+        condition: F,
+        deadline: SystemTime,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_system_time", || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        || {
+                            Some(
+                                deadline
+                                    .duration_since(SystemTime::now())
+                                    .unwrap_or_default()
+                                    .min(WALL_CLOCK_SLICE),
+                            )
+                        },
+                    )?
+                    .into_reason())
+            })
+    }
+
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// but blocks at most for `slice` at a time, and calls `yield_fn` in between.
+    ///
+    /// This lets a cooperative scheduler that multiplexes tasks over a few threads
+    /// run other tasks in `yield_fn`, instead of being blocked for the whole wait.
+    /// The lock is not held while `yield_fn` runs; modifications that happen meanwhile
+    /// are not missed, as the condition is evaluated again afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::{thread, time::Duration};
+    ///
+    /// let cond_sync = CondSync::new(false);
+    /// let cond_sync_t = cond_sync.clone();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     cond_sync_t.modify_and_notify(|v| *v = true, Other::All).unwrap();
+    /// });
+    ///
+    /// let mut yields = 0;
+    /// cond_sync
+    ///     .wait_until_yielding(|v| *v, Duration::from_millis(1), || {
+    ///         yields += 1; // ...run other tasks...
+    ///     })
+    ///     .unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_yielding<F, Y>(
+        &self,
+        condition: F,
+        slice: Duration,
+        mut yield_fn: Y,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+        Y: FnMut(),
+    {
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_yielding", || loop {
+                let mtx_guard = self.0.lock()?;
+                let reason = self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(Instant::now().checked_add(slice)),
+                    )?
+                    .into_reason();
+                if reason.is_condition() {
+                    return Ok(reason);
+                }
+                yield_fn();
+            })
+    }
+
+    /// Blocks the current thread until a notification is received, but no longer
+    /// than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `true` if the timeout was reached, and `false` otherwise.
+    ///
+    /// With [sticky notifications](CondSyncBuilder::sticky_notifications), a notification
+    /// that was sent while no thread was waiting is consumed by this call,
+    /// which then returns immediately.
+    ///
+    /// With feature `notifications`, spurious wakeups are not reported as notifications;
+    /// the thread continues to wait.
+    /// Notifications that are sent directly with the [raw condvar](CondSync::as_condvar)
+    /// cannot be told apart from spurious wakeups, and are ignored, too.
+    /// Changes of a [`Deadline`] and cancellations of a [`CancelToken`] are no notifications.
+    /// Without feature `notifications`, every wakeup before the timeout is reported as
+    /// notification.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_timeout(&self, duration: Duration) -> Result<Reason, PoisonedError> {
+        self.0.intercept(OperationKind::Wait, "wait_timeout", || {
+            let mut mtx_guard = self.0.lock()?;
+            #[cfg(feature = "notifications")]
+            if self.0.take_pending_notification() {
+                return Ok(Reason::Notification);
+            }
+            let end = Instant::now() + duration;
+            #[cfg(feature = "notifications")]
+            let epoch = self.0.notify_epoch.load(Ordering::SeqCst);
+            loop {
+                let remaining = end.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(Reason::Timeout);
+                }
+                let timeout = (self.0.spurious_wakeup_after())
+                    .map_or(remaining, |spurious| spurious.min(remaining));
+                let result = self
+                    .0
+                    .blocking(|| self.0.cvar.wait_timeout(mtx_guard, timeout))?;
+                mtx_guard = result.0;
+                // spurious wakeups continue the wait
+                #[cfg(feature = "notifications")]
+                if self.0.take_pending_notification()
+                    || self.0.notify_epoch.load(Ordering::SeqCst) != epoch
+                {
+                    return Ok(Reason::Notification);
+                }
+                #[cfg(not(feature = "notifications"))]
+                if !result.1.timed_out() {
+                    return Ok(Reason::Notification);
+                }
+            }
+        })
+    }
+
+    /// Applies a change to the wrapped variable (by calling the given function `modify`) and
+    /// notifies one or all of the other affected threads, depending on the value of `other`.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn modify_and_notify<F>(&self, modify: F, other: Other) -> Result<(), PoisonedError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.0.modify("modify_and_notify", modify, Some(other))
+    }
+
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// and then applies the modification, without releasing the lock in between,
+    /// so that no other thread can interfere.
+    ///
+    /// `notify` decides which of the other threads are notified after the modification;
+    /// with `None`, nobody is notified.
+    /// A consumer that takes an item out can so wake up a producer in the same call.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    ///
+    /// let queue = CondSync::new(vec![1, 2, 3]);
+    /// let mut item = None;
+    /// queue
+    ///     .wait_until_and_modify(|v| !v.is_empty(), |v| item = v.pop(), Other::One)
+    ///     .unwrap();
+    /// assert_eq!(item, Some(3));
+    ///
+    /// // modify without notifying
+    /// queue.wait_until_and_modify(|_| true, |v| v.clear(), None).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_and_modify<C, M, N>(
+        &self,
+        condition: C,
+        modify: M,
+        notify: N,
+    ) -> Result<(), PoisonedError>
+    where
+        C: Fn(&T) -> bool,
+        M: FnOnce(&mut T),
+        N: Into<Option<Other>>,
+    {
+        let notify = notify.into();
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_and_modify", || {
+                let mtx_guard = self.0.lock()?;
+                let mut waited =
+                    self.0
+                        .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?;
+                let generation = self.0.apply(&mut waited.mtx_guard, modify);
+                if let Some(other) = notify {
+                    self.0.notify(other);
+                }
+                waited.finish();
+                self.0.modified(generation, notify);
+                Ok(())
+            })
+    }
+}
+
+impl<T> CondSync<T>
+where
+    T: Debug,
+{
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`, but no longer
+    /// than the given duration; reaching the timeout is reported as an error.
+    ///
+    /// The error describes the wait: the method, the optional label, the call site,
+    /// the configured duration, the actually elapsed time, and the state of the wrapped
+    /// variable at expiry.
+    ///
+    /// ## Errors
+    ///
+    /// [`WaitError::Timeout`] if the timeout was reached before the condition was fulfilled.
+    ///
+    /// [`WaitError::Poisoned`] if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_or_timeout_error<F>(
+        &self,
+        label: Option<&'static str>,
+        condition: F,
+        duration: Duration,
+    ) -> Result<(), WaitError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.wait_or_timeout_error("wait_until_or_timeout_error", label, condition, duration)
+    }
+
+    // Waits like wait_until_or_timeout, and describes a timeout with a TimeoutError.
+    #[track_caller]
+    pub(crate) fn wait_or_timeout_error<F>(
+        &self,
+        method: &'static str,
+        label: Option<&'static str>,
+        condition: F,
+        duration: Duration,
+    ) -> Result<(), WaitError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let location = Location::caller();
+        let start = Instant::now();
+        let (state, _reason) =
+            self.0
+                .intercept_labeled(OperationKind::Wait, method, label, || {
+                    let mtx_guard = self.0.lock()?;
+                    let waited = self.0.wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(start.checked_add(duration)),
+                    )?;
+                    let reason = waited.reason();
+                    let state = reason
+                        .is_timeout()
+                        .then(|| format!("{:?}", *waited.mtx_guard));
+                    waited.finish();
+                    Ok((state, reason))
+                })?;
+        match state {
+            Some(state) => Err(WaitError::Timeout(TimeoutError {
+                method,
+                label,
+                location,
+                duration,
+                elapsed: start.elapsed(),
+                state,
+            })),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> CondSync<T>
+where
+    T: Send + 'static,
+{
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// or until the given [`CancelToken`] (or one of its ancestors) is cancelled.
+    ///
+    /// ## Returns
+    ///
+    /// [`Reason::Condition`] if the condition is fulfilled (even if the token was
+    /// cancelled as well), and [`Reason::Cancelled`] otherwise.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_or_cancelled<F>(
+        &self,
+        condition: F,
+        token: &CancelToken,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_or_cancelled", || {
+                token.register(Arc::downgrade(&self.0) as Weak<dyn Wake>);
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| {
+                        if condition(v) {
+                            Some(Reason::Condition)
+                        } else {
+                            token.is_cancelled().then_some(Reason::Cancelled)
+                        }
+                    },
+                    || None,
+                )?;
+                Ok(waited
+                    .finish()
+                    .unwrap_or_else(|| unreachable!("unbounded waits only end with the condition")))
+            })
+    }
+
+    /// Schedules a notification of waiting threads after the given duration,
+    /// without a modification of the wrapped variable.
+    ///
+    /// This is useful e.g. for waking up a coordinator if nothing else happened in time.
+    /// Note that threads waiting in [`CondSync::wait_until`] and its variants re-evaluate
+    /// their condition and continue waiting if it does not hold;
+    /// [`CondSync::wait_timeout`] returns with [`Reason::Notification`].
+    ///
+    /// The notification is sent by the crate's internal timer thread.
+    /// It can be cancelled with the returned handle; dropping the handle does not cancel it.
+    // the handle is not needed if the notification is never cancelled
+    #[allow(clippy::must_use_candidate)]
+    pub fn notify_after(&self, duration: Duration, other: Other) -> ScheduledNotification {
+        let i = Arc::clone(&self.0);
+        let task = Box::new(move || {
+            let _mtx_guard = i.lock_unpoisoned();
+            i.notify(other);
+        });
+        let at = Instant::now().checked_add(duration);
+        ScheduledNotification(at.map(|at| timer::schedule(at, task)))
+    }
+
+    /// Notifies waiting threads periodically, until the returned guard is dropped.
+    ///
+    /// Threads waiting in [`CondSync::wait_until`] and its variants re-evaluate their
+    /// condition with every tick, which allows conditions that also depend on
+    /// external resources.
+    ///
+    /// The notifications are sent by the crate's internal timer thread.
+    /// Ticking also stops when all instances of this `CondSync` are dropped.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn notify_every(&self, interval: Duration, other: Other) -> Ticker {
+        assert!(!interval.is_zero(), "interval must not be zero");
+        let stopped = Arc::new(AtomicBool::new(false));
+        if let Some(at) = Instant::now().checked_add(interval) {
+            schedule_tick(
+                Arc::downgrade(&self.0),
+                at,
+                interval,
+                other,
+                Arc::clone(&stopped),
+            );
+        }
+        Ticker(stopped)
+    }
+
+    /// Creates a [`Deadline`] that expires after the given duration,
+    /// for use with [`CondSync::wait_until_or_deadline`].
+    ///
+    /// Changes to the deadline wake up the threads that are waiting on this instance.
+    #[must_use]
+    pub fn deadline(&self, duration: Duration) -> Deadline {
+        Deadline::new(duration, Arc::clone(&self.0) as Arc<dyn Wake>)
+    }
+
+    /// Applies a change to the wrapped variable (by calling the given function `modify`) and
+    /// notifies the waiting threads one after the other, with the given interval in between.
+    ///
+    /// Compared to [`Other::All`], this avoids that many waiting threads are woken up at
+    /// once and then all at the same time hit some downstream resource.
+    /// The first waiting thread is notified immediately, the others are notified by the
+    /// crate's timer thread. The number of notifications is determined by the number of
+    /// threads that are waiting when this method is called.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    ///
+    /// Only available with feature `notifications`.
+    #[cfg(feature = "notifications")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notifications")))]
+    #[track_caller]
+    pub fn modify_and_notify_staggered<F>(
+        &self,
+        modify: F,
+        interval: Duration,
+    ) -> Result<(), PoisonedError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.0
+            .intercept(OperationKind::Modify, "modify_and_notify_staggered", || {
+                let mut mtx_guard = self.0.lock()?;
+                let generation = self.0.apply(&mut mtx_guard, modify);
+                self.0.notify(Other::One);
+                let waiters = self.0.waiters.load(Ordering::SeqCst);
+                drop(mtx_guard);
+                self.0.modified(generation, Other::One);
+
+                let start = Instant::now();
+                let mut at = start;
+                for _ in 1..waiters {
+                    at = match at.checked_add(interval) {
+                        Some(at) => at,
+                        None => break,
+                    };
+                    let i = Arc::clone(&self.0);
+                    let _ = timer::schedule(at, Box::new(move || i.wake_one()));
+                }
+                Ok(())
+            })
+    }
+}
+
+impl<T> I<T> {
+    fn lock(&self) -> Result<MutexGuard<'_, T>, CondSyncError> {
+        Ok(self.lock_checked()??)
+    }
+
+    // Like lock, for operations that cannot fail: poisoning is ignored,
+    // and a re-entrant call panics.
+    fn lock_unpoisoned(&self) -> MutexGuard<'_, T> {
+        match self.lock_checked() {
+            Ok(result) => result.unwrap_or_else(PoisonError::into_inner),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    // The outer error reports a re-entrant call, which would deadlock.
+    #[cfg_attr(not(feature = "lock_order"), allow(clippy::unnecessary_wraps))]
+    fn lock_checked(&self) -> Result<LockResult<MutexGuard<'_, T>>, CondSyncError> {
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        #[cfg(feature = "lock_order")]
+        lock_order::acquiring(self.lock_order.id())?;
+        let result = self.mtx.lock();
+        #[cfg(feature = "stats")]
+        self.record_stats(|stats| stats.lock_acquisition.record(start.elapsed()));
+        Ok(result)
+    }
+
+    // Runs `f`, which must be called while holding the lock, e.g. to lock another instance.
+    #[cfg_attr(not(feature = "lock_order"), allow(clippy::unused_self))]
+    fn holding<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        #[cfg(feature = "lock_order")]
+        return lock_order::holding(self.lock_order.id(), f);
+        #[cfg(not(feature = "lock_order"))]
+        f()
+    }
+
+    // Every modification of the value must be done with this method,
+    // which returns the new generation (or zero, without feature `generations`).
+    fn apply<F: FnOnce(&mut T)>(&self, value: &mut T, modify: F) -> u64 {
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        self.holding(|| modify(value));
+        self.refresh_copies(value);
+        #[cfg(feature = "stats")]
+        self.record_stats(|stats| stats.modification.record(start.elapsed()));
+        #[cfg(feature = "generations")]
+        return self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "generations"))]
+        0
+    }
+
+    // Brings the copies of the value, which are read without the lock, up to date.
+    #[cfg_attr(
+        not(any(feature = "optimistic_reads", feature = "snapshots")),
+        allow(clippy::unused_self, unused_variables)
+    )]
+    fn refresh_copies(&self, value: &T) {
+        #[cfg(feature = "optimistic_reads")]
+        if let Some(mirror) = self.mirror.get() {
+            mirror.update(value);
+        }
+        #[cfg(feature = "snapshots")]
+        if let Some(snapshot) = self.snapshot.get() {
+            snapshot.update(value);
+        }
+    }
+
+    // The common modifying operation: applies the modification, and notifies the other
+    // threads if `notify` is given.
+    #[track_caller]
+    fn modify<F>(
+        &self,
+        method: &'static str,
+        modify: F,
+        notify: Option<Other>,
+    ) -> Result<(), PoisonedError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.intercept(OperationKind::Modify, method, || {
+            let mut mtx_guard = self.lock()?;
+            let generation = self.apply(&mut mtx_guard, modify);
+            if let Some(other) = notify {
+                self.notify(other);
+            }
+            drop(mtx_guard);
+            self.modified(generation, notify);
+            Ok(())
+        })
+    }
+
+    // Brings an instance that is no longer in use back into its initial state, for reuse;
+    // the exclusive access ensures that no clone, handle, or timer refers to it anymore.
+    // The generation is continued, so that memoized conditions do not confuse old and new
+    // values.
+    fn reset(&mut self, initial: T) {
+        *self.mtx.get_mut().unwrap_or_else(PoisonError::into_inner) = initial;
+        #[cfg(feature = "generations")]
+        {
+            *self.generation.get_mut() += 1;
+        }
+        #[cfg(feature = "notifications")]
+        {
+            *self.pending_notifications.get_mut() = 0;
+            *self.raw_condvar.get_mut() = false;
+        }
+        #[cfg(feature = "observers")]
+        {
+            // handles of the previous user unregister from the old list
+            self.observers = Arc::default();
+        }
+        #[cfg(feature = "async")]
+        {
+            self.wakers
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+            *self.waker_count.get_mut() = 0;
+        }
+        #[cfg(all(unix, feature = "readiness"))]
+        self.readiness.take();
+        #[cfg(feature = "tags")]
+        self.tags
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        #[cfg(feature = "stats")]
+        {
+            *self.stats.get_mut().unwrap_or_else(PoisonError::into_inner) =
+                ContentionStats::default();
+        }
+    }
+
+    // Runs the given operation, surrounded by the calls to the interceptors, if there are any.
+    #[track_caller]
+    fn intercept<R, F>(
+        &self,
+        kind: OperationKind,
+        method: &'static str,
+        f: F,
+    ) -> Result<R, PoisonedError>
+    where
+        R: Intercepted,
+        F: FnOnce() -> Result<R, PoisonedError>,
+    {
+        self.intercept_labeled(kind, method, None, f)
+    }
+
+    // Like intercept, with a label given by the caller.
+    #[track_caller]
+    fn intercept_labeled<R, F>(
+        &self,
+        kind: OperationKind,
+        method: &'static str,
+        label: Option<&'static str>,
+        f: F,
+    ) -> Result<R, PoisonedError>
+    where
+        R: Intercepted,
+        F: FnOnce() -> Result<R, PoisonedError>,
+    {
+        self.intercept_at(kind, method, label, Location::caller(), true, f)
+    }
+
+    // Like intercept_labeled, with the call site given by the caller,
+    // for operations that are reported by several instances;
+    // only one of them must report the operation to the global interceptor.
+    #[cfg_attr(
+        not(any(feature = "interceptors", feature = "stats", feature = "diagnostics")),
+        allow(clippy::unused_self)
+    )]
+    fn intercept_at<R, F>(
+        &self,
+        kind: OperationKind,
+        method: &'static str,
+        label: Option<&'static str>,
+        location: &'static Location<'static>,
+        with_global: bool,
+        f: F,
+    ) -> Result<R, PoisonedError>
+    where
+        R: Intercepted,
+        F: FnOnce() -> Result<R, PoisonedError>,
+    {
+        #[cfg(feature = "stats")]
+        let f = || {
+            let start = Instant::now();
+            let result = f();
+            if kind == OperationKind::Wait {
+                let timed_out = result
+                    .as_ref()
+                    .ok()
+                    .and_then(Intercepted::reason)
+                    .is_some_and(|reason| reason.is_timeout());
+                let elapsed = start.elapsed();
+                self.record_stats(|stats| {
+                    stats.wait.record(elapsed);
+                    stats.timeouts += u64::from(timed_out);
+                    if stats
+                        .longest_wait
+                        .map_or(true, |(longest, _)| elapsed > longest)
+                    {
+                        stats.longest_wait = Some((elapsed, location));
+                    }
+                });
+            }
+            result
+        };
+        #[cfg(feature = "diagnostics")]
+        let _registration = (kind == OperationKind::Wait)
+            .then(|| diagnostics::register(self.id(), method, label, location));
+
+        #[cfg(feature = "interceptors")]
+        {
+            let global = with_global.then(interceptor::global).flatten();
+            let interceptors: Vec<&dyn Interceptor> = global
+                .as_deref()
+                .into_iter()
+                .chain(self.config.interceptor.as_deref())
+                .collect();
+            if !interceptors.is_empty() {
+                let operation = Operation::new(kind, method, label, location);
+                for interceptor in &interceptors {
+                    interceptor.before(&operation);
+                }
+                let start = Instant::now();
+                let result = f();
+                let outcome = Outcome::new(
+                    start.elapsed(),
+                    result.as_ref().ok().and_then(Intercepted::reason),
+                    result.is_err(),
+                );
+                for interceptor in interceptors.iter().rev() {
+                    interceptor.after(&operation, &outcome);
+                }
+                return result;
+            }
+        }
+        #[cfg(not(feature = "interceptors"))]
+        let _ = (kind, method, label, location, with_global);
+        f()
+    }
+
+    // identifies the instance, e.g. in diagnostics
+    #[cfg(feature = "diagnostics")]
+    fn id(&self) -> usize {
+        (self as *const Self).cast::<()>() as usize
+    }
+
+    #[cfg(feature = "stats")]
+    fn record_stats<F: FnOnce(&mut ContentionStats)>(&self, f: F) {
+        f(&mut self.stats.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+
+    // informs the observers about a modification; must be called without holding the lock
+    #[cfg_attr(not(feature = "observers"), allow(clippy::unused_self))]
+    fn modified(&self, generation: u64, other: impl Into<Option<Other>>) {
+        #[cfg(feature = "observers")]
+        self.observers.modified(generation, other);
+        #[cfg(not(feature = "observers"))]
+        let _ = (generation, other);
+    }
+
+    // must be called while holding the lock
+    fn notify(&self, other: Other) {
+        #[cfg(feature = "stats")]
+        self.record_stats(|stats| stats.notifications += 1);
+        #[cfg(feature = "notifications")]
+        if self.config.counting_notifications {
+            self.pending_notifications.fetch_add(1, Ordering::SeqCst);
+        } else if self.config.sticky_notifications {
+            self.pending_notifications.store(1, Ordering::SeqCst);
+        }
+        #[cfg(feature = "testing")]
+        let Some(other) = self
+            .chaos
+            .as_ref()
+            .map_or(Some(other), |c| c.intervene(other))
+        else {
+            return;
+        };
+        #[cfg(feature = "debounce")]
+        if let Some(ref debouncer) = self.debouncer {
+            if debouncer.defer(other) {
+                return;
+            }
+        }
+        self.notify_now(other);
+    }
+
+    // must be called while holding the lock
+    fn notify_now(&self, other: Other) {
+        // waiters register under the lock, so if there is none, there is nobody to notify;
+        // threads that wait directly on the condvar are not counted, though
+        #[cfg(feature = "notifications")]
+        if self.waiters.load(Ordering::SeqCst) > 0 || self.raw_condvar.load(Ordering::SeqCst) {
+            self.signal(other);
+        } else {
+            #[cfg(feature = "stats")]
+            self.record_stats(|stats| stats.skipped_notifications += 1);
+        }
+        #[cfg(not(feature = "notifications"))]
+        self.signal(other);
+        #[cfg(feature = "async")]
+        self.wake_wakers();
+        #[cfg(all(unix, feature = "readiness"))]
+        if let Some(readiness) = self.readiness.get() {
+            readiness.signal();
+        }
+    }
+
+    // must be called while holding the lock
+    fn signal(&self, other: Other) {
+        // lets waiting threads distinguish notifications from spurious wakeups
+        #[cfg(feature = "notifications")]
+        self.notify_epoch.fetch_add(1, Ordering::SeqCst);
+        match other {
+            Other::One => self.cvar.notify_one(),
+            Other::All => self.cvar.notify_all(),
+        }
+    }
+
+    // wakes and forgets all registered wakers
+    #[cfg(feature = "async")]
+    fn wake_wakers(&self) {
+        if self.waker_count.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let wakers = {
+            let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+            self.waker_count.store(0, Ordering::SeqCst);
+            std::mem::take(&mut *wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    // must be called while holding the lock
+    #[cfg(feature = "notifications")]
+    fn take_pending_notification(&self) -> bool {
+        if self.config.counting_notifications {
+            self.pending_notifications
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+        } else {
+            self.config.sticky_notifications
+                && self.pending_notifications.swap(0, Ordering::SeqCst) > 0
+        }
+    }
+
+    // The common waiting loop: evaluates `check` until it returns `Some`, and blocks in between,
+    // until `remaining` returns a zero duration (`None` stands for "no time limit").
+    fn wait_for<'a, R, C, D>(
+        &'a self,
+        mtx_guard: MutexGuard<'a, T>,
+        check: C,
+        remaining: D,
+    ) -> Result<Waited<'a, T, R>, PoisonedError>
+    where
+        C: FnMut(&mut T) -> Option<R>,
+        D: FnMut() -> Option<Duration>,
+    {
+        self.wait_for_on(&self.cvar, mtx_guard, check, remaining)
+    }
+
+    // Like `wait_for`, but blocks on the given condvar, which must be used with `self.mtx`.
+    fn wait_for_on<'a, R, C, D>(
+        &'a self,
+        cvar: &Condvar,
+        mut mtx_guard: MutexGuard<'a, T>,
+        mut check: C,
+        mut remaining: D,
+    ) -> Result<Waited<'a, T, R>, PoisonedError>
+    where
+        C: FnMut(&mut T) -> Option<R>,
+        D: FnMut() -> Option<Duration>,
+    {
+        let mut wakeups = Wakeups::default();
+        #[cfg(feature = "stats")]
+        let mut deadline: Option<Instant> = None;
+        loop {
+            #[cfg(feature = "stats")]
+            let start = Instant::now();
+            let outcome = self.holding(|| check(&mut mtx_guard));
+            #[cfg(feature = "stats")]
+            self.record_stats(|stats| stats.condition.record(start.elapsed()));
+            if let Some(outcome) = outcome {
+                #[cfg(feature = "stats")]
+                if let Some(spare) = remaining() {
+                    self.record_stats(|stats| stats.timing.record_satisfied(spare));
+                }
+                return Ok(Waited::new(self, mtx_guard, Some(outcome), wakeups));
+            }
+            let remaining = remaining();
+            if remaining.is_some_and(|duration| duration.is_zero()) {
+                #[cfg(feature = "stats")]
+                self.record_stats(|stats| {
+                    stats.timing.record_expired(
+                        deadline.map_or(Duration::ZERO, |deadline| deadline.elapsed()),
+                    );
+                });
+                return Ok(Waited::new(self, mtx_guard, None, wakeups));
+            }
+            #[cfg(feature = "stats")]
+            {
+                deadline = remaining.and_then(|remaining| Instant::now().checked_add(remaining));
+            }
+            let timeout = match (remaining, self.spurious_wakeup_after()) {
+                (Some(remaining), Some(spurious)) => Some(remaining.min(spurious)),
+                (remaining, spurious) => remaining.or(spurious),
+            };
+            #[cfg(feature = "generations")]
+            let generation = self.generation.load(Ordering::SeqCst);
+            let result = self.blocking(|| match timeout {
+                None => cvar.wait(mtx_guard).map(|mtx_guard| (mtx_guard, false)),
+                Some(duration) => cvar
+                    .wait_timeout(mtx_guard, duration)
+                    .map(|(mtx_guard, result)| (mtx_guard, result.timed_out()))
+                    .map_err(|e| PoisonError::new(e.into_inner().0)),
+            });
+            let timed_out;
+            (mtx_guard, timed_out) = result?;
+            #[cfg(feature = "generations")]
+            wakeups.record(
+                timed_out,
+                self.generation.load(Ordering::SeqCst) == generation,
+            );
+            #[cfg(not(feature = "generations"))]
+            wakeups.record(timed_out);
+        }
+    }
+
+    // Runs `f`, which blocks on a condvar of this instance; meanwhile, the current thread
+    // is counted as waiter.
+    #[cfg_attr(not(feature = "notifications"), allow(clippy::unused_self))]
+    fn blocking<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        #[cfg(feature = "notifications")]
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        let result = f();
+        #[cfg(feature = "notifications")]
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+        result
+    }
+
+    // Limits the duration of the next single wait, to inject spurious wakeups.
+    #[cfg_attr(not(feature = "testing"), allow(clippy::unused_self))]
+    fn spurious_wakeup_after(&self) -> Option<Duration> {
+        #[cfg(feature = "testing")]
+        if let Some(ref chaos) = self.chaos {
+            return chaos.spurious_wakeup_after();
+        }
+        None
+    }
+
+    // is called after a wait that was woken up, without holding the lock
+    #[cfg_attr(not(feature = "jitter"), allow(clippy::unused_self))]
+    fn after_wakeup(&self) {
+        #[cfg(feature = "jitter")]
+        if let Some(max_jitter) = self.wakeup_jitter.get() {
+            std::thread::sleep(self.rng.duration_up_to(max_jitter));
+        }
+    }
+}
+
+// Counts the wakeups of a wait; wakeups without a modification since the thread started
+// waiting are considered spurious.
+#[derive(Clone, Copy, Default)]
+struct Wakeups {
+    count: usize,
+    #[cfg(feature = "generations")]
+    spurious: usize,
+    #[cfg(feature = "generations")]
+    last_spurious: bool,
+}
+impl Wakeups {
+    #[cfg(feature = "generations")]
+    fn record(&mut self, timed_out: bool, unmodified: bool) {
+        self.count += 1;
+        self.last_spurious = unmodified && !timed_out;
+        self.spurious += usize::from(self.last_spurious);
+    }
+
+    #[cfg(not(feature = "generations"))]
+    fn record(&mut self, _timed_out: bool) {
+        self.count += 1;
+    }
+}
+
+// The result of `I::wait_for`; `finish` releases the lock.
+struct Waited<'a, T, R> {
+    i: &'a I<T>,
+    mtx_guard: MutexGuard<'a, T>,
+    outcome: Option<R>,
+    wakeups: Wakeups,
+}
+impl<'a, T, R> Waited<'a, T, R> {
+    fn new(
+        i: &'a I<T>,
+        mtx_guard: MutexGuard<'a, T>,
+        outcome: Option<R>,
+        wakeups: Wakeups,
+    ) -> Self {
+        Self {
+            i,
+            mtx_guard,
+            outcome,
+            wakeups,
+        }
+    }
+
+    fn reason(&self) -> Reason {
+        if self.outcome.is_some() {
+            Reason::Condition
+        } else {
+            Reason::Timeout
+        }
+    }
+
+    fn into_reason(self) -> Reason {
+        let reason = self.reason();
+        self.finish();
+        reason
+    }
+
+    #[cfg(feature = "generations")]
+    fn into_wait_outcome(self) -> WaitOutcome {
+        let outcome = WaitOutcome {
+            reason: self.reason(),
+            wakeups: self.wakeups.count,
+            spurious_wakeups: self.wakeups.spurious,
+            last_wakeup_spurious: self.wakeups.last_spurious,
+            generation: self.i.generation.load(Ordering::SeqCst),
+        };
+        self.finish();
+        outcome
+    }
+
+    fn finish(self) -> Option<R> {
+        let Self {
+            i,
+            mtx_guard,
+            outcome,
+            wakeups,
+        } = self;
+        drop(mtx_guard);
+        if wakeups.count > 0 {
+            i.after_wakeup();
+        }
+        outcome
+    }
+}
+
+// For timed waits; `None` as end means that there is no time limit.
+pub(crate) fn remaining_until(end: Option<Instant>) -> impl FnMut() -> Option<Duration> {
+    move || end.map(|end| end.saturating_duration_since(Instant::now()))
+}
+
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+impl<T> CondSync<T> {
+    /// Returns information about the threads that are currently waiting on this instance,
+    /// including the call sites of the waits.
+    ///
+    /// Only available with feature `diagnostics`.
+    #[must_use]
+    pub fn waiting(&self) -> Vec<WaitInfo> {
+        let id = self.0.id();
+        dump_all()
+            .into_iter()
+            .filter(|wait_info| wait_info.instance() == id)
+            .collect()
+    }
+
+    /// Returns the longest wait that is currently in progress on this instance, if any.
+    ///
+    /// Together with [`ContentionStats::longest_wait`] (feature `stats`), this allows
+    /// alerting on a single number.
+    ///
+    /// Only available with feature `diagnostics`.
+    #[must_use]
+    pub fn longest_waiting(&self) -> Option<WaitInfo> {
+        self.waiting().into_iter().max_by_key(WaitInfo::elapsed)
+    }
+}
+
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+impl<T> CondSync<T> {
+    /// Returns statistics about how long threads had to wait for the internal mutex,
+    /// and how long it was held for modifications and for evaluating conditions.
+    ///
+    /// Only available with feature `stats`.
+    #[must_use]
+    pub fn contention_stats(&self) -> ContentionStats {
+        let mut stats = self
+            .0
+            .stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        stats.waiters = self.0.waiters.load(Ordering::SeqCst);
+        stats
+    }
+
+    /// Returns statistics about the waits with a time limit: how often they were satisfied
+    /// or expired, how much time was left when they were satisfied, and how late they
+    /// returned when they expired.
+    ///
+    /// Only available with feature `stats`.
+    #[must_use]
+    pub fn timing_stats(&self) -> TimingStats {
+        self.0
+            .stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .timing
+            .clone()
+    }
+
+    /// Resets the statistics that are returned by [`CondSync::contention_stats`]
+    /// and [`CondSync::timing_stats`].
+    ///
+    /// Only available with feature `stats`.
+    pub fn reset_contention_stats(&self) {
+        self.0
+            .record_stats(|stats| *stats = ContentionStats::default());
+    }
+}
+
+impl<T> Clone for CondSync<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> CondSync<T>
+where
+    T: Clone,
+{
+    /// Produces a detached clone of the contained variable.
+    ///
+    /// A poisoned mutex is ignored; use [`CondSync::try_clone_inner`]
+    /// if poisoning should be noticed.
+    #[must_use]
+    pub fn clone_inner(&self) -> T {
+        self.0.lock_unpoisoned().clone()
+    }
+
+    /// Produces a detached clone of the contained variable.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned,
+    /// i.e., if a thread panicked while modifying the variable.
+    pub fn try_clone_inner(&self) -> Result<T, PoisonedError> {
+        Ok(self.0.lock()?.clone())
+    }
+
+    /// Returns a blocking iterator that yields a clone of the wrapped variable
+    /// whenever it was modified and the given predicate holds for the new state.
+    ///
+    /// Only states produced after the creation of the iterator are considered,
+    /// and every modification is yielded at most once.
+    /// Since waiting threads can miss intermediate states, the iterator is best suited
+    /// for predicates that describe a state that persists until the consumer reacts to it.
+    ///
+    /// Use [`Filtered::until`] or [`Filtered::timeout`] to let the iterator end.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::thread;
+    ///
+    /// let cond_sync = CondSync::new((0_usize, false));
+    /// let cond_sync_t = cond_sync.clone();
+    /// let consumer = thread::spawn(move || {
+    ///     cond_sync_t
+    ///         .filtered(|(batch, _)| *batch >= 3)
+    ///         .until(|(_, shutdown)| *shutdown)
+    ///         .count()
+    /// });
+    ///
+    /// // ...
+    /// cond_sync
+    ///     .modify_and_notify(|(_, shutdown)| *shutdown = true, Other::All)
+    ///     .unwrap();
+    /// assert_eq!(consumer.join().unwrap(), 0);
+    /// ```
+    ///
+    /// Only available with feature `generations`.
+    #[cfg(feature = "generations")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+    pub fn filtered<F>(&self, predicate: F) -> Filtered<'_, T, F>
+    where
+        F: Fn(&T) -> bool,
+    {
+        Filtered::new(self, predicate)
+    }
+}
+
+impl<T> CondSync<T>
+where
+    T: Default,
+{
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// then takes the value out and leaves `T::default()` behind, without releasing the lock
+    /// in between.
+    ///
+    /// This is convenient for batch accumulators, where a consumer wants to take over
+    /// everything that was collected so far.
+    /// Since the wrapped variable is modified, all other waiting threads
+    /// are notified afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    ///
+    /// let cond_sync = CondSync::new(Vec::<usize>::new());
+    /// cond_sync.modify_and_notify(|v| v.push(1), Other::All).unwrap();
+    /// cond_sync.modify_and_notify(|v| v.push(2), Other::All).unwrap();
+    ///
+    /// assert_eq!(cond_sync.wait_drain(|v| !v.is_empty()).unwrap(), vec![1, 2]);
+    /// assert!(cond_sync.try_clone_inner().unwrap().is_empty());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_drain<F>(&self, condition: F) -> Result<T, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (taken, _) = self.0.intercept(OperationKind::Wait, "wait_drain", || {
+            let mtx_guard = self.0.lock()?;
+            let waited = self
+                .0
+                .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?;
+            Ok(self.drain(waited))
+        })?;
+        Ok(taken.unwrap_or_else(|| unreachable!("unbounded waits only end with the condition")))
+    }
+
+    /// Like [`CondSync::wait_drain`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The taken value, or `None` if the timeout was reached; in the latter case the wrapped
+    /// variable is left untouched.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_drain_or_timeout<F>(
+        &self,
+        condition: F,
+        duration: Duration,
+    ) -> Result<Option<T>, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (taken, _) = self
+            .0
+            .intercept(OperationKind::Wait, "wait_drain_or_timeout", || {
+                let mtx_guard = self.0.lock()?;
+                let waited = self.0.wait_for(
+                    mtx_guard,
+                    |v| condition(v).then_some(()),
+                    remaining_until(Instant::now().checked_add(duration)),
+                )?;
+                Ok(self.drain(waited))
+            })?;
+        Ok(taken)
+    }
+
+    // takes the value out if the wait was successful, and releases the lock
+    fn drain(&self, mut waited: Waited<'_, T, ()>) -> (Option<T>, Reason) {
+        let reason = waited.reason();
+        let mut taken = None;
+        let generation = waited.outcome.is_some().then(|| {
+            self.0.apply(&mut waited.mtx_guard, |v| {
+                taken = Some(std::mem::take(v));
+            })
+        });
+        if generation.is_some() {
+            self.0.notify(Other::All);
+        }
+        waited.finish();
+        if let Some(generation) = generation {
+            self.0.modified(generation, Other::All);
+        }
+        (taken, reason)
+    }
+}
+
+// Type-erased access to the condvar, for handles that need to wake up waiting threads.
+trait Wake: Send + Sync {
+    #[cfg(feature = "notifications")]
+    fn wake_one(&self);
+    fn wake_all(&self);
+    #[cfg_attr(not(any(feature = "debounce", feature = "testing")), allow(dead_code))]
+    fn wake(&self, other: Other);
+}
+impl<T: Send> Wake for I<T> {
+    fn wake(&self, other: Other) {
+        let _mtx_guard = self.lock_unpoisoned();
+        self.notify_now(other);
+    }
+
+    #[cfg(feature = "notifications")]
+    fn wake_one(&self) {
+        let _mtx_guard = self.lock_unpoisoned();
+        self.signal(Other::One);
+    }
+
+    fn wake_all(&self) {
+        // taking the lock ensures that no waiter misses the wakeup;
+        // it is no notification, so `wait_timeout` continues to wait
+        let _mtx_guard = self.lock_unpoisoned();
+        self.cvar.notify_all();
+    }
+}
+
+/// Helper enum to decide if one or all of the other threads should be notified.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Other {
+    /// One of the other threads should be notified.
+    One,
+    /// All other threads should be notified.
+    All,
+}
+
+/// Handle for a notification that was scheduled with [`CondSync::notify_after`].
+pub struct ScheduledNotification(Option<TimerHandle>);
+impl ScheduledNotification {
+    /// Cancels the notification.
+    ///
+    /// Returns `true` if the notification was cancelled before it was sent.
+    pub fn cancel(&self) -> bool {
+        self.0.as_ref().is_some_and(TimerHandle::cancel)
+    }
+
+    /// Returns `true` if the notification was neither sent nor cancelled yet.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.0.as_ref().is_some_and(TimerHandle::is_pending)
+    }
+}
+
+/// Guard for the periodic notifications started with [`CondSync::notify_every`];
+/// stops them when dropped.
+#[must_use = "the ticking stops immediately if the guard is not kept"]
+pub struct Ticker(Arc<AtomicBool>);
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// Lets the timer notify at `at`, and reschedule itself, as long as the ticker is alive.
+fn schedule_tick<T: Send + 'static>(
+    i: Weak<I<T>>,
+    at: Instant,
+    interval: Duration,
+    other: Other,
+    stopped: Arc<AtomicBool>,
+) {
+    let task = Box::new(move || {
+        if stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        let Some(i_strong) = i.upgrade() else {
+            return;
+        };
+        {
+            let _mtx_guard = i_strong.lock_unpoisoned();
+            i_strong.notify(other);
+        }
+        // a fixed rate, but without catching up on ticks that were missed
+        let next = (at + interval).max(Instant::now());
+        schedule_tick(i, next, interval, other, stopped);
+    });
+    let _ = timer::schedule(at, task);
+}
+
+/// Describes why the method returned (if it returned successfully).
+///
+/// New reasons may be added in future versions, as new ways to end a wait are added;
+/// matches should therefore have a wildcard arm, or use [`Reason::into_result`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The timeout was reached.
+    Timeout,
+    /// A notification was received and the condition is fulfilled.
+    Condition,
+    /// A notification was received.
+    Notification,
+    /// The [`CancelToken`] was cancelled.
+    Cancelled,
+}
+impl Reason {
+    /// Convenience method to check the concrete reason.
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        matches!(&self, Self::Timeout)
+    }
+    /// Convenience method to check the concrete reason.
+    #[must_use]
+    pub fn is_condition(&self) -> bool {
+        matches!(&self, Self::Condition)
+    }
+    /// Convenience method to check the concrete reason.
+    #[must_use]
+    pub fn is_notification(&self) -> bool {
+        matches!(&self, Self::Notification)
+    }
+    /// Convenience method to check the concrete reason.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(&self, Self::Cancelled)
+    }
+
+    /// Separates the reasons where the wait got what it was waiting for
+    /// (the condition, or a notification) from the others (like a timeout or a cancellation),
+    /// so that the latter can be handled with `?`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Reason};
+    /// use std::time::Duration;
+    ///
+    /// fn wait_ready(cond_sync: &CondSync<bool>) -> Result<(), Reason> {
+    ///     cond_sync
+    ///         .wait_until_or_timeout(|ready| *ready, Duration::from_millis(10))
+    ///         .expect("poisoned")
+    ///         .into_result()?;
+    ///     // ...
+    ///     Ok(())
+    /// }
+    /// assert_eq!(wait_ready(&CondSync::new(false)), Err(Reason::Timeout));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The reason, if it is neither [`Reason::Condition`] nor [`Reason::Notification`].
+    pub fn into_result(self) -> Result<Reason, Reason> {
+        match self {
+            Self::Condition | Self::Notification => Ok(self),
+            Self::Timeout | Self::Cancelled => Err(self),
+        }
+    }
+}
+
+/// The error of the methods of [`CondSync`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CondSyncError {
+    /// The inner mutex got poisoned.
+    ///
+    /// This most likely happens if one of the provided closures panics.
+    Poisoned,
+    /// The current thread holds the lock of the instance already, e.g. because a condition
+    /// or a modification calls a method of the same instance; locking it again would deadlock.
+    ///
+    /// The instance is identified by the same sequence number as in
+    /// [`LockOrderInversion::instances`].
+    ///
+    /// Only returned with feature `lock_order`, which detects such calls.
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl std::fmt::Display for CondSyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Poisoned => write!(f, "cond_sync: the internally used mutex is poisoned"),
+            Self::WouldDeadlock { instance } => write!(
+                f,
+                "cond_sync: re-entrant call would deadlock: the current thread holds the lock \
+                 of instance #{instance} already, e.g. because a condition or a modification \
+                 calls a method of the same instance"
+            ),
+        }
+    }
+}
+impl<T> From<PoisonError<T>> for CondSyncError {
+    fn from(_e: PoisonError<T>) -> CondSyncError {
+        CondSyncError::Poisoned
+    }
+}
+
+/// The former name of [`CondSyncError`], from when poisoning was the only error.
+pub type PoisonedError = CondSyncError;
+
+// The default build has no features, and its shared state must have the layout of the
+// `(Mutex<T>, Condvar)` that the crate started from, whatever the wrapped type is.
+#[cfg(all(
+    test,
+    not(any(
+        feature = "generations",
+        feature = "notifications",
+        feature = "optimistic_reads",
+        feature = "observers",
+        feature = "async",
+        feature = "debounce",
+        feature = "jitter",
+        feature = "tags",
+        feature = "readiness",
+        feature = "interceptors",
+        feature = "stats",
+        feature = "snapshots",
+        feature = "diagnostics",
+        feature = "lock_order",
+        feature = "testing",
+        feature = "bench"
+    ))
+))]
+mod default_build {
+    use super::{CondSync, I};
+    use std::{
+        mem::{align_of, size_of},
+        sync::{Condvar, Mutex},
+    };
+
+    fn assert_baseline_layout<T>() {
+        assert_eq!(size_of::<I<T>>(), size_of::<(Mutex<T>, Condvar)>());
+        assert_eq!(align_of::<I<T>>(), align_of::<(Mutex<T>, Condvar)>());
+    }
+
+    #[test]
+    fn test_layout_of_shared_state() {
+        assert_baseline_layout::<()>();
+        assert_baseline_layout::<bool>();
+        assert_baseline_layout::<u64>();
+        assert_baseline_layout::<[u8; 100]>();
+        assert_baseline_layout::<String>();
+        assert_baseline_layout::<Vec<u64>>();
+        // the handle is just the Arc
+        assert_eq!(size_of::<CondSync<u64>>(), size_of::<usize>());
     }
 }
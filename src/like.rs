@@ -0,0 +1,94 @@
+use crate::{CondSync, Other, PoisonedError, Reason};
+
+/// Captures the core operations of [`CondSync`], so that coordination code can be written
+/// once and be used with different implementations, e.g. a blocking and an async one.
+///
+/// The results of the operations are associated types, so that an async implementation
+/// can return futures where [`CondSync`] returns the plain results.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CondSync, CondSyncLike, Other};
+/// use std::thread;
+///
+/// fn count_to_three<C>(cond_sync: &C)
+/// where
+///     C: CondSyncLike<usize> + Send + 'static,
+/// {
+///     for _ in 0..3 {
+///         let cond_sync_t = cond_sync.clone_handle();
+///         thread::spawn(move || {
+///             let _ = cond_sync_t.modify_and_notify(|v| *v += 1, Other::One);
+///         });
+///     }
+///     let _ = cond_sync.wait_until(|v| *v == 3);
+/// }
+///
+/// count_to_three(&CondSync::new(0_usize));
+/// ```
+pub trait CondSyncLike<T> {
+    /// The result of [`CondSyncLike::wait_until`].
+    type WaitResult<'a, F>
+    where
+        Self: 'a,
+        F: 'a;
+
+    /// The result of [`CondSyncLike::modify_and_notify`].
+    type ModifyResult<'a, F>
+    where
+        Self: 'a,
+        F: 'a;
+
+    /// Waits until the given condition is fulfilled.
+    ///
+    /// See [`CondSync::wait_until`].
+    fn wait_until<'a, F>(&'a self, condition: F) -> Self::WaitResult<'a, F>
+    where
+        F: Fn(&T) -> bool + 'a;
+
+    /// Modifies the wrapped variable and notifies waiting threads or tasks.
+    ///
+    /// See [`CondSync::modify_and_notify`].
+    fn modify_and_notify<'a, F>(&'a self, modify: F, other: Other) -> Self::ModifyResult<'a, F>
+    where
+        F: FnOnce(&mut T) + 'a;
+
+    /// Returns a further handle to the same shared state.
+    #[must_use]
+    fn clone_handle(&self) -> Self
+    where
+        Self: Sized;
+}
+
+impl<T> CondSyncLike<T> for CondSync<T> {
+    type WaitResult<'a, F>
+        = Result<Reason, PoisonedError>
+    where
+        Self: 'a,
+        F: 'a;
+
+    type ModifyResult<'a, F>
+        = Result<(), PoisonedError>
+    where
+        Self: 'a,
+        F: 'a;
+
+    fn wait_until<'a, F>(&'a self, condition: F) -> Self::WaitResult<'a, F>
+    where
+        F: Fn(&T) -> bool + 'a,
+    {
+        CondSync::wait_until(self, condition)
+    }
+
+    fn modify_and_notify<'a, F>(&'a self, modify: F, other: Other) -> Self::ModifyResult<'a, F>
+    where
+        F: FnOnce(&mut T) + 'a,
+    {
+        CondSync::modify_and_notify(self, modify, other)
+    }
+
+    fn clone_handle(&self) -> Self {
+        self.clone()
+    }
+}
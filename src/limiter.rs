@@ -0,0 +1,89 @@
+use crate::{CondSync, Other, PoisonedError};
+
+/// Limits the number of units of work that are in flight at the same time.
+///
+/// [`Limiter::enter`] blocks until the number of active units is below the limit,
+/// and returns a [`LimiterGuard`] that releases the slot again when it is dropped.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Limiter;
+/// use std::thread;
+///
+/// let limiter = Limiter::new(2);
+///
+/// for i in 0..5 {
+///     // blocks while two threads are still working:
+///     let guard = limiter.enter().unwrap();
+///     thread::spawn(move || {
+///         println!("Thread {i}: working");
+///         drop(guard); // <- frees the slot
+///     });
+/// }
+/// // [main thread] wait until all threads have finished their work:
+/// limiter.wait_idle().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Limiter {
+    cond_sync: CondSync<usize>,
+    max_in_flight: usize,
+}
+
+impl Limiter {
+    /// Construct a new instance that allows at most `max_in_flight` active units.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_in_flight` is 0.
+    #[must_use]
+    pub fn new(max_in_flight: usize) -> Self {
+        assert!(max_in_flight > 0, "max_in_flight must be at least 1");
+        Self {
+            cond_sync: CondSync::new(0),
+            max_in_flight,
+        }
+    }
+
+    /// Blocks the current thread until the number of active units is below the limit,
+    /// and then occupies a slot.
+    ///
+    /// The slot is freed when the returned guard is dropped.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn enter(&self) -> Result<LimiterGuard, PoisonedError> {
+        let max_in_flight = self.max_in_flight;
+        self.cond_sync
+            .wait_until_and_modify(|v| *v < max_in_flight, |v| *v += 1, Other::All)?;
+        Ok(LimiterGuard(self.cond_sync.clone()))
+    }
+
+    /// Blocks the current thread until no unit is active anymore.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn wait_idle(&self) -> Result<(), PoisonedError> {
+        self.cond_sync.wait_until(|v| *v == 0)?;
+        Ok(())
+    }
+
+    /// Returns the number of currently active units.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.cond_sync.clone_inner()
+    }
+}
+
+/// Occupies a slot of a [`Limiter`], frees it when dropped.
+#[must_use = "the slot is freed immediately if the guard is not kept"]
+pub struct LimiterGuard(CondSync<usize>);
+
+impl Drop for LimiterGuard {
+    fn drop(&mut self) {
+        // both `enter` and `wait_idle` may be waiting, so we need to notify all
+        self.0.modify_and_notify(|v| *v -= 1, Other::All).ok();
+    }
+}
@@ -0,0 +1,188 @@
+use crate::CondSyncError;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+    thread::{self, ThreadId},
+};
+
+// For each pair of instances that was locked in a nested way, as (outer, inner),
+// the thread that did it first.
+static ORDERS: Mutex<BTreeMap<(usize, usize), ThreadId>> = Mutex::new(BTreeMap::new());
+// The inversions that were detected so far.
+static INVERSIONS: Mutex<Vec<LockOrderInversion>> = Mutex::new(Vec::new());
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    // The instances whose locks are held by the current thread, in the order of acquisition.
+    static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Two instances whose internal locks were acquired in opposite orders by different threads.
+///
+/// If both threads do this at the same time, they deadlock.
+///
+/// Only available with feature `lock_order`.
+#[derive(Clone, Debug)]
+pub struct LockOrderInversion {
+    first: usize,
+    second: usize,
+    established_by: ThreadId,
+    inverted_by: ThreadId,
+}
+impl LockOrderInversion {
+    /// Identifies the two [`CondSync`](crate::CondSync) instances, by sequence numbers that are
+    /// assigned when they are created, in the order in which they were locked first.
+    #[must_use]
+    pub fn instances(&self) -> (usize, usize) {
+        (self.first, self.second)
+    }
+
+    /// The thread that locked the instances first, in the order given by
+    /// [`instances`](LockOrderInversion::instances).
+    #[must_use]
+    pub fn established_by(&self) -> ThreadId {
+        self.established_by
+    }
+
+    /// The thread that locked the instances in the opposite order.
+    #[must_use]
+    pub fn inverted_by(&self) -> ThreadId {
+        self.inverted_by
+    }
+}
+impl fmt::Display for LockOrderInversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lock-order inversion: instances #{} and #{} were locked in this order by \
+             thread {:?}, and in the opposite order by thread {:?}",
+            self.first, self.second, self.established_by, self.inverted_by
+        )
+    }
+}
+
+/// Returns all lock-order inversions that were detected so far.
+///
+/// With feature `interceptors`, every inversion is also reported to the
+/// [global interceptor](crate::set_global_interceptor) when it is detected,
+/// see [`Interceptor::lock_order_inversion`](crate::Interceptor::lock_order_inversion).
+///
+/// The detection covers the locks that this crate takes while it runs a closure of the caller,
+/// like a condition or a modification, and the locks of [`multi_modify`](crate::multi_modify).
+/// Guards of the [raw mutex](crate::CondSync::as_mutex), which are held outside of this crate,
+/// are not tracked.
+///
+/// Besides that, the feature detects re-entrant calls, where a condition or a modification
+/// calls a blocking method of the same instance: such a call returns
+/// [`CondSyncError::WouldDeadlock`], rather than deadlocking silently.
+/// Methods that cannot return an error, and methods whose error types cannot express it,
+/// panic with the description of the error instead.
+///
+/// Only available with feature `lock_order`.
+#[must_use]
+pub fn lock_order_inversions() -> Vec<LockOrderInversion> {
+    INVERSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+// Records that the current thread is about to lock the given instance,
+// and reports an inversion if the instance was ever locked before one it holds now.
+// Fails if the current thread holds the lock of the instance already,
+// because locking it again would deadlock.
+pub(crate) fn acquiring(instance: usize) -> Result<(), CondSyncError> {
+    let held = HELD.with(|held| held.borrow().clone());
+    if held.is_empty() {
+        return Ok(());
+    }
+    if held.contains(&instance) {
+        return Err(CondSyncError::WouldDeadlock { instance });
+    }
+    let current = thread::current().id();
+    let mut detected = Vec::new();
+    {
+        let mut orders = ORDERS.lock().unwrap_or_else(PoisonError::into_inner);
+        for outer in held.into_iter().filter(|outer| *outer != instance) {
+            orders.entry((outer, instance)).or_insert(current);
+            if let Some(&established_by) = orders.get(&(instance, outer)) {
+                if established_by != current {
+                    detected.push(LockOrderInversion {
+                        first: instance,
+                        second: outer,
+                        established_by,
+                        inverted_by: current,
+                    });
+                }
+            }
+        }
+    }
+    // the global interceptor is called without holding the locks of the bookkeeping
+    for inversion in &detected {
+        report(inversion);
+    }
+    Ok(())
+}
+
+fn report(inversion: &LockOrderInversion) {
+    {
+        let mut inversions = INVERSIONS.lock().unwrap_or_else(PoisonError::into_inner);
+        if inversions
+            .iter()
+            .any(|i| i.instances() == inversion.instances())
+        {
+            return;
+        }
+        inversions.push(inversion.clone());
+    }
+    #[cfg(feature = "interceptors")]
+    if let Some(interceptor) = crate::interceptor::global() {
+        interceptor.lock_order_inversion(inversion);
+    }
+}
+
+// Marks the lock of the given instance as held by the current thread while `f` runs.
+pub(crate) fn holding<R, F: FnOnce() -> R>(instance: usize, f: F) -> R {
+    HELD.with(|held| held.borrow_mut().push(instance));
+    let _release = Release(instance);
+    f()
+}
+
+// Unmarks the lock also if `f` panics.
+struct Release(usize);
+impl Drop for Release {
+    fn drop(&mut self) {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(pos) = held.iter().rposition(|i| *i == self.0) {
+                held.remove(pos);
+            }
+        });
+    }
+}
+
+// Identifies an instance; its recorded lock orders are forgotten when it is dropped.
+pub(crate) struct Tracked(usize);
+impl Tracked {
+    pub(crate) fn new() -> Self {
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub(crate) fn id(&self) -> usize {
+        self.0
+    }
+}
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        ORDERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|&(outer, inner), _| outer != self.0 && inner != self.0);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    time::{Duration, Instant},
+};
+
+/// A mailbox where receivers always get the pending message with the highest priority.
+///
+/// The priority of a message is given by its [`Ord`] implementation; messages of equal
+/// priority are received in the order in which they were sent.
+/// Sending never blocks; receiving blocks while the mailbox is empty.
+///
+/// Clones share the same mailbox.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::PriorityMailbox;
+///
+/// #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// enum Command {
+///     Report(u32),
+///     Shutdown, // the highest priority, as the last variant
+/// }
+///
+/// let mailbox = PriorityMailbox::new();
+/// mailbox.send(Command::Report(1)).unwrap();
+/// mailbox.send(Command::Report(2)).unwrap();
+/// mailbox.send(Command::Shutdown).unwrap();
+///
+/// // the urgent command jumps the queue
+/// assert_eq!(mailbox.wait_recv().unwrap(), Command::Shutdown);
+/// assert_eq!(mailbox.wait_recv().unwrap(), Command::Report(2));
+/// ```
+pub struct PriorityMailbox<T>(CondSync<Messages<T>>);
+
+struct Messages<T> {
+    heap: BinaryHeap<Entry<T>>,
+    sent: u64,
+}
+
+// Orders by priority, and then by the order of sending.
+struct Entry<T> {
+    message: T,
+    seq: Reverse<u64>,
+}
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.message
+            .cmp(&other.message)
+            .then(self.seq.cmp(&other.seq))
+    }
+}
+impl<T: Ord> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T: Ord> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<T: Ord> Eq for Entry<T> {}
+
+impl<T: Ord> PriorityMailbox<T> {
+    /// Construct a new, empty mailbox.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CondSync::new(Messages {
+            heap: BinaryHeap::new(),
+            sent: 0,
+        }))
+    }
+
+    /// Adds the message, and wakes up a waiting receiver.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn send(&self, message: T) -> Result<(), PoisonedError> {
+        self.0.modify_and_notify(
+            |messages| {
+                messages.heap.push(Entry {
+                    message,
+                    seq: Reverse(messages.sent),
+                });
+                messages.sent += 1;
+            },
+            Other::One,
+        )
+    }
+
+    /// Removes the message with the highest priority,
+    /// and blocks the current thread while the mailbox is empty.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_recv(&self) -> Result<T, PoisonedError> {
+        self.recv_until("wait_recv", None).map(|message| {
+            message.unwrap_or_else(|| unreachable!("unbounded waits only end with the condition"))
+        })
+    }
+
+    /// Like [`PriorityMailbox::wait_recv`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The message with the highest priority, or `None` if the mailbox remained empty.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_recv_timeout(&self, duration: Duration) -> Result<Option<T>, PoisonedError> {
+        self.recv_until("wait_recv_timeout", Instant::now().checked_add(duration))
+    }
+
+    /// Removes the message with the highest priority if there is one, without blocking.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn try_recv(&self) -> Result<Option<T>, PoisonedError> {
+        self.recv_until("try_recv", Some(Instant::now()))
+    }
+
+    /// Returns the number of pending messages.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn len(&self) -> Result<usize, PoisonedError> {
+        Ok(self.0 .0.lock()?.heap.len())
+    }
+
+    /// Returns `true` if no message is pending.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn is_empty(&self) -> Result<bool, PoisonedError> {
+        Ok(self.len()? == 0)
+    }
+
+    #[track_caller]
+    fn recv_until(
+        &self,
+        method: &'static str,
+        end: Option<Instant>,
+    ) -> Result<Option<T>, PoisonedError> {
+        let (message, _) = self.0 .0.intercept(OperationKind::Wait, method, || {
+            let mtx_guard = self.0 .0.lock()?;
+            let mut waited = self.0 .0.wait_for(
+                mtx_guard,
+                |messages| (!messages.heap.is_empty()).then_some(()),
+                remaining_until(end),
+            )?;
+            let reason = waited.reason();
+            let mut message = None;
+            if reason.is_condition() {
+                self.0 .0.apply(&mut waited.mtx_guard, |messages| {
+                    message = messages.heap.pop().map(|entry| entry.message);
+                });
+            }
+            waited.finish();
+            Ok((message, reason))
+        })?;
+        Ok(message)
+    }
+}
+
+impl<T: Ord> Default for PriorityMailbox<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PriorityMailbox<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// marks the cache as empty
+const NOTHING_CACHED: u64 = u64::MAX;
+
+/// A condition whose result is cached per modification of the wrapped variable.
+///
+/// Is created with [`CondSync::memoized`](crate::CondSync::memoized) and
+/// used with [`CondSync::wait_until_memoized`](crate::CondSync::wait_until_memoized).
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CondSync, Other};
+/// use std::{sync::Arc, thread};
+///
+/// let cond_sync = CondSync::new(Vec::<u64>::new());
+/// // expensive condition, shared by all waiting threads:
+/// let enough = Arc::new(cond_sync.memoized(|v| v.iter().sum::<u64>() > 1_000));
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let (cond_sync_t, enough_t) = (cond_sync.clone(), Arc::clone(&enough));
+///         thread::spawn(move || cond_sync_t.wait_until_memoized(&enough_t).unwrap())
+///     })
+///     .collect();
+///
+/// for i in 0..100 {
+///     cond_sync.modify_and_notify(|v| v.push(i), Other::All).unwrap();
+/// }
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+///
+/// Only available with feature `generations`.
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+pub struct Memoized<F> {
+    condition: F,
+    // the generation of the value, shifted left by one, plus the result in the lowest bit
+    cache: AtomicU64,
+    owner: usize,
+}
+
+impl<F> Memoized<F> {
+    pub(crate) fn new(condition: F, owner: usize) -> Self {
+        Self {
+            condition,
+            cache: AtomicU64::new(NOTHING_CACHED),
+            owner,
+        }
+    }
+
+    pub(crate) fn owner(&self) -> usize {
+        self.owner
+    }
+
+    // must be called while holding the lock of the owner
+    pub(crate) fn evaluate<T>(&self, value: &T, generation: u64) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        let cached = self.cache.load(Ordering::SeqCst);
+        if cached != NOTHING_CACHED && cached >> 1 == generation {
+            return cached & 1 == 1;
+        }
+        let result = (self.condition)(value);
+        self.cache
+            .store((generation << 1) | u64::from(result), Ordering::SeqCst);
+        result
+    }
+}
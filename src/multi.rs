@@ -0,0 +1,96 @@
+use crate::{CondSync, OperationKind, Other, PoisonedError};
+use std::{panic::Location, sync::Arc};
+
+/// Modifies the wrapped variables of two instances together, and then notifies the waiting
+/// threads of both instances.
+///
+/// The internal locks of both instances are held while `modify` runs, so no thread sees
+/// one modification without the other.
+/// The locks are acquired in a stable global order, so concurrent calls with the same instances
+/// in any order cannot deadlock each other.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{multi_modify, CondSync, Other};
+///
+/// let accounts = CondSync::new(100_u32);
+/// let audit_log = CondSync::new(Vec::<String>::new());
+///
+/// multi_modify(
+///     (&accounts, &audit_log),
+///     |balance, log| {
+///         *balance -= 30;
+///         log.push("withdrew 30".to_string());
+///     },
+///     Other::All,
+/// )
+/// .unwrap();
+/// assert_eq!(accounts.clone_inner(), 70);
+/// ```
+///
+/// ## Errors
+///
+/// This function will return an error if one of the internally used mutexes is poisoned;
+/// then nothing is modified.
+///
+/// ## Panics
+///
+/// Panics if both arguments are clones of the same instance.
+#[track_caller]
+pub fn multi_modify<A, B, F>(
+    cond_syncs: (&CondSync<A>, &CondSync<B>),
+    modify: F,
+    other: Other,
+) -> Result<(), PoisonedError>
+where
+    F: FnOnce(&mut A, &mut B),
+{
+    let (a, b) = cond_syncs;
+    let address_a = Arc::as_ptr(&a.0).cast::<()>() as usize;
+    let address_b = Arc::as_ptr(&b.0).cast::<()>() as usize;
+    assert_ne!(
+        address_a, address_b,
+        "multi_modify needs two different instances"
+    );
+
+    // both instances report the operation to their interceptors,
+    // but the global interceptor sees it only once
+    let location = Location::caller();
+    a.0.intercept_at(
+        OperationKind::Modify,
+        "multi_modify",
+        None,
+        location,
+        true,
+        || {
+            b.0.intercept_at(
+                OperationKind::Modify,
+                "multi_modify",
+                None,
+                location,
+                false,
+                || {
+                    let (mut guard_a, mut guard_b) = if address_a < address_b {
+                        let guard_a = a.0.lock()?;
+                        (guard_a, a.0.holding(|| b.0.lock())?)
+                    } else {
+                        let guard_b = b.0.lock()?;
+                        (b.0.holding(|| a.0.lock())?, guard_b)
+                    };
+                    let mut generation_b = 0;
+                    let generation_a = a.0.apply(&mut guard_a, |value_a| {
+                        generation_b = b.0.apply(&mut guard_b, |value_b| modify(value_a, value_b));
+                    });
+                    a.0.notify(other);
+                    b.0.notify(other);
+                    drop(guard_a);
+                    drop(guard_b);
+                    a.0.modified(generation_a, other);
+                    b.0.modified(generation_b, other);
+                    Ok(())
+                },
+            )
+        },
+    )
+}
@@ -0,0 +1,123 @@
+use crate::Other;
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc, Mutex, PoisonError, Weak,
+};
+
+/// Describes a modification of the wrapped variable, or a notification of waiting threads.
+///
+/// Is passed to the callbacks that are registered with
+/// [`CondSync::on_modify`](crate::CondSync::on_modify) and
+/// [`CondSync::on_notify`](crate::CondSync::on_notify).
+///
+/// Only available with feature `observers`.
+#[cfg_attr(docsrs, doc(cfg(feature = "observers")))]
+#[derive(Clone, Copy, Debug)]
+pub struct Event {
+    generation: u64,
+    other: Option<Other>,
+}
+impl Event {
+    /// The number of modifications of the wrapped variable, including this one.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Which of the waiting threads were notified,
+    /// or `None` for modifications that did not notify anybody.
+    #[must_use]
+    pub fn other(&self) -> Option<Other> {
+        self.other
+    }
+}
+
+type Callback = Arc<dyn Fn(&Event) + Send + Sync>;
+
+#[derive(Default)]
+pub(crate) struct Observers {
+    next_id: AtomicU64,
+    // allows skipping the lock when no observer is registered
+    count: AtomicUsize,
+    // (id, is_notify_observer, callback)
+    entries: Mutex<Vec<(u64, bool, Callback)>>,
+}
+
+impl Observers {
+    pub(crate) fn register(
+        this: &Arc<Self>,
+        on_notify: bool,
+        callback: Callback,
+    ) -> ObserverHandle {
+        let id = this.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut entries = this.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.push((id, on_notify, callback));
+        this.count.store(entries.len(), Ordering::SeqCst);
+        ObserverHandle {
+            observers: Arc::downgrade(this),
+            id,
+        }
+    }
+
+    fn unregister(&self, id: u64) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.retain(|(entry_id, _, _)| *entry_id != id);
+        self.count.store(entries.len(), Ordering::SeqCst);
+    }
+
+    // must be called without holding the lock of the CondSync;
+    // notify observers are skipped if nobody was notified
+    pub(crate) fn modified<O: Into<Option<Other>>>(&self, generation: u64, other: O) {
+        if self.count.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let callbacks: Vec<(bool, Callback)> = self
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(_, on_notify, callback)| (*on_notify, Arc::clone(callback)))
+            .collect();
+        let event = Event {
+            generation,
+            other: other.into(),
+        };
+        // modify observers first, then notify observers
+        let phases: &[bool] = if event.other.is_some() {
+            &[false, true]
+        } else {
+            &[false]
+        };
+        for on_notify in phases {
+            for (_, callback) in callbacks.iter().filter(|(n, _)| n == on_notify) {
+                callback(&event);
+            }
+        }
+    }
+}
+
+/// Keeps an observer registered, see [`CondSync::on_modify`](crate::CondSync::on_modify) and
+/// [`CondSync::on_notify`](crate::CondSync::on_notify).
+///
+/// The observer is removed when the handle is dropped, unless [`ObserverHandle::detach`]
+/// was called.
+///
+/// Only available with feature `observers`.
+#[cfg_attr(docsrs, doc(cfg(feature = "observers")))]
+#[must_use = "the observer is removed immediately if the handle is not kept"]
+pub struct ObserverHandle {
+    observers: Weak<Observers>,
+    id: u64,
+}
+impl ObserverHandle {
+    /// Keeps the observer registered for the lifetime of the `CondSync`.
+    pub fn detach(mut self) {
+        self.observers = Weak::new();
+    }
+}
+impl Drop for ObserverHandle {
+    fn drop(&mut self) {
+        if let Some(observers) = self.observers.upgrade() {
+            observers.unregister(self.id);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::CondSync;
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+type Init<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// A pool of [`CondSync`] instances, to avoid allocation churn when many short-lived
+/// instances are needed, e.g. one per request.
+///
+/// [`CondSyncPool::checkout`] hands out an instance, wrapped in a [`PooledCondSync`];
+/// when that is dropped, the instance is reset to a fresh value and returned to the pool.
+/// An instance is only recycled if no clones of it are alive anymore,
+/// and nothing else refers to it, like a [`Deadline`](crate::Deadline) or
+/// [`CancelToken`](crate::CancelToken) it was registered with, or a pending timer;
+/// otherwise it is simply dropped.
+/// Observers, waker registrations, and readiness handles of the previous user are not carried
+/// over to the next one.
+///
+/// Clones share the same pool.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{CondSyncPool, Other};
+/// use std::thread;
+///
+/// let pool = CondSyncPool::new(16, || false);
+///
+/// for _request in 0..3 {
+///     let done = pool.checkout();
+///     let done_t = (*done).clone();
+///     thread::spawn(move || {
+///         done_t.modify_and_notify(|v| *v = true, Other::All).unwrap();
+///     });
+///     done.wait_until(|v| *v).unwrap();
+/// }
+/// ```
+pub struct CondSyncPool<T>(Arc<Inner<T>>);
+
+struct Inner<T> {
+    idle: Mutex<Vec<CondSync<T>>>,
+    max_idle: usize,
+    init: Init<T>,
+}
+
+impl<T> CondSyncPool<T> {
+    /// Construct a new, empty pool, which keeps at most `max_idle` unused instances.
+    ///
+    /// `init` produces the value with which instances are created and reset.
+    pub fn new<F>(max_idle: usize, init: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self(Arc::new(Inner {
+            idle: Mutex::new(Vec::with_capacity(max_idle)),
+            max_idle,
+            init: Box::new(init),
+        }))
+    }
+
+    /// Hands out an unused instance from the pool, or a new one if the pool is empty.
+    #[must_use]
+    pub fn checkout(&self) -> PooledCondSync<T> {
+        let cond_sync = self
+            .0
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop()
+            .unwrap_or_else(|| CondSync::new((self.0.init)()));
+        PooledCondSync {
+            cond_sync: Some(cond_sync),
+            pool: Arc::clone(&self.0),
+        }
+    }
+
+    /// Returns the number of unused instances in the pool.
+    #[must_use]
+    pub fn idle(&self) -> usize {
+        self.0
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+impl<T> Clone for CondSyncPool<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+/// A [`CondSync`] that was checked out from a [`CondSyncPool`],
+/// and is returned to it when dropped.
+///
+/// Dereferences to [`CondSync`].
+pub struct PooledCondSync<T> {
+    cond_sync: Option<CondSync<T>>,
+    pool: Arc<Inner<T>>,
+}
+
+impl<T> Deref for PooledCondSync<T> {
+    type Target = CondSync<T>;
+
+    fn deref(&self) -> &CondSync<T> {
+        self.cond_sync
+            .as_ref()
+            .unwrap_or_else(|| unreachable!("only taken on drop"))
+    }
+}
+
+impl<T> Drop for PooledCondSync<T> {
+    fn drop(&mut self) {
+        let Some(mut cond_sync) = self.cond_sync.take() else {
+            return;
+        };
+        // clones of the instance might still be used by other threads,
+        // and handles or timers might still refer to it
+        let Some(i) = Arc::get_mut(&mut cond_sync.0) else {
+            return;
+        };
+        let mut idle = self
+            .pool
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if idle.len() < self.pool.max_idle {
+            i.reset((self.pool.init)());
+            idle.push(cond_sync);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::time::{Duration, Instant};
+
+/// Aggregates the progress of workers that process a known number of units.
+///
+/// Workers report the units they have finished with [`Progress::report`];
+/// observers wait for a certain fraction with [`Progress::wait_until_progress`],
+/// or follow all changes with [`Progress::updates`].
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Progress;
+/// use std::thread;
+///
+/// let progress = Progress::new(100);
+/// for _ in 0..4 {
+///     let progress_t = progress.clone();
+///     thread::spawn(move || {
+///         for _ in 0..25 {
+///             // ...process one unit...
+///             progress_t.report(1).unwrap();
+///         }
+///     });
+/// }
+/// progress.wait_until_progress(0.5).unwrap();
+/// println!("half done, rate: {:.1} units/s", progress.snapshot().unwrap().rate());
+/// progress.wait_until_progress(1.0).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Progress(CondSync<State>);
+
+struct State {
+    done: u64,
+    total: u64,
+    start: Instant,
+}
+impl State {
+    fn snapshot(&self) -> ProgressSnapshot {
+        ProgressSnapshot {
+            done: self.done,
+            total: self.total,
+            elapsed: self.start.elapsed(),
+        }
+    }
+}
+
+impl Progress {
+    /// Construct a new instance for the given total number of units.
+    ///
+    /// The measurement of the rate starts now.
+    #[must_use]
+    pub fn new(total: u64) -> Self {
+        Self(CondSync::new(State {
+            done: 0,
+            total,
+            start: Instant::now(),
+        }))
+    }
+
+    /// Adds the given number of finished units.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn report(&self, units_done: u64) -> Result<(), PoisonedError> {
+        self.0
+            .modify_and_notify(|state| state.done += units_done, Other::All)
+    }
+
+    /// Returns the current state.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn snapshot(&self) -> Result<ProgressSnapshot, PoisonedError> {
+        Ok(self.0 .0.lock()?.snapshot())
+    }
+
+    /// Blocks the current thread until the given fraction (between 0.0 and 1.0)
+    /// of the total units is done.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    pub fn wait_until_progress(&self, fraction: f64) -> Result<ProgressSnapshot, PoisonedError> {
+        self.0
+            .wait_until(|state| state.snapshot().fraction() >= fraction)?;
+        self.snapshot()
+    }
+
+    /// Like [`Progress::wait_until_progress`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The state when the fraction was reached, or `None` if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    pub fn wait_until_progress_or_timeout(
+        &self,
+        fraction: f64,
+        duration: Duration,
+    ) -> Result<Option<ProgressSnapshot>, PoisonedError> {
+        if self
+            .0
+            .wait_until_or_timeout(|state| state.snapshot().fraction() >= fraction, duration)?
+            .is_timeout()
+        {
+            return Ok(None);
+        }
+        self.snapshot().map(Some)
+    }
+
+    /// Returns a blocking iterator that yields a snapshot whenever the progress changed,
+    /// and ends after yielding the completion.
+    #[must_use]
+    pub fn updates(&self) -> ProgressUpdates<'_> {
+        ProgressUpdates {
+            progress: self,
+            last_done: None,
+        }
+    }
+}
+
+/// A blocking iterator over the changes of a [`Progress`]; see [`Progress::updates`].
+///
+/// Ends after the completion was yielded, or if the internal mutex is poisoned.
+pub struct ProgressUpdates<'a> {
+    progress: &'a Progress,
+    last_done: Option<u64>,
+}
+impl Iterator for ProgressUpdates<'_> {
+    type Item = ProgressSnapshot;
+
+    fn next(&mut self) -> Option<ProgressSnapshot> {
+        let last_done = self.last_done;
+        if last_done.is_some_and(|last_done| {
+            self.progress
+                .snapshot()
+                .map_or(true, |snapshot| last_done >= snapshot.total)
+        }) {
+            return None;
+        }
+        self.progress
+            .0
+            .wait_until(|state| Some(state.done) != last_done)
+            .ok()?;
+        let snapshot = self.progress.snapshot().ok()?;
+        self.last_done = Some(snapshot.done);
+        Some(snapshot)
+    }
+}
+
+/// The state of a [`Progress`] at a point in time.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressSnapshot {
+    done: u64,
+    total: u64,
+    elapsed: Duration,
+}
+impl ProgressSnapshot {
+    /// The number of finished units.
+    #[must_use]
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+
+    /// The total number of units.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The time since the creation of the [`Progress`].
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The finished fraction, between 0.0 and 1.0 (or above, if more units were reported
+    /// than expected).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+
+    /// The average number of finished units per second.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rate(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.done as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns `true` if all units are done.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.done >= self.total
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::time::Duration;
+
+/// The producing side of a one-shot value handover; see [`Completer::new`].
+///
+/// Completing consumes the handle, so a single completer can complete at most once.
+/// Clones of a completer compete: the first completion wins, later ones get their value
+/// back in an [`AlreadyCompleted`] error.
+///
+/// If all completers are dropped without completing, the [`Awaiter`] is released with
+/// [`AwaitError::Abandoned`].
+pub struct Completer<T>(CondSync<State<T>>);
+
+/// The consuming side of a one-shot value handover; see [`Completer::new`].
+pub struct Awaiter<T>(CondSync<State<T>>);
+
+struct State<T> {
+    value: Option<T>,
+    completed: bool,
+    completers: usize,
+}
+
+impl<T> Completer<T> {
+    /// Creates a connected pair of a [`Completer`] and an [`Awaiter`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::Completer;
+    /// use std::thread;
+    ///
+    /// let (completer, awaiter) = Completer::new();
+    /// thread::spawn(move || {
+    ///     completer.complete(42).unwrap();
+    /// });
+    /// assert_eq!(awaiter.wait().unwrap(), 42);
+    /// ```
+    #[must_use]
+    pub fn new() -> (Self, Awaiter<T>) {
+        let cond_sync = CondSync::new(State {
+            value: None,
+            completed: false,
+            completers: 1,
+        });
+        (Self(cond_sync.clone()), Awaiter(cond_sync))
+    }
+
+    /// Hands over the value to the [`Awaiter`].
+    ///
+    /// ## Errors
+    ///
+    /// [`AlreadyCompleted`], which contains the given value, if a clone of this completer
+    /// completed already, or if the internally used mutex is poisoned.
+    pub fn complete(self, value: T) -> Result<(), AlreadyCompleted<T>> {
+        let mut value = Some(value);
+        self.0
+            .modify_and_notify(
+                |state| {
+                    if !state.completed {
+                        state.completed = true;
+                        state.value = value.take();
+                    }
+                },
+                Other::All,
+            )
+            .ok();
+        match value {
+            None => Ok(()),
+            Some(value) => Err(AlreadyCompleted(value)),
+        }
+    }
+
+    /// Returns `true` if the value was handed over already, by this completer or a clone.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.0 .0.lock_unpoisoned().completed
+    }
+}
+
+impl<T> Clone for Completer<T> {
+    fn clone(&self) -> Self {
+        self.0
+            .modify_and_notify(|state| state.completers += 1, Other::All)
+            .ok();
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        self.0
+            .modify_and_notify(|state| state.completers -= 1, Other::All)
+            .ok();
+    }
+}
+
+impl<T> Awaiter<T> {
+    /// Blocks the current thread until the value was handed over, and returns it.
+    ///
+    /// ## Errors
+    ///
+    /// [`AwaitError::Abandoned`] if all completers were dropped without completing.
+    ///
+    /// [`AwaitError::Poisoned`] if the internally used mutex is poisoned.
+    pub fn wait(self) -> Result<T, AwaitError> {
+        self.take(None)
+    }
+
+    /// Like [`Awaiter::wait`], but waits no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// [`AwaitError::Timeout`] if the value was not handed over within the given duration;
+    /// the awaiter can be used to wait again.
+    ///
+    /// [`AwaitError::Abandoned`] if all completers were dropped without completing.
+    ///
+    /// [`AwaitError::AlreadyTaken`] if a previous call returned the value already.
+    ///
+    /// [`AwaitError::Poisoned`] if the internally used mutex is poisoned.
+    pub fn wait_timeout(&mut self, duration: Duration) -> Result<T, AwaitError> {
+        self.take(Some(duration))
+    }
+
+    fn take(&self, duration: Option<Duration>) -> Result<T, AwaitError> {
+        let finished = |state: &State<T>| state.completed || state.completers == 0;
+        if let Some(duration) = duration {
+            if self
+                .0
+                .wait_until_or_timeout(finished, duration)?
+                .is_timeout()
+            {
+                return Err(AwaitError::Timeout);
+            }
+        }
+        let mut result = Err(AwaitError::Abandoned);
+        self.0.wait_until_and_modify(
+            finished,
+            |state| {
+                result = match state.value.take() {
+                    Some(value) => Ok(value),
+                    None if state.completed => Err(AwaitError::AlreadyTaken),
+                    None => Err(AwaitError::Abandoned),
+                };
+            },
+            Other::One,
+        )?;
+        result
+    }
+}
+
+/// Error of [`Awaiter::wait`] and [`Awaiter::wait_timeout`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum AwaitError {
+    /// The value was not handed over in time.
+    Timeout,
+    /// All completers were dropped without completing.
+    Abandoned,
+    /// The value was returned already, by a previous call of [`Awaiter::wait_timeout`].
+    AlreadyTaken,
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for AwaitError {
+    fn from(e: PoisonedError) -> AwaitError {
+        match e {
+            PoisonedError::Poisoned => AwaitError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => AwaitError::WouldDeadlock { instance },
+        }
+    }
+}
+
+/// Error of [`Completer::complete`]; gives the value back.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AlreadyCompleted<T>(pub T);
@@ -0,0 +1,299 @@
+use crate::PoisonedError;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A bounded FIFO queue for producer/consumer setups.
+///
+/// Producers and consumers wait on separate condvars (over the same mutex),
+/// so a `push` only ever wakes up a consumer, and a `pop` only ever wakes up a producer.
+/// This avoids futile wakeups when many threads of the same kind are blocked.
+/// A queue created with [`CondQueue::bounded_fair`] additionally guarantees that
+/// waiting threads are served in the order of their arrival.
+///
+/// Clones share the same queue.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::CondQueue;
+/// use std::thread;
+///
+/// let queue = CondQueue::bounded(2);
+///
+/// let queue_t = queue.clone();
+/// let producer = thread::spawn(move || {
+///     for i in 0..10 {
+///         // blocks while the queue is full
+///         queue_t.push(i).unwrap();
+///     }
+/// });
+///
+/// let sum: usize = (0..10).map(|_| queue.pop().unwrap()).sum();
+/// assert_eq!(sum, 45);
+/// producer.join().unwrap();
+/// ```
+pub struct CondQueue<T>(Arc<Inner<T>>);
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+    fair: bool,
+    // producers wait here until the queue is not full
+    not_full: Condvar,
+    // consumers wait here until the queue is not empty
+    not_empty: Condvar,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    producers: Turns,
+    consumers: Turns,
+}
+
+// The waiting threads of one side, in the order of their arrival; only used by fair queues.
+#[derive(Default)]
+struct Turns {
+    waiting: VecDeque<u64>,
+    next_ticket: u64,
+}
+impl Turns {
+    fn draw(&mut self) -> u64 {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.waiting.push_back(ticket);
+        ticket
+    }
+
+    fn is_turn(&self, ticket: Option<u64>) -> bool {
+        ticket.map_or(true, |ticket| self.waiting.front() == Some(&ticket))
+    }
+
+    fn leave(&mut self, ticket: Option<u64>) {
+        if let Some(pos) = ticket.and_then(|ticket| self.waiting.iter().position(|t| *t == ticket))
+        {
+            self.waiting.remove(pos);
+        }
+    }
+}
+
+impl<T> CondQueue<T> {
+    /// Construct a new, empty queue that holds at most `capacity` items.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn bounded(capacity: usize) -> Self {
+        Self::with_fairness(capacity, false)
+    }
+
+    /// Like [`CondQueue::bounded`], but producers and consumers are served strictly
+    /// in the order in which they started waiting.
+    ///
+    /// With [`CondQueue::bounded`], a thread that arrives while the queue has space
+    /// (or items) can overtake threads that are already waiting, so a continuous stream of
+    /// producers (or consumers) can starve an individual one.
+    /// A fair queue rules this out: a waiting thread is overtaken by no thread that arrives
+    /// after it. The price is that each push and pop wakes all waiting threads of the other
+    /// side, of which all but the first one go back to sleep.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn bounded_fair(capacity: usize) -> Self {
+        Self::with_fairness(capacity, true)
+    }
+
+    fn with_fairness(capacity: usize, fair: bool) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        Self(Arc::new(Inner {
+            state: Mutex::new(State {
+                items: VecDeque::with_capacity(capacity),
+                producers: Turns::default(),
+                consumers: Turns::default(),
+            }),
+            capacity,
+            fair,
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }))
+    }
+
+    /// Returns `true` if the queue was created with [`CondQueue::bounded_fair`].
+    #[must_use]
+    pub fn is_fair(&self) -> bool {
+        self.0.fair
+    }
+
+    /// Returns the maximal number of items.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity
+    }
+
+    /// Returns the current number of items.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn len(&self) -> Result<usize, PoisonedError> {
+        Ok(self.0.state.lock()?.items.len())
+    }
+
+    /// Returns `true` if the queue contains no items.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn is_empty(&self) -> Result<bool, PoisonedError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Appends the item, and blocks the current thread while the queue is full.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn push(&self, item: T) -> Result<(), PoisonedError> {
+        self.push_until(item, None)
+            .map(|rejected| debug_assert!(rejected.is_none()))
+    }
+
+    /// Like [`CondQueue::push`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The item, if it could not be appended in time.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn push_timeout(&self, item: T, duration: Duration) -> Result<Option<T>, PoisonedError> {
+        self.push_until(item, Instant::now().checked_add(duration))
+    }
+
+    /// Appends the item if the queue is not full, without blocking.
+    ///
+    /// ## Returns
+    ///
+    /// The item, if the queue is full.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn try_push(&self, item: T) -> Result<Option<T>, PoisonedError> {
+        self.push_until(item, Some(Instant::now()))
+    }
+
+    /// Removes the first item, and blocks the current thread while the queue is empty.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn pop(&self) -> Result<T, PoisonedError> {
+        self.pop_until(None)
+            .map(|item| item.unwrap_or_else(|| unreachable!("unbounded waits return an item")))
+    }
+
+    /// Like [`CondQueue::pop`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The first item, or `None` if the queue remained empty.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn pop_timeout(&self, duration: Duration) -> Result<Option<T>, PoisonedError> {
+        self.pop_until(Instant::now().checked_add(duration))
+    }
+
+    /// Removes the first item if there is one, without blocking.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn try_pop(&self) -> Result<Option<T>, PoisonedError> {
+        self.pop_until(Some(Instant::now()))
+    }
+
+    fn push_until(&self, item: T, end: Option<Instant>) -> Result<Option<T>, PoisonedError> {
+        let inner = &*self.0;
+        let mut state = inner.state.lock()?;
+        let ticket = inner.fair.then(|| state.producers.draw());
+        while state.items.len() >= inner.capacity || !state.producers.is_turn(ticket) {
+            state = match end.map(|end| end.saturating_duration_since(Instant::now())) {
+                None => inner.not_full.wait(state)?,
+                Some(remaining) if remaining.is_zero() => {
+                    state.producers.leave(ticket);
+                    inner.wake_next(&state);
+                    return Ok(Some(item));
+                }
+                Some(remaining) => inner.not_full.wait_timeout(state, remaining)?.0,
+            };
+        }
+        state.producers.leave(ticket);
+        state.items.push_back(item);
+        inner.wake(&inner.not_empty, &state);
+        Ok(None)
+    }
+
+    fn pop_until(&self, end: Option<Instant>) -> Result<Option<T>, PoisonedError> {
+        let inner = &*self.0;
+        let mut state = inner.state.lock()?;
+        let ticket = inner.fair.then(|| state.consumers.draw());
+        loop {
+            if state.consumers.is_turn(ticket) {
+                if let Some(item) = state.items.pop_front() {
+                    state.consumers.leave(ticket);
+                    inner.wake(&inner.not_full, &state);
+                    return Ok(Some(item));
+                }
+            }
+            state = match end.map(|end| end.saturating_duration_since(Instant::now())) {
+                None => inner.not_empty.wait(state)?,
+                Some(remaining) if remaining.is_zero() => {
+                    state.consumers.leave(ticket);
+                    inner.wake_next(&state);
+                    return Ok(None);
+                }
+                Some(remaining) => inner.not_empty.wait_timeout(state, remaining)?.0,
+            };
+        }
+    }
+}
+
+impl<T> Inner<T> {
+    // wakes a thread on the other side after a push or a pop
+    fn wake(&self, cvar: &Condvar, state: &State<T>) {
+        if self.fair {
+            self.wake_next(state);
+        } else {
+            cvar.notify_one();
+        }
+    }
+
+    // In a fair queue, only the first thread in line may proceed, and a thread that leaves
+    // hands the turn over to the next one; as they can't be woken individually,
+    // all threads are woken whose side can proceed.
+    fn wake_next(&self, state: &State<T>) {
+        if self.fair {
+            if !state.producers.waiting.is_empty() && state.items.len() < self.capacity {
+                self.not_full.notify_all();
+            }
+            if !state.consumers.waiting.is_empty() && !state.items.is_empty() {
+                self.not_empty.notify_all();
+            }
+        }
+    }
+}
+
+impl<T> Clone for CondQueue<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+/// Collects the reports of a fixed set of named reporters, and lets threads wait until
+/// a certain number of them has reported.
+///
+/// Each reporter can attach a value to its report, e.g. a vote or a sequence number;
+/// a later report of the same reporter replaces its value.
+///
+/// Clones share the same quorum.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::Quorum;
+/// use std::{thread, time::Duration};
+///
+/// let quorum = Quorum::new(["node-1", "node-2", "node-3"]);
+/// for (name, term) in [("node-1", 7), ("node-3", 7)] {
+///     let quorum_t = quorum.clone();
+///     thread::spawn(move || quorum_t.report(name, term).unwrap());
+/// }
+///
+/// // wait for the majority, and fold the reported values
+/// let reports = quorum
+///     .wait_for_majority(Duration::from_secs(5))
+///     .unwrap()
+///     .expect("no majority in time");
+/// let highest_term = reports.iter().map(|(_, term)| *term).max();
+/// assert_eq!(highest_term, Some(7));
+/// ```
+pub struct Quorum<V>(CondSync<BTreeMap<String, Option<V>>>);
+
+impl<V> Quorum<V> {
+    /// Construct a new instance for the given reporters, none of which has reported yet.
+    #[must_use]
+    pub fn new<I, S>(reporters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(CondSync::new(
+            reporters.into_iter().map(|r| (r.into(), None)).collect(),
+        ))
+    }
+
+    /// Records the report of the given reporter.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `reporter` is not one of the reporters that were given to [`Quorum::new`].
+    pub fn report(&self, reporter: &str, value: V) -> Result<(), PoisonedError> {
+        let mut known = false;
+        self.0.modify_and_notify(
+            |reports| {
+                if let Some(report) = reports.get_mut(reporter) {
+                    known = true;
+                    *report = Some(value);
+                }
+            },
+            Other::All,
+        )?;
+        // panicking only now does not poison the mutex
+        assert!(known, "unknown reporter {reporter:?}");
+        Ok(())
+    }
+
+    /// Returns the number of reporters that have reported so far.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn reported(&self) -> Result<usize, PoisonedError> {
+        Ok(count_reported(&*self.0 .0.lock()?))
+    }
+
+    /// Returns the smallest number of reporters that forms a majority.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn majority(&self) -> Result<usize, PoisonedError> {
+        Ok(self.0 .0.lock()?.len() / 2 + 1)
+    }
+}
+
+impl<V: Clone> Quorum<V> {
+    /// Blocks the current thread until at least `k` reporters have reported,
+    /// but no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The reports of all reporters that have reported so far, ordered by their names,
+    /// or `None` if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_for(
+        &self,
+        k: usize,
+        timeout: Duration,
+    ) -> Result<Option<Vec<(String, V)>>, PoisonedError> {
+        let i = &self.0 .0;
+        let (reports, _) = i.intercept(OperationKind::Wait, "wait_for", || {
+            let mtx_guard = i.lock()?;
+            let waited = i.wait_for(
+                mtx_guard,
+                |reports| (count_reported(reports) >= k).then(|| collect(reports)),
+                remaining_until(Instant::now().checked_add(timeout)),
+            )?;
+            let reason = waited.reason();
+            Ok((waited.finish(), reason))
+        })?;
+        Ok(reports)
+    }
+
+    /// Like [`Quorum::wait_for`], for a [majority](Quorum::majority) of the reporters.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_for_majority(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<Vec<(String, V)>>, PoisonedError> {
+        self.wait_for(self.majority()?, timeout)
+    }
+}
+
+impl<V> Clone for Quorum<V> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+fn count_reported<V>(reports: &BTreeMap<String, Option<V>>) -> usize {
+    reports.values().filter(|report| report.is_some()).count()
+}
+
+fn collect<V: Clone>(reports: &BTreeMap<String, Option<V>>) -> Vec<(String, V)> {
+    reports
+        .iter()
+        .filter_map(|(reporter, report)| report.clone().map(|value| (reporter.clone(), value)))
+        .collect()
+}
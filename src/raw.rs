@@ -0,0 +1,73 @@
+use crate::{builder::Config, CondSync, I};
+#[cfg(feature = "notifications")]
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Condvar, Mutex};
+
+// Interoperability with code that uses the std primitives directly.
+impl<T> CondSync<T> {
+    /// Construct a new instance from the raw std primitives.
+    ///
+    /// Allows migrating code that uses `Arc<(Mutex<T>, Condvar)>` incrementally.
+    /// The mutex is taken over as it is, so a poisoned mutex stays poisoned.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the given `Arc` unchanged if it is not the only reference to the primitives,
+    /// because a `CondSync` cannot share them with other owners.
+    /// Other code can use [`CondSync::as_mutex`] and [`CondSync::as_condvar`] instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::sync::{Arc, Condvar, Mutex};
+    ///
+    /// let raw = Arc::new((Mutex::new(0_usize), Condvar::new()));
+    /// let cond_sync = CondSync::from_raw(raw).unwrap();
+    /// cond_sync.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    ///
+    /// let Ok(raw) = cond_sync.into_raw() else { unreachable!() };
+    /// assert_eq!(*raw.0.lock().unwrap(), 1);
+    /// ```
+    pub fn from_raw(raw: Arc<(Mutex<T>, Condvar)>) -> Result<Self, Arc<(Mutex<T>, Condvar)>> {
+        let (mtx, cvar) = Arc::try_unwrap(raw)?;
+        Ok(Self::from_parts(mtx, cvar, Config::default()))
+    }
+
+    /// Decomposes the instance into the raw std primitives.
+    ///
+    /// The configuration of the instance, registered observers, wakers, and pending
+    /// notifications are dropped.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the instance unchanged if it is not the only reference to the wrapped variable,
+    /// i.e., if clones of it exist.
+    pub fn into_raw(self) -> Result<Arc<(Mutex<T>, Condvar)>, Self> {
+        let I { mtx, cvar, .. } = Arc::try_unwrap(self.0).map_err(Self)?;
+        Ok(Arc::new((mtx, cvar)))
+    }
+
+    /// Gives access to the internally used mutex, e.g. for APIs that want the raw parts.
+    ///
+    /// Modifications that are done directly with the mutex do not notify waiting threads,
+    /// and are not seen by observers, interceptors, and statistics.
+    /// Use [`CondSync::as_condvar`] to notify waiting threads after such modifications.
+    #[must_use]
+    pub fn as_mutex(&self) -> &Mutex<T> {
+        &self.0.mtx
+    }
+
+    /// Gives access to the internally used condition variable, e.g. for APIs that want the
+    /// raw parts.
+    ///
+    /// Threads that wait directly on the condition variable are woken up by all notifications
+    /// of this instance.
+    #[must_use]
+    pub fn as_condvar(&self) -> &Condvar {
+        // from now on, notifications can't be skipped when no waiters are registered
+        #[cfg(feature = "notifications")]
+        self.0.raw_condvar.store(true, Ordering::SeqCst);
+        &self.0.cvar
+    }
+}
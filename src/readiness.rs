@@ -0,0 +1,97 @@
+use crate::CondSync;
+use std::{
+    io::{self, Read, Write},
+    os::unix::{
+        io::{AsFd, AsRawFd, BorrowedFd, RawFd},
+        net::UnixStream,
+    },
+};
+
+// A socket pair: a byte is written to `signal` with each notification,
+// and foreign event loops poll the readable end.
+pub(crate) struct Readiness {
+    signal: UnixStream,
+    handle: UnixStream,
+}
+impl Readiness {
+    fn new() -> io::Result<Self> {
+        let (signal, handle) = UnixStream::pair()?;
+        signal.set_nonblocking(true)?;
+        handle.set_nonblocking(true)?;
+        Ok(Self { signal, handle })
+    }
+
+    pub(crate) fn signal(&self) {
+        // a full buffer means that the handle is readable anyway
+        (&self.signal).write_all(&[1]).ok();
+    }
+}
+
+impl<T> CondSync<T> {
+    /// Returns a handle that becomes readable with each notification of waiting threads,
+    /// so that foreign event loops can multiplex the readiness of this instance with their
+    /// native primitives, e.g. with `poll`, `epoll`, or `kqueue`.
+    ///
+    /// The handle stays readable until [`ReadinessHandle::clear`] is called;
+    /// clear it before evaluating the condition, to not miss a notification.
+    /// All handles of an instance share the same underlying socket.
+    ///
+    /// Only available on Unix platforms, with feature `readiness`.
+    ///
+    /// ## Errors
+    ///
+    /// The error of the operating system if the socket could not be created.
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "readiness"))))]
+    pub fn readiness_handle(&self) -> io::Result<ReadinessHandle> {
+        let readiness = if let Some(readiness) = self.0.readiness.get() {
+            readiness
+        } else {
+            let readiness = Readiness::new()?;
+            // if another thread was faster, its socket pair is used
+            self.0.readiness.get_or_init(|| readiness)
+        };
+        Ok(ReadinessHandle(readiness.handle.try_clone()?))
+    }
+}
+
+/// A handle that is readable after notifications of a [`CondSync`], see
+/// [`CondSync::readiness_handle`].
+///
+/// Only available on Unix platforms, with feature `readiness`.
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "readiness"))))]
+#[derive(Debug)]
+pub struct ReadinessHandle(UnixStream);
+impl ReadinessHandle {
+    /// Resets the handle to not readable, until the next notification.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if a notification happened since the last call.
+    ///
+    /// ## Errors
+    ///
+    /// The error of the operating system if reading from the socket failed.
+    pub fn clear(&self) -> io::Result<bool> {
+        let mut buf = [0_u8; 64];
+        let mut notified = false;
+        loop {
+            match (&self.0).read(&mut buf) {
+                Ok(0) => return Ok(notified),
+                Ok(_) => notified = true,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(notified),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+impl AsFd for ReadinessHandle {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+impl AsRawFd for ReadinessHandle {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
@@ -0,0 +1,132 @@
+use crate::CondSync;
+use std::time::Duration;
+
+/// Changes to the configuration of a live instance; see [`CondSync::reconfigure`].
+///
+/// Only the settings that are explicitly set are changed.
+///
+/// The settings that can be changed are those an instance has: the wakeup jitter and the
+/// debounce timings. There is no spin budget and no long-wait threshold to tune,
+/// because waits block on the condvar right away, without spinning first,
+/// and the backoff of [`CondSync::wait_until_with_backoff`] is given with each call.
+///
+/// ## Examples
+///
+/// With feature `debounce`:
+///
+/// ```rust
+/// # #[cfg(feature = "debounce")] {
+/// use cond_sync::{CondSync, Reconfiguration};
+/// use std::time::Duration;
+///
+/// let cond_sync = CondSync::builder(0_u32)
+///     .debounce(Duration::from_millis(1), Duration::from_millis(5))
+///     .build();
+///
+/// // under heavy load, merge more notifications
+/// cond_sync
+///     .reconfigure(
+///         Reconfiguration::new().debounce(Duration::from_millis(5), Duration::from_millis(20)),
+///     )
+///     .unwrap();
+/// # }
+/// ```
+///
+/// With feature `jitter`:
+///
+/// ```rust
+/// # #[cfg(feature = "jitter")] {
+/// use cond_sync::{CondSync, Reconfiguration};
+/// use std::time::Duration;
+///
+/// let cond_sync = CondSync::new(0_u32);
+///
+/// // spread the wakeups of many waiting threads
+/// cond_sync
+///     .reconfigure(Reconfiguration::new().wakeup_jitter(Duration::from_micros(50)))
+///     .unwrap();
+/// # }
+/// ```
+///
+/// Only available with feature `debounce` or `jitter`.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "debounce", feature = "jitter"))))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reconfiguration {
+    #[cfg(feature = "jitter")]
+    wakeup_jitter: Option<Duration>,
+    #[cfg(feature = "debounce")]
+    debounce: Option<(Duration, Duration)>,
+}
+
+impl Reconfiguration {
+    /// Construct a new instance that changes nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes the maximal [wakeup jitter](crate::CondSyncBuilder::wakeup_jitter);
+    /// [`Duration::ZERO`] switches the jitter off.
+    ///
+    /// Only available with feature `jitter`.
+    #[cfg(feature = "jitter")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jitter")))]
+    #[must_use]
+    pub fn wakeup_jitter(mut self, max_jitter: Duration) -> Self {
+        self.wakeup_jitter = Some(max_jitter);
+        self
+    }
+
+    /// Changes the timings of the [debouncing](crate::CondSyncBuilder::debounce).
+    ///
+    /// Debouncing cannot be switched on or off on a live instance.
+    ///
+    /// Only available with feature `debounce`.
+    #[cfg(feature = "debounce")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debounce")))]
+    #[must_use]
+    pub fn debounce(mut self, window: Duration, max_latency: Duration) -> Self {
+        self.debounce = Some((window, max_latency));
+        self
+    }
+}
+
+impl<T> CondSync<T> {
+    /// Adjusts the configuration of this instance at runtime.
+    ///
+    /// The changes apply to waits that are woken up, and to notifications that are
+    /// debounced, from now on; a debounce window that is already open keeps its timing.
+    ///
+    /// ## Errors
+    ///
+    /// [`ReconfigureError::NotDebounced`] if the debounce timings are changed on an instance
+    /// that was not built with [debouncing](crate::CondSyncBuilder::debounce);
+    /// nothing is changed then.
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "debounce", feature = "jitter"))))]
+    pub fn reconfigure(&self, changes: Reconfiguration) -> Result<(), ReconfigureError> {
+        #[cfg(feature = "debounce")]
+        if let Some((window, max_latency)) = changes.debounce {
+            self.0
+                .debouncer
+                .as_ref()
+                .ok_or(ReconfigureError::NotDebounced)?
+                .set_timings(window, max_latency);
+        }
+        #[cfg(feature = "jitter")]
+        if let Some(max_jitter) = changes.wakeup_jitter {
+            self.0.wakeup_jitter.set(Some(max_jitter));
+        }
+        Ok(())
+    }
+}
+
+/// Error of [`CondSync::reconfigure`].
+///
+/// Only available with feature `debounce` or `jitter`.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "debounce", feature = "jitter"))))]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReconfigureError {
+    /// The debounce timings were to be changed, but the instance was not built with
+    /// [debouncing](crate::CondSyncBuilder::debounce).
+    NotDebounced,
+}
@@ -0,0 +1,67 @@
+use crate::{CondSync, OperationKind, PoisonedError};
+use std::sync::{Mutex, MutexGuard};
+
+impl<T> CondSync<T> {
+    /// Like [`CondSync::wait_until`], for a caller that holds the lock of another mutex,
+    /// which must not be held while blocking, e.g. because the thread that fulfills the
+    /// condition needs it.
+    ///
+    /// The given guard, which must belong to `other`, is released before the wait starts,
+    /// and `other` is locked again after the wait has ended and the lock of this instance
+    /// was released, so that the two locks are never waited for in the wrong order.
+    ///
+    /// Note that the content of `other` may have changed in the meantime.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::{
+    ///     sync::{Arc, Mutex},
+    ///     thread,
+    /// };
+    ///
+    /// let registry = Arc::new(Mutex::new(Vec::<&str>::new()));
+    /// let cond_sync = CondSync::new(false);
+    ///
+    /// let (registry_t, cond_sync_t) = (Arc::clone(&registry), cond_sync.clone());
+    /// thread::spawn(move || {
+    ///     registry_t.lock().unwrap().push("worker");
+    ///     cond_sync_t.modify_and_notify(|v| *v = true, Other::All).unwrap();
+    /// });
+    ///
+    /// let registry_guard = registry.lock().unwrap();
+    /// // waiting with the guard would deadlock, because the worker needs the registry
+    /// let registry_guard = cond_sync
+    ///     .wait_until_releasing(&registry, registry_guard, |v| *v)
+    ///     .unwrap();
+    /// assert_eq!(*registry_guard, vec!["worker"]);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread tries to re-acquire the lock,
+    /// or if `other` is poisoned.
+    #[track_caller]
+    pub fn wait_until_releasing<'m, U, F>(
+        &self,
+        other: &'m Mutex<U>,
+        other_guard: MutexGuard<'m, U>,
+        condition: F,
+    ) -> Result<MutexGuard<'m, U>, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        drop(other_guard);
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_releasing", || {
+                let mtx_guard = self.0.lock()?;
+                self.0
+                    .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?
+                    .finish();
+                Ok(())
+            })?;
+        Ok(other.lock()?)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::time::Duration;
+
+/// A cell that is completed with a result, where errors take precedence.
+///
+/// Workers report failure with [`ResultCell::fail`]; the value is provided with
+/// [`ResultCell::complete`].
+/// The first reported error wins: it replaces a value that was set before, and later
+/// errors and values are ignored.
+/// So a thread that waits for the value naturally fails as soon as any worker reports
+/// an error.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{ResultCell, ResultCellError};
+/// use std::thread;
+///
+/// let cell = ResultCell::<usize, String>::new();
+/// for i in 0..3 {
+///     let cell_t = cell.clone();
+///     thread::spawn(move || {
+///         if i == 1 {
+///             cell_t.fail(format!("worker {i} failed"));
+///         }
+///     });
+/// }
+/// assert_eq!(
+///     cell.wait(),
+///     Err(ResultCellError::Failed("worker 1 failed".to_string()))
+/// );
+/// ```
+pub struct ResultCell<T, E>(CondSync<Option<Result<T, E>>>);
+
+impl<T, E> ResultCell<T, E> {
+    /// Construct a new, empty instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CondSync::new(None))
+    }
+
+    /// Sets the value, unless the cell was already completed or failed.
+    ///
+    /// Returns `true` if the value was set.
+    pub fn complete(&self, value: T) -> bool {
+        self.set(Ok(value))
+    }
+
+    /// Reports an error, unless an error was already reported.
+    ///
+    /// Returns `true` if the error was set.
+    pub fn fail(&self, error: E) -> bool {
+        self.set(Err(error))
+    }
+
+    fn set(&self, result: Result<T, E>) -> bool {
+        let mut result = Some(result);
+        self.0
+            .modify_and_notify(
+                |current| {
+                    let accept = match (&*current, &result) {
+                        (None, _) | (Some(Ok(_)), Some(Err(_))) => true,
+                        (Some(_), _) => false,
+                    };
+                    if accept {
+                        *current = result.take();
+                    }
+                },
+                Other::All,
+            )
+            .ok();
+        result.is_none()
+    }
+}
+
+impl<T: Clone, E: Clone> ResultCell<T, E> {
+    /// Blocks the current thread until the cell was completed or failed,
+    /// and returns the value.
+    ///
+    /// ## Errors
+    ///
+    /// [`ResultCellError::Failed`] with the first error that was reported with
+    /// [`ResultCell::fail`].
+    ///
+    /// [`ResultCellError::Poisoned`] if the internally used mutex is poisoned, which can only
+    /// happen if cloning the value or the error panicked.
+    pub fn wait(&self) -> Result<T, ResultCellError<E>> {
+        self.0.wait_until(Option::is_some)?;
+        self.result()?
+            .ok_or_else(|| unreachable!("the cell is never reset"))
+    }
+
+    /// Like [`ResultCell::wait`], but waits no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// [`ResultCellError::Timeout`] if the cell was neither completed nor failed in time.
+    ///
+    /// [`ResultCellError::Failed`] with the first error that was reported with
+    /// [`ResultCell::fail`].
+    ///
+    /// [`ResultCellError::Poisoned`] if the internally used mutex is poisoned, which can only
+    /// happen if cloning the value or the error panicked.
+    pub fn wait_timeout(&self, duration: Duration) -> Result<T, ResultCellError<E>> {
+        if self
+            .0
+            .wait_until_or_timeout(Option::is_some, duration)?
+            .is_timeout()
+        {
+            return Err(ResultCellError::Timeout);
+        }
+        self.result()?
+            .ok_or_else(|| unreachable!("the cell is never reset"))
+    }
+
+    /// Returns the current value, without waiting.
+    ///
+    /// ## Returns
+    ///
+    /// The value, or `None` if the cell was neither completed nor failed yet.
+    ///
+    /// ## Errors
+    ///
+    /// [`ResultCellError::Failed`] with the first error that was reported with
+    /// [`ResultCell::fail`].
+    ///
+    /// [`ResultCellError::Poisoned`] if the internally used mutex is poisoned, which can only
+    /// happen if cloning the value or the error panicked.
+    pub fn result(&self) -> Result<Option<T>, ResultCellError<E>> {
+        match self.0.try_clone_inner()? {
+            None => Ok(None),
+            Some(Ok(value)) => Ok(Some(value)),
+            Some(Err(error)) => Err(ResultCellError::Failed(error)),
+        }
+    }
+}
+
+impl<T, E> Clone for ResultCell<T, E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T, E> Default for ResultCell<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error of [`ResultCell::wait`], [`ResultCell::wait_timeout`], and [`ResultCell::result`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResultCellError<E> {
+    /// A worker reported this error.
+    Failed(E),
+    /// The cell was neither completed nor failed in time.
+    Timeout,
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl<E> From<PoisonedError> for ResultCellError<E> {
+    fn from(e: PoisonedError) -> ResultCellError<E> {
+        match e {
+            PoisonedError::Poisoned => ResultCellError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => {
+                ResultCellError::WouldDeadlock { instance }
+            }
+        }
+    }
+}
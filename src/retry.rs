@@ -0,0 +1,101 @@
+use crate::{CondSync, PoisonedError};
+use std::time::{Duration, Instant};
+
+impl<T> CondSync<T> {
+    /// Repeatedly "pokes" something, and then waits a while for the given condition.
+    ///
+    /// In each attempt, `poke` is called with the number of the attempt (starting with 1),
+    /// and then the current thread waits until the condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// but no longer than `per_attempt_timeout`.
+    ///
+    /// ## Returns
+    ///
+    /// The number of the attempt after which the condition was fulfilled.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::time::Duration;
+    ///
+    /// let acknowledged = CondSync::new(false);
+    /// let attempt = acknowledged
+    ///     .retry_until(
+    ///         3,
+    ///         Duration::from_millis(10),
+    ///         |attempt| {
+    ///             // e.g. send a request; here, only the second one is successful
+    ///             if attempt == 2 {
+    ///                 acknowledged.modify_and_notify(|v| *v = true, Other::All).unwrap();
+    ///             }
+    ///         },
+    ///         |acknowledged| *acknowledged,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(attempt, 2);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// [`RetryError::Exhausted`] if the condition was not fulfilled after the last attempt.
+    ///
+    /// [`RetryError::Poisoned`] if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn retry_until<P, F>(
+        &self,
+        max_attempts: usize,
+        per_attempt_timeout: Duration,
+        mut poke: P,
+        condition: F,
+    ) -> Result<usize, RetryError>
+    where
+        P: FnMut(usize),
+        F: Fn(&T) -> bool,
+    {
+        let start = Instant::now();
+        for attempt in 1..=max_attempts {
+            poke(attempt);
+            if self
+                .wait_until_or_timeout(&condition, per_attempt_timeout)?
+                .is_condition()
+            {
+                return Ok(attempt);
+            }
+        }
+        Err(RetryError::Exhausted {
+            attempts: max_attempts,
+            elapsed: start.elapsed(),
+        })
+    }
+}
+
+/// Error of [`CondSync::retry_until`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RetryError {
+    /// The condition was not fulfilled after the last attempt.
+    Exhausted {
+        /// The number of attempts that were made.
+        attempts: usize,
+        /// The time spent in all attempts.
+        elapsed: Duration,
+    },
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for RetryError {
+    fn from(e: PoisonedError) -> RetryError {
+        match e {
+            PoisonedError::Poisoned => RetryError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => RetryError::WouldDeadlock { instance },
+        }
+    }
+}
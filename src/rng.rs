@@ -0,0 +1,64 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+// A small thread-safe pseudo-random number generator (splitmix64),
+// which is good enough for jitter and the like.
+pub(crate) struct Rng(AtomicU64);
+
+impl Rng {
+    // without a seed, a random seed is used
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        Self(AtomicU64::new(seed.unwrap_or_else(|| {
+            RandomState::new().build_hasher().finish()
+        })))
+    }
+
+    pub(crate) fn next_u64(&self) -> u64 {
+        const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut z = self
+            .0
+            .fetch_add(GAMMA, Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // returns a duration between zero and `max` (inclusive)
+    pub(crate) fn duration_up_to(&self, max: Duration) -> Duration {
+        let max_nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+        Duration::from_nanos(self.next_u64() % max_nanos.saturating_add(1))
+    }
+}
+
+// The maximal wakeup jitter of an instance, which can be changed at runtime
+// and is read after every wakeup, so it is kept in an atomic rather than behind a lock.
+#[cfg(feature = "jitter")]
+pub(crate) struct MaxJitter(AtomicU64);
+
+#[cfg(feature = "jitter")]
+impl MaxJitter {
+    // `None` and zero switch the jitter off
+    pub(crate) fn new(max: Option<Duration>) -> Self {
+        Self(AtomicU64::new(Self::nanos(max)))
+    }
+
+    pub(crate) fn set(&self, max: Option<Duration>) {
+        self.0.store(Self::nanos(max), Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> Option<Duration> {
+        match self.0.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    fn nanos(max: Option<Duration>) -> u64 {
+        max.map_or(0, |max| u64::try_from(max.as_nanos()).unwrap_or(u64::MAX))
+    }
+}
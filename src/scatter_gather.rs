@@ -0,0 +1,161 @@
+use crate::{remaining_until, CondSync, Other, PoisonedError};
+use std::{
+    collections::VecDeque,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Applies `f` to all inputs, on up to `threads` newly spawned threads,
+/// and returns the outputs in the order of the inputs.
+///
+/// The call returns as soon as all outputs are available, or when the total `timeout`
+/// is reached, or when `f` panicked. In the latter two cases, the inputs that were not
+/// yet started are discarded; calls of `f` that are still running are not interrupted,
+/// but their outputs are discarded as well.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// let squares =
+///     cond_sync::scatter_gather(vec![1, 2, 3, 4], 2, Duration::from_secs(10), |i| i * i)
+///         .unwrap();
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// ```
+///
+/// ## Errors
+///
+/// [`GatherError::Timeout`] if not all outputs were available in time.
+///
+/// [`GatherError::Panicked`] if `f` panicked.
+///
+/// [`GatherError::Poisoned`] if the internally used mutex is poisoned.
+///
+/// ## Panics
+///
+/// Panics if `threads` is 0, or if a thread cannot be spawned.
+pub fn scatter_gather<In, Out, F>(
+    inputs: Vec<In>,
+    threads: usize,
+    timeout: Duration,
+    f: F,
+) -> Result<Vec<Out>, GatherError>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Out + Send + Sync + 'static,
+{
+    assert!(threads > 0, "scatter_gather needs at least one thread");
+    let end = Instant::now().checked_add(timeout);
+    let len = inputs.len();
+    let cond_sync = CondSync::new(State {
+        inputs: inputs.into_iter().enumerate().collect(),
+        outputs: (0..len).map(|_| None).collect(),
+        finished: 0,
+        panicked: false,
+    });
+    let f = Arc::new(f);
+    for _ in 0..threads.min(len) {
+        let cond_sync = cond_sync.clone();
+        let f = Arc::clone(&f);
+        thread::Builder::new()
+            .name("scatter-gather".to_string())
+            .spawn(move || work(&cond_sync, &*f))
+            .expect("failed to spawn thread");
+    }
+
+    let mtx_guard = cond_sync.0.lock()?;
+    let mut waited = cond_sync.0.wait_for(
+        mtx_guard,
+        |state| (state.panicked || state.finished == len).then_some(()),
+        remaining_until(end),
+    )?;
+    let state = &mut *waited.mtx_guard;
+    state.inputs.clear();
+    let result = if state.panicked {
+        Err(GatherError::Panicked)
+    } else if waited.outcome.is_none() {
+        Err(GatherError::Timeout {
+            finished: state.finished,
+        })
+    } else {
+        Ok(state
+            .outputs
+            .iter_mut()
+            .map(|output| {
+                output
+                    .take()
+                    .unwrap_or_else(|| unreachable!("all finished"))
+            })
+            .collect())
+    };
+    waited.finish();
+    result
+}
+
+struct State<In, Out> {
+    inputs: VecDeque<(usize, In)>,
+    outputs: Vec<Option<Out>>,
+    finished: usize,
+    panicked: bool,
+}
+
+fn work<In, Out, F>(cond_sync: &CondSync<State<In, Out>>, f: &F)
+where
+    F: Fn(In) -> Out,
+{
+    loop {
+        let Ok(mut state) = cond_sync.0.lock() else {
+            return;
+        };
+        let Some((index, input)) = state.inputs.pop_front() else {
+            return;
+        };
+        drop(state);
+
+        let output = catch_unwind(AssertUnwindSafe(|| f(input)));
+        cond_sync
+            .modify_and_notify(
+                |state| match output {
+                    Ok(output) => {
+                        state.outputs[index] = Some(output);
+                        state.finished += 1;
+                    }
+                    Err(_) => state.panicked = true,
+                },
+                Other::One,
+            )
+            .ok();
+    }
+}
+
+/// Error of [`scatter_gather`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GatherError {
+    /// Not all outputs were available in time.
+    Timeout {
+        /// The number of outputs that were available.
+        finished: usize,
+    },
+    /// The function panicked for one of the inputs.
+    Panicked,
+    /// The inner mutex got poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for GatherError {
+    fn from(e: PoisonedError) -> GatherError {
+        match e {
+            PoisonedError::Poisoned => GatherError::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => GatherError::WouldDeadlock { instance },
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::CondSync;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Types that can be mirrored into a sequence of `u64` words,
+/// which enables [optimistic reads](CondSync::read_optimistic).
+///
+/// Implemented for the primitive number types, `bool`, `char`, arrays,
+/// and tuples of up to three elements.
+///
+/// Only available with feature `optimistic_reads`.
+#[cfg_attr(docsrs, doc(cfg(feature = "optimistic_reads")))]
+pub trait SeqLockable: Copy {
+    /// The number of words the value needs.
+    const WORDS: usize;
+
+    /// Passes the value to `sink`, as exactly [`SeqLockable::WORDS`] words.
+    fn to_words(&self, sink: &mut impl FnMut(u64));
+
+    /// Reconstructs the value from exactly [`SeqLockable::WORDS`] words,
+    /// in the order in which [`SeqLockable::to_words`] produced them.
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self;
+}
+
+macro_rules! impl_seq_lockable_int {
+    ($($t:ty),*) => {
+        $(
+            impl SeqLockable for $t {
+                const WORDS: usize = 1;
+                #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+                fn to_words(&self, sink: &mut impl FnMut(u64)) {
+                    sink(*self as u64);
+                }
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+                    source() as $t
+                }
+            }
+        )*
+    };
+}
+impl_seq_lockable_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl SeqLockable for bool {
+    const WORDS: usize = 1;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        sink(u64::from(*self));
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        source() != 0
+    }
+}
+impl SeqLockable for char {
+    const WORDS: usize = 1;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        sink(u64::from(*self));
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        u32::try_from(source())
+            .ok()
+            .and_then(char::from_u32)
+            .unwrap_or_default()
+    }
+}
+impl SeqLockable for f32 {
+    const WORDS: usize = 1;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        sink(u64::from(self.to_bits()));
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        f32::from_bits(source() as u32)
+    }
+}
+impl SeqLockable for f64 {
+    const WORDS: usize = 1;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        sink(self.to_bits());
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        f64::from_bits(source())
+    }
+}
+impl<A: SeqLockable, const N: usize> SeqLockable for [A; N] {
+    const WORDS: usize = A::WORDS * N;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        for a in self {
+            a.to_words(sink);
+        }
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        std::array::from_fn(|_| A::from_words(source))
+    }
+}
+impl<A: SeqLockable, B: SeqLockable> SeqLockable for (A, B) {
+    const WORDS: usize = A::WORDS + B::WORDS;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        self.0.to_words(sink);
+        self.1.to_words(sink);
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        let a = A::from_words(source);
+        (a, B::from_words(source))
+    }
+}
+impl<A: SeqLockable, B: SeqLockable, C: SeqLockable> SeqLockable for (A, B, C) {
+    const WORDS: usize = A::WORDS + B::WORDS + C::WORDS;
+    fn to_words(&self, sink: &mut impl FnMut(u64)) {
+        self.0.to_words(sink);
+        self.1.to_words(sink);
+        self.2.to_words(sink);
+    }
+    fn from_words(source: &mut impl FnMut() -> u64) -> Self {
+        let a = A::from_words(source);
+        let b = B::from_words(source);
+        (a, b, C::from_words(source))
+    }
+}
+
+// A copy of the wrapped variable that can be read without the mutex.
+//
+// Is only written while the mutex is held, so there is only one writer at a time.
+pub(crate) struct Mirror<T> {
+    seq: AtomicU64,
+    words: Box<[AtomicU64]>,
+    write: fn(&T, &[AtomicU64]),
+}
+impl<T> Mirror<T> {
+    pub(crate) fn new(words: usize, write: fn(&T, &[AtomicU64])) -> Self {
+        Self {
+            seq: AtomicU64::new(0),
+            words: (0..words).map(|_| AtomicU64::new(0)).collect(),
+            write,
+        }
+    }
+
+    // must be called while holding the lock
+    pub(crate) fn update(&self, value: &T) {
+        // an odd sequence number tells readers that a write is in progress
+        self.seq.fetch_add(1, Ordering::SeqCst);
+        (self.write)(value, &self.words);
+        self.seq.fetch_add(1, Ordering::SeqCst);
+    }
+}
+impl<T: SeqLockable> Mirror<T> {
+    fn read(&self) -> T {
+        loop {
+            let before = self.seq.load(Ordering::SeqCst);
+            if before & 1 == 0 {
+                let mut words = self.words.iter();
+                let value = T::from_words(&mut || {
+                    words.next().map_or(0, |word| word.load(Ordering::SeqCst))
+                });
+                if self.seq.load(Ordering::SeqCst) == before {
+                    return value;
+                }
+            }
+            std::hint::spin_loop();
+        }
+    }
+}
+
+pub(crate) fn write_words<T: SeqLockable>(value: &T, words: &[AtomicU64]) {
+    let mut words = words.iter();
+    value.to_words(&mut |w| {
+        if let Some(word) = words.next() {
+            word.store(w, Ordering::SeqCst);
+        }
+    });
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "optimistic_reads")))]
+impl<T: SeqLockable> CondSync<T> {
+    /// Returns a copy of the wrapped variable, without touching the mutex if
+    /// [optimistic reads](crate::CondSyncBuilder::optimistic_reads) are enabled.
+    ///
+    /// Optimistic reads use a sequence counter and retry if a modification happened
+    /// concurrently, so they never block, and never delay modifying threads.
+    /// Without optimistic reads enabled, this method acquires the lock
+    /// (and ignores poisoning).
+    ///
+    /// Only available with feature `optimistic_reads`.
+    #[must_use]
+    pub fn read_optimistic(&self) -> T {
+        match self.0.mirror.get() {
+            Some(mirror) => mirror.read(),
+            None => *self.0.lock_unpoisoned(),
+        }
+    }
+}
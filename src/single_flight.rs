@@ -0,0 +1,166 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// Deduplicates concurrent computations of the same value.
+///
+/// When several threads call [`SingleFlight::do_or_wait`] with the same key at the same time,
+/// only the first one, the leader, runs its computation; the others wait for the leader's
+/// result and get a clone of it.
+/// Only computations that are in flight are shared; once the leader is done, the next call
+/// for the key computes again, so caching the results is up to the caller.
+///
+/// If the leader panics, one of the waiting threads takes over and runs its own computation.
+///
+/// Clones share the same set of computations.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::SingleFlight;
+/// use std::thread;
+///
+/// let single_flight = SingleFlight::<&str, String>::new();
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             let (value, _leader) = single_flight
+///                 .do_or_wait("config", || "loaded".to_string())
+///                 .unwrap();
+///             assert_eq!(value, "loaded");
+///         });
+///     }
+/// });
+/// ```
+pub struct SingleFlight<K, V>(Arc<Mutex<HashMap<K, CondSync<Flight<V>>>>>);
+
+enum Flight<V> {
+    Running,
+    Done(V),
+    Abandoned,
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Construct a new instance without computations.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(HashMap::new())))
+    }
+
+    /// Runs the given computation, unless a computation for the same key is already
+    /// in flight; in that case, blocks until that computation is done and returns its result.
+    ///
+    /// ## Returns
+    ///
+    /// The value, and `true` if the current thread was the leader, i.e., ran the computation.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if an internally used mutex is poisoned.
+    // the key is taken by value, like in `HashMap::insert`
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn do_or_wait<F>(&self, key: K, compute: F) -> Result<(V, bool), PoisonedError>
+    where
+        F: FnOnce() -> V,
+    {
+        let flight = loop {
+            let flight = {
+                let mut flights = self.0.lock()?;
+                if let Some(flight) = flights.get(&key) {
+                    flight.clone()
+                } else {
+                    let flight = CondSync::new(Flight::Running);
+                    flights.insert(key.clone(), flight.clone());
+                    break flight;
+                }
+            };
+            let mtx_guard = flight.0.lock()?;
+            let waited = flight.0.wait_for(
+                mtx_guard,
+                |flight| match flight {
+                    Flight::Running => None,
+                    Flight::Done(value) => Some(Some(value.clone())),
+                    Flight::Abandoned => Some(None),
+                },
+                || None,
+            )?;
+            if let Some(value) = waited.finish().flatten() {
+                return Ok((value, false));
+            }
+            // the leader panicked, so try to take over
+        };
+
+        let mut leading = Leading {
+            single_flight: self,
+            key: &key,
+            flight: &flight,
+            done: false,
+        };
+        let value = compute();
+        leading.finish(Flight::Done(value.clone()))?;
+        Ok((value, true))
+    }
+
+    /// Returns the number of computations that are in flight.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn in_flight(&self) -> Result<usize, PoisonedError> {
+        Ok(self.0.lock()?.len())
+    }
+}
+
+// Publishes the result of the leader; if the computation panics, the waiting threads are
+// told that the flight was abandoned.
+struct Leading<'a, K: Eq + Hash, V> {
+    single_flight: &'a SingleFlight<K, V>,
+    key: &'a K,
+    flight: &'a CondSync<Flight<V>>,
+    done: bool,
+}
+impl<K: Eq + Hash, V> Leading<'_, K, V> {
+    fn finish(&mut self, result: Flight<V>) -> Result<(), PoisonedError> {
+        self.done = true;
+        self.single_flight.0.lock()?.remove(self.key);
+        self.flight
+            .modify_and_notify(|flight| *flight = result, Other::All)
+    }
+}
+impl<K: Eq + Hash, V> Drop for Leading<'_, K, V> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.single_flight
+                .0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(self.key);
+            self.flight
+                .modify_and_notify(|flight| *flight = Flight::Abandoned, Other::All)
+                .ok();
+        }
+    }
+}
+
+impl<K, V> Clone for SingleFlight<K, V> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K, V> Default for SingleFlight<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
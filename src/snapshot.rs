@@ -0,0 +1,68 @@
+use crate::CondSync;
+use std::{
+    any::Any,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+// The latest state of the wrapped variable, shared as an `Arc`.
+//
+// Is only replaced while the mutex is held; readers only briefly lock the pointer,
+// never the mutex, so they are not blocked by modifications or waiting threads.
+struct Snapshot<T>(RwLock<Arc<T>>);
+
+// Type-erased holder of a `Snapshot<T>`, so that `CondSync<T>` does not need `T: Sync`
+// unless snapshots are used.
+pub(crate) struct SnapshotSlot<T> {
+    snapshot: Box<dyn Any + Send + Sync>,
+    update: fn(&(dyn Any + Send + Sync), &T),
+}
+impl<T> SnapshotSlot<T> {
+    pub(crate) fn new(value: &T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        Self {
+            snapshot: Box::new(Snapshot(RwLock::new(Arc::new(value.clone())))),
+            update: |snapshot, value| {
+                if let Some(snapshot) = snapshot.downcast_ref::<Snapshot<T>>() {
+                    let value = Arc::new(value.clone());
+                    *snapshot.0.write().unwrap_or_else(PoisonError::into_inner) = value;
+                }
+            },
+        }
+    }
+
+    // must be called while holding the lock
+    pub(crate) fn update(&self, value: &T) {
+        (self.update)(&*self.snapshot, value);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshots")))]
+impl<T> CondSync<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Returns the latest state of the wrapped variable, without touching the mutex if
+    /// [snapshots](crate::CondSyncBuilder::snapshots) are enabled.
+    ///
+    /// The returned snapshot is not affected by later modifications.
+    /// Without snapshots enabled, this method acquires the lock and clones the variable
+    /// (and ignores poisoning).
+    ///
+    /// Only available with feature `snapshots`.
+    #[must_use]
+    pub fn latest(&self) -> Arc<T> {
+        match self
+            .0
+            .snapshot
+            .get()
+            .and_then(|slot| slot.snapshot.downcast_ref::<Snapshot<T>>())
+        {
+            Some(snapshot) => {
+                Arc::clone(&snapshot.0.read().unwrap_or_else(PoisonError::into_inner))
+            }
+            None => Arc::new(self.clone_inner()),
+        }
+    }
+}
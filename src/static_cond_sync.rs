@@ -0,0 +1,60 @@
+use crate::CondSync;
+use std::{
+    ops::Deref,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+/// A [`CondSync`] that can be used in `static` items.
+///
+/// [`StaticCondSync::new`] is a `const fn`; the [`CondSync`] itself is created on first use,
+/// from the given value.
+/// `StaticCondSync` dereferences to [`CondSync`], so it offers the same methods.
+///
+/// This saves the `OnceLock` (or `lazy_static`) wrapper in the own code, but not more:
+/// the [`CondSync`] is allocated on first use, like any other instance,
+/// and until then the initial value is kept behind a mutex of its own.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::{Other, StaticCondSync};
+/// use std::thread;
+///
+/// static READY: StaticCondSync<bool> = StaticCondSync::new(false);
+///
+/// let handle = thread::spawn(|| {
+///     READY.modify_and_notify(|ready| *ready = true, Other::All).unwrap();
+/// });
+/// READY.wait_until(|ready| *ready).unwrap();
+/// handle.join().unwrap();
+/// ```
+pub struct StaticCondSync<T> {
+    initial: Mutex<Option<T>>,
+    cond_sync: OnceLock<CondSync<T>>,
+}
+
+impl<T> StaticCondSync<T> {
+    /// Construct a new instance, with the initial value of the wrapped variable.
+    pub const fn new(value: T) -> Self {
+        Self {
+            initial: Mutex::new(Some(value)),
+            cond_sync: OnceLock::new(),
+        }
+    }
+}
+
+impl<T> Deref for StaticCondSync<T> {
+    type Target = CondSync<T>;
+
+    fn deref(&self) -> &CondSync<T> {
+        self.cond_sync.get_or_init(|| {
+            let value = self
+                .initial
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take()
+                .unwrap_or_else(|| unreachable!("the CondSync is only initialized once"));
+            CondSync::new(value)
+        })
+    }
+}
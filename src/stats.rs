@@ -0,0 +1,306 @@
+use std::{
+    fmt::{self, Write},
+    panic::Location,
+    time::Duration,
+};
+
+/// Statistics about the usage of the internal mutex of a [`CondSync`](crate::CondSync),
+/// and about the waits on it.
+///
+/// Is returned by [`CondSync::contention_stats`](crate::CondSync::contention_stats).
+/// Long hold times for modifications or conditions point to closures that do too much
+/// work (or even I/O) while holding the lock.
+#[derive(Clone, Debug, Default)]
+pub struct ContentionStats {
+    pub(crate) lock_acquisition: DurationSummary,
+    pub(crate) modification: DurationSummary,
+    pub(crate) condition: DurationSummary,
+    pub(crate) wait: DurationSummary,
+    pub(crate) notifications: u64,
+    pub(crate) skipped_notifications: u64,
+    pub(crate) timeouts: u64,
+    pub(crate) waiters: usize,
+    pub(crate) longest_wait: Option<(Duration, &'static Location<'static>)>,
+    pub(crate) timing: TimingStats,
+}
+impl ContentionStats {
+    /// How long threads had to wait until they got the lock.
+    #[must_use]
+    pub fn lock_acquisition(&self) -> &DurationSummary {
+        &self.lock_acquisition
+    }
+    /// How long the lock was held for modifying the wrapped variable.
+    #[must_use]
+    pub fn modification(&self) -> &DurationSummary {
+        &self.modification
+    }
+    /// How long the lock was held for evaluating conditions.
+    #[must_use]
+    pub fn condition(&self) -> &DurationSummary {
+        &self.condition
+    }
+    /// How long the waiting methods took, from call to return.
+    #[must_use]
+    pub fn wait(&self) -> &DurationSummary {
+        &self.wait
+    }
+    /// The number of notifications that were sent.
+    #[must_use]
+    pub fn notifications(&self) -> u64 {
+        self.notifications
+    }
+    /// The number of notifications that did not need to signal the condition variable,
+    /// because no thread was waiting.
+    #[must_use]
+    pub fn skipped_notifications(&self) -> u64 {
+        self.skipped_notifications
+    }
+    /// The number of waits that ended with a timeout.
+    #[must_use]
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts
+    }
+    /// The number of threads that were waiting when the statistics were taken.
+    #[must_use]
+    pub fn waiters(&self) -> usize {
+        self.waiters
+    }
+    /// The longest completed wait, with its call site; a high-watermark that is reset with
+    /// [`CondSync::reset_contention_stats`](crate::CondSync::reset_contention_stats).
+    #[must_use]
+    pub fn longest_wait(&self) -> Option<(Duration, &'static Location<'static>)> {
+        self.longest_wait
+    }
+
+    /// Renders the statistics in the text exposition format of Prometheus,
+    /// with the label `cond_sync` set to the given name.
+    ///
+    /// The output contains the gauge `cond_sync_waiters`, the counters
+    /// `cond_sync_notifications_total` and `cond_sync_timeouts_total`,
+    /// and the histogram `cond_sync_wait_seconds`.
+    #[must_use]
+    pub fn to_prometheus(&self, name: &str) -> String {
+        let label = format!(
+            "cond_sync=\"{}\"",
+            name.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        );
+        let mut out = String::new();
+        // writing into a String does not fail
+        self.write_prometheus(&mut out, &label).ok();
+        out
+    }
+
+    fn write_prometheus(&self, out: &mut String, label: &str) -> fmt::Result {
+        for (name, kind, value) in [
+            ("cond_sync_waiters", "gauge", self.waiters as u64),
+            (
+                "cond_sync_notifications_total",
+                "counter",
+                self.notifications,
+            ),
+            ("cond_sync_timeouts_total", "counter", self.timeouts),
+        ] {
+            writeln!(out, "# TYPE {name} {kind}\n{name}{{{label}}} {value}")?;
+        }
+
+        writeln!(out, "# TYPE cond_sync_wait_seconds histogram")?;
+        let mut cumulative = 0;
+        for (idx, count) in self.wait.buckets.iter().enumerate() {
+            cumulative += count;
+            if PROMETHEUS_BUCKETS.contains(&idx) {
+                let upper_bound = Duration::from_nanos((1_u64 << idx) - 1);
+                writeln!(
+                    out,
+                    "cond_sync_wait_seconds_bucket{{{label},le=\"{}\"}} {cumulative}",
+                    upper_bound.as_secs_f64()
+                )?;
+            }
+        }
+        writeln!(
+            out,
+            "cond_sync_wait_seconds_bucket{{{label},le=\"+Inf\"}} {}",
+            self.wait.count
+        )?;
+        writeln!(
+            out,
+            "cond_sync_wait_seconds_sum{{{label}}} {}",
+            self.wait.total.as_secs_f64()
+        )?;
+        writeln!(
+            out,
+            "cond_sync_wait_seconds_count{{{label}}} {}",
+            self.wait.count
+        )
+    }
+}
+
+/// Statistics about the waits with a time limit, for tuning time budgets.
+///
+/// Is returned by [`CondSync::timing_stats`](crate::CondSync::timing_stats).
+/// Waits without a time limit are not considered.
+#[derive(Clone, Debug, Default)]
+pub struct TimingStats {
+    spare: DurationSummary,
+    overshoot: DurationSummary,
+}
+impl TimingStats {
+    pub(crate) fn record_satisfied(&mut self, spare: Duration) {
+        self.spare.record(spare);
+    }
+
+    pub(crate) fn record_expired(&mut self, overshoot: Duration) {
+        self.overshoot.record(overshoot);
+    }
+
+    /// The number of timed waits that ended because the condition was fulfilled.
+    #[must_use]
+    pub fn satisfied(&self) -> u64 {
+        self.spare.count
+    }
+
+    /// The number of timed waits that ended because the time limit was reached.
+    #[must_use]
+    pub fn expired(&self) -> u64 {
+        self.overshoot.count
+    }
+
+    /// The share of timed waits that expired, a value between 0.0 and 1.0.
+    #[must_use]
+    pub fn miss_ratio(&self) -> f64 {
+        let all = self.satisfied() + self.expired();
+        if all == 0 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let ratio = self.expired() as f64 / all as f64;
+            ratio
+        }
+    }
+
+    /// How much time was left when the condition was fulfilled.
+    #[must_use]
+    pub fn spare(&self) -> &DurationSummary {
+        &self.spare
+    }
+
+    /// How late expired waits returned, compared to their time limit.
+    #[must_use]
+    pub fn overshoot(&self) -> &DurationSummary {
+        &self.overshoot
+    }
+}
+
+// the indices of the buckets that are exported to Prometheus,
+// from about 1µs up to about 18min
+const PROMETHEUS_BUCKETS: std::ops::RangeInclusive<usize> = 10..=40;
+
+// one bucket per power of two of nanoseconds
+const NO_OF_BUCKETS: usize = 65;
+
+/// A summary of recorded durations.
+///
+/// Percentiles are approximated with a resolution of a power of two.
+#[derive(Clone, Debug)]
+pub struct DurationSummary {
+    count: u64,
+    total: Duration,
+    max: Duration,
+    buckets: [u64; NO_OF_BUCKETS],
+}
+impl Default for DurationSummary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+            buckets: [0; NO_OF_BUCKETS],
+        }
+    }
+}
+impl DurationSummary {
+    pub(crate) fn record(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.count += 1;
+        self.total = self.total.saturating_add(duration);
+        self.max = self.max.max(duration);
+        self.buckets[(u64::BITS - nanos.leading_zeros()) as usize] += 1;
+    }
+
+    /// The number of recorded durations.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of all recorded durations.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// The longest recorded duration.
+    #[must_use]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// The average of the recorded durations.
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos(
+                u64::try_from(self.total.as_nanos() / u128::from(self.count)).unwrap_or(u64::MAX),
+            )
+        }
+    }
+
+    /// An upper bound for the given percentile (a value between 0.0 and 100.0)
+    /// of the recorded durations.
+    #[must_use]
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let rank =
+            ((percentile.clamp(0.0, 100.0) / 100.0 * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (idx, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= rank {
+                let upper_bound = if idx >= 64 {
+                    u64::MAX
+                } else {
+                    (1_u64 << idx) - 1
+                };
+                return Duration::from_nanos(upper_bound).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Returns the histogram of the recorded durations, e.g. for exporting them into
+    /// a metrics system: pairs of an upper bound and the number of durations
+    /// that are not longer than this upper bound, but longer than the previous one.
+    ///
+    /// Only buckets with recorded durations are returned, in ascending order.
+    pub fn histogram(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(idx, count)| {
+                let upper_bound = if idx >= 64 {
+                    u64::MAX
+                } else {
+                    (1_u64 << idx) - 1
+                };
+                (Duration::from_nanos(upper_bound), *count)
+            })
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{remaining_until, CondSync, OperationKind, Other, PoisonedError, Reason, I};
+use std::{
+    sync::{Arc, Condvar, PoisonError},
+    time::{Duration, Instant},
+};
+
+impl<T> I<T> {
+    // the condvar of the tag, which is created with the first use of the tag
+    fn tag_cvar(&self, tag: &'static str) -> Arc<Condvar> {
+        Arc::clone(
+            self.tags
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(tag)
+                .or_default(),
+        )
+    }
+}
+
+// Notification of groups of waiting threads.
+#[cfg_attr(docsrs, doc(cfg(feature = "tags")))]
+impl<T> CondSync<T> {
+    /// Like [`CondSync::wait_until`], but the waiting thread is only woken up by
+    /// notifications for the given tag, i.e., with [`CondSync::notify_tag`] or
+    /// [`CondSync::modify_and_notify_tag`].
+    ///
+    /// This allows waking a group of threads, e.g. all writers, without waking the others,
+    /// like with separate condition variables.
+    /// The tag is also used as label, see [`CondSync::wait_until_named`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::thread;
+    ///
+    /// let cond_sync = CondSync::new(0_usize);
+    /// let cond_sync_t = cond_sync.clone();
+    /// let writer = thread::spawn(move || {
+    ///     cond_sync_t.wait_until_tagged("writers", |v| *v > 0).unwrap();
+    /// });
+    ///
+    /// cond_sync
+    ///     .modify_and_notify_tag(|v| *v = 1, "writers", Other::All)
+    ///     .unwrap();
+    /// writer.join().unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_tagged<F>(
+        &self,
+        tag: &'static str,
+        condition: F,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let cvar = self.0.tag_cvar(tag);
+        self.0
+            .intercept_labeled(OperationKind::Wait, "wait_until_tagged", Some(tag), || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for_on(&cvar, mtx_guard, |v| condition(v).then_some(()), || None)?
+                    .into_reason())
+            })
+    }
+
+    /// Like [`CondSync::wait_until_tagged`], but waits no longer than the given duration.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_until_or_timeout_tagged<F>(
+        &self,
+        tag: &'static str,
+        condition: F,
+        duration: Duration,
+    ) -> Result<Reason, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let cvar = self.0.tag_cvar(tag);
+        self.0.intercept_labeled(
+            OperationKind::Wait,
+            "wait_until_or_timeout_tagged",
+            Some(tag),
+            || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for_on(
+                        &cvar,
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(Instant::now().checked_add(duration)),
+                    )?
+                    .into_reason())
+            },
+        )
+    }
+
+    /// Notifies the threads that wait with the given tag.
+    ///
+    /// Threads that wait without tag, or with another tag, are not woken up.
+    /// Debouncing, sticky notifications, and wakers are not applied to tag notifications.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn notify_tag(&self, tag: &'static str, other: Other) -> Result<(), PoisonedError> {
+        let cvar = self.0.tag_cvar(tag);
+        // taking the lock ensures that no thread is between evaluating its condition and waiting
+        let _mtx_guard = self.0.lock()?;
+        match other {
+            Other::One => cvar.notify_one(),
+            Other::All => cvar.notify_all(),
+        }
+        Ok(())
+    }
+
+    /// Modifies the wrapped variable and notifies the threads that wait with the given tag.
+    ///
+    /// See [`CondSync::notify_tag`] for details.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    #[track_caller]
+    pub fn modify_and_notify_tag<F>(
+        &self,
+        modify: F,
+        tag: &'static str,
+        other: Other,
+    ) -> Result<(), PoisonedError>
+    where
+        F: Fn(&mut T),
+    {
+        let cvar = self.0.tag_cvar(tag);
+        self.0.intercept_labeled(
+            OperationKind::Modify,
+            "modify_and_notify_tag",
+            Some(tag),
+            || {
+                let mut mtx_guard = self.0.lock()?;
+                let generation = self.0.apply(&mut mtx_guard, modify);
+                match other {
+                    Other::One => cvar.notify_one(),
+                    Other::All => cvar.notify_all(),
+                }
+                drop(mtx_guard);
+                self.0.modified(generation, other);
+                Ok(())
+            },
+        )
+    }
+}
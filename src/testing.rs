@@ -0,0 +1,78 @@
+use crate::{CondSync, WaitError};
+use std::{fmt::Debug, panic, thread, time::Duration};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+impl<T> CondSync<T>
+where
+    T: Send,
+{
+    /// Poisons the internally used mutex, to test how code deals with
+    /// [`CondSyncError::Poisoned`](crate::CondSyncError::Poisoned).
+    ///
+    /// The mutex is poisoned by a helper thread that unwinds while holding the lock.
+    ///
+    /// Only available with feature `testing`.
+    pub fn poison(&self) {
+        thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let _mtx_guard = self.0.lock();
+                    // unlike panic!, this does not invoke the panic hook
+                    panic::resume_unwind(Box::new("poisoning the mutex on purpose"));
+                })
+                .join()
+                .ok();
+        });
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+impl<T> CondSync<T>
+where
+    T: Debug,
+{
+    /// Blocks the current thread until the given condition,
+    /// when called with the current value of the wrapped variable, returns `true`,
+    /// and panics if that does not happen within the given duration.
+    ///
+    /// Meant for tests, where a forgotten check of the [`Reason`](crate::Reason)
+    /// would let a timeout go unnoticed.
+    ///
+    /// Only available with feature `testing`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cond_sync::{CondSync, Other};
+    /// use std::{thread, time::Duration};
+    ///
+    /// let cond_sync = CondSync::new(0_usize);
+    /// let cond_sync_t = cond_sync.clone();
+    /// thread::spawn(move || {
+    ///     cond_sync_t.modify_and_notify(|v| *v = 42, Other::All).unwrap();
+    /// });
+    /// cond_sync.expect_within(Duration::from_secs(2), |v| *v == 42);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the condition is not fulfilled in time; the panic message contains
+    /// the call site, the elapsed time, and the state of the wrapped variable.
+    ///
+    /// Panics also if the internally used mutex is poisoned,
+    /// or if the call would deadlock (see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock)).
+    #[track_caller]
+    pub fn expect_within<F>(&self, duration: Duration, condition: F)
+    where
+        F: Fn(&T) -> bool,
+    {
+        match self.wait_or_timeout_error("expect_within", None, condition, duration) {
+            Ok(()) => {}
+            Err(WaitError::Timeout(error)) => panic!("{error}"),
+            Err(WaitError::Poisoned) => panic!("the mutex of the CondSync is poisoned"),
+            Err(WaitError::WouldDeadlock { instance }) => {
+                panic!("{}", crate::CondSyncError::WouldDeadlock { instance })
+            }
+        }
+    }
+}
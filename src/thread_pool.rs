@@ -0,0 +1,166 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::{
+    collections::VecDeque,
+    panic::{catch_unwind, AssertUnwindSafe},
+    thread::{self, JoinHandle},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A minimal pool of worker threads that execute jobs from a shared queue.
+///
+/// The pool has a fixed size. Jobs that panic do not take down their worker thread;
+/// they are only counted, see [`ThreadPool::panicked_jobs`].
+///
+/// Dropping the pool (or calling [`ThreadPool::shutdown`]) lets the workers finish all jobs
+/// that are still queued, and then joins them.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::ThreadPool;
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// let pool = ThreadPool::new(4);
+/// let counter = Arc::new(AtomicUsize::new(0));
+/// for _ in 0..10 {
+///     let counter_t = Arc::clone(&counter);
+///     pool.execute(move || {
+///         counter_t.fetch_add(1, Ordering::SeqCst);
+///     })
+///     .unwrap();
+/// }
+/// pool.shutdown();
+/// assert_eq!(counter.load(Ordering::SeqCst), 10);
+/// ```
+pub struct ThreadPool {
+    cond_sync: CondSync<State>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct State {
+    jobs: VecDeque<Job>,
+    active: usize,
+    panicked: usize,
+    shutdown: bool,
+}
+
+impl ThreadPool {
+    /// Construct a new instance with `size` worker threads.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is 0, or if a worker thread cannot be spawned.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "size must be at least 1");
+        let cond_sync = CondSync::new(State::default());
+        let workers = (0..size)
+            .map(|i| {
+                let cond_sync_t = cond_sync.clone();
+                thread::Builder::new()
+                    .name(format!("cond_sync-pool-{i}"))
+                    .spawn(move || work(&cond_sync_t))
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+        Self { cond_sync, workers }
+    }
+
+    /// Returns the number of worker threads.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues the given job for execution by one of the worker threads.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn execute<F>(&self, job: F) -> Result<(), PoisonedError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job: Job = Box::new(job);
+        self.cond_sync
+            .modify_and_notify(|state| state.jobs.push_back(job), Other::One)
+    }
+
+    /// Blocks the current thread until the queue is empty and no job is running.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    pub fn wait_idle(&self) -> Result<(), PoisonedError> {
+        self.cond_sync
+            .wait_until(|state| state.jobs.is_empty() && state.active == 0)?;
+        Ok(())
+    }
+
+    /// Returns the number of jobs that panicked so far.
+    #[must_use]
+    pub fn panicked_jobs(&self) -> usize {
+        let i = &self.cond_sync.0;
+        let mtx_guard = i.lock_unpoisoned();
+        i.holding(|| mtx_guard.panicked)
+    }
+
+    /// Lets the workers finish all queued jobs, and then joins them.
+    ///
+    /// This is what also happens when the pool is dropped.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.cond_sync
+            .modify_and_notify(|state| state.shutdown = true, Other::All)
+            .ok();
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+    }
+}
+
+fn work(cond_sync: &CondSync<State>) {
+    loop {
+        let mut job = None;
+        if cond_sync
+            .wait_until_and_modify(
+                |state| state.shutdown || !state.jobs.is_empty(),
+                |state| {
+                    job = state.jobs.pop_front();
+                    state.active += usize::from(job.is_some());
+                },
+                Other::One,
+            )
+            .is_err()
+        {
+            return;
+        }
+        // the queue is drained before shutting down
+        let Some(job) = job else {
+            return;
+        };
+        let panicked = catch_unwind(AssertUnwindSafe(job)).is_err();
+        // `wait_idle` may be waiting, so we need to notify all
+        cond_sync
+            .modify_and_notify(
+                |state| {
+                    state.active -= 1;
+                    state.panicked += usize::from(panicked);
+                },
+                Other::All,
+            )
+            .ok();
+    }
+}
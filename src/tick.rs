@@ -0,0 +1,173 @@
+use crate::{CondSync, OperationKind, Other, PoisonedError, Reason};
+
+/// Synchronizes worker threads with the frames (ticks) of a driver thread,
+/// e.g. in a game loop.
+///
+/// The driver calls [`TickBarrier::tick`] to advance the frame counter, which wakes up all
+/// waiting workers. Workers either wait for a specific tick with [`TickBarrier::wait_tick`],
+/// or use a [`TickWorker`], which remembers the last tick it has seen and reports
+/// the ticks that were missed in between.
+///
+/// The frame counter is a `u64` and starts with 0, so wraparound is not an issue in practice.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::TickBarrier;
+/// use std::thread;
+///
+/// let tick_barrier = TickBarrier::new();
+/// let mut worker = tick_barrier.worker();
+///
+/// let handle = thread::spawn(move || {
+///     let tick = worker.wait_next_tick().unwrap();
+///     println!("Worker: frame {}, missed {}", tick.number(), tick.missed());
+/// });
+///
+/// tick_barrier.tick().unwrap();
+/// handle.join().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TickBarrier(CondSync<u64>);
+
+impl TickBarrier {
+    /// Construct a new instance, with the frame counter at 0.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CondSync::new(0))
+    }
+
+    /// Advances the frame counter and wakes up all waiting workers.
+    ///
+    /// ## Returns
+    ///
+    /// The number of the new tick.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn tick(&self) -> Result<u64, PoisonedError> {
+        let mut number = 0;
+        self.0.modify_and_notify(
+            |v| {
+                *v += 1;
+                number = *v;
+            },
+            Other::All,
+        )?;
+        Ok(number)
+    }
+
+    /// Returns the number of the current tick.
+    #[must_use]
+    pub fn current(&self) -> u64 {
+        self.0.clone_inner()
+    }
+
+    /// Blocks the current thread until the frame counter has reached at least `number`.
+    ///
+    /// ## Returns
+    ///
+    /// The number of the current tick, which can be higher than `number`.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    #[track_caller]
+    pub fn wait_tick(&self, number: u64) -> Result<u64, PoisonedError> {
+        let i = &self.0 .0;
+        let (current, _) = i.intercept(OperationKind::Wait, "wait_tick", || {
+            let mtx_guard = i.lock()?;
+            // the current tick is read in the same critical section as the condition
+            let waited = i.wait_for(mtx_guard, |v| (*v >= number).then_some(*v), || None)?;
+            let current = waited
+                .finish()
+                .unwrap_or_else(|| unreachable!("unbounded waits only end with the condition"));
+            Ok((current, Reason::Condition))
+        })?;
+        Ok(current)
+    }
+
+    /// Creates a handle for a worker thread, which starts at the current tick.
+    #[must_use]
+    pub fn worker(&self) -> TickWorker {
+        TickWorker {
+            tick_barrier: self.clone(),
+            last_seen: self.current(),
+            total_missed: 0,
+        }
+    }
+}
+
+impl Default for TickBarrier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A worker's view on a [`TickBarrier`], remembers the last tick it has seen.
+pub struct TickWorker {
+    tick_barrier: TickBarrier,
+    last_seen: u64,
+    total_missed: u64,
+}
+
+impl TickWorker {
+    /// Blocks the current thread until a tick after the last seen one has happened.
+    ///
+    /// If the driver has ticked more than once since the last call, the skipped ticks
+    /// are reported as missed.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    pub fn wait_next_tick(&mut self) -> Result<Tick, PoisonedError> {
+        let number = self.tick_barrier.wait_tick(self.last_seen + 1)?;
+        let missed = number - self.last_seen - 1;
+        self.last_seen = number;
+        self.total_missed += missed;
+        Ok(Tick { number, missed })
+    }
+
+    /// Returns the number of the last tick this worker has seen.
+    #[must_use]
+    pub fn last_seen(&self) -> u64 {
+        self.last_seen
+    }
+
+    /// Returns the number of ticks this worker has missed so far.
+    #[must_use]
+    pub fn total_missed(&self) -> u64 {
+        self.total_missed
+    }
+}
+
+/// A tick as seen by a [`TickWorker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tick {
+    number: u64,
+    missed: u64,
+}
+impl Tick {
+    /// The number of the tick.
+    #[must_use]
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The number of ticks that happened between the previously seen tick and this one.
+    #[must_use]
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    /// Returns `true` if ticks were missed.
+    #[must_use]
+    pub fn has_missed(&self) -> bool {
+        self.missed > 0
+    }
+}
@@ -0,0 +1,87 @@
+use crate::PoisonedError;
+use std::{fmt, panic::Location, time::Duration};
+
+/// Error type of [`CondSync::wait_until_or_timeout_error`](crate::CondSync::wait_until_or_timeout_error).
+#[derive(Debug)]
+pub enum WaitError {
+    /// The timeout was reached before the condition was fulfilled.
+    Timeout(TimeoutError),
+    /// The internally used mutex was poisoned.
+    Poisoned,
+    /// A re-entrant call on an instance would have deadlocked,
+    /// see [`CondSyncError::WouldDeadlock`](crate::CondSyncError::WouldDeadlock).
+    WouldDeadlock {
+        /// The instance that the current thread tried to lock again.
+        instance: usize,
+    },
+}
+impl From<PoisonedError> for WaitError {
+    fn from(e: PoisonedError) -> Self {
+        match e {
+            PoisonedError::Poisoned => Self::Poisoned,
+            PoisonedError::WouldDeadlock { instance } => Self::WouldDeadlock { instance },
+        }
+    }
+}
+
+/// Describes a wait that ran into its timeout, with enough context to make sense of it
+/// in a log file.
+#[derive(Clone, Debug)]
+pub struct TimeoutError {
+    pub(crate) method: &'static str,
+    pub(crate) label: Option<&'static str>,
+    pub(crate) location: &'static Location<'static>,
+    pub(crate) duration: Duration,
+    pub(crate) elapsed: Duration,
+    pub(crate) state: String,
+}
+impl TimeoutError {
+    /// The name of the waiting method.
+    #[must_use]
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The label that was given to the wait, if any.
+    #[must_use]
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// The call site of the wait.
+    #[must_use]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The configured timeout.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The time that was actually spent waiting.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The `Debug` representation of the wrapped variable when the timeout was reached.
+    #[must_use]
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+}
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.method)?;
+        if let Some(label) = self.label {
+            write!(f, " (\"{label}\")")?;
+        }
+        write!(
+            f,
+            " at {} timed out after {:?} (timeout: {:?}), state: {}",
+            self.location, self.elapsed, self.duration, self.state
+        )
+    }
+}
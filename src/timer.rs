@@ -0,0 +1,142 @@
+use crate::{CondSync, Other};
+use std::{
+    cell::Cell,
+    cmp::Ordering as CmpOrdering,
+    collections::BinaryHeap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        Arc, OnceLock,
+    },
+    thread,
+    time::Instant,
+};
+
+// The crate's timer facility: a single background thread that executes scheduled tasks.
+//
+// Tasks are executed one after the other, so they must be short.
+pub(crate) type Task = Box<dyn FnOnce() + Send>;
+
+static TIMER: OnceLock<Timer> = OnceLock::new();
+
+struct Timer {
+    queue: CondSync<BinaryHeap<Entry>>,
+    next_seq: AtomicU64,
+}
+
+struct Entry {
+    at: Instant,
+    seq: u64,
+    task: Task,
+    state: Arc<AtomicU8>,
+}
+
+const PENDING: u8 = 0;
+const EXECUTED: u8 = 1;
+const CANCELLED: u8 = 2;
+
+// Allows cancelling a scheduled task.
+pub(crate) struct TimerHandle(Arc<AtomicU8>);
+impl TimerHandle {
+    // Returns true if the task was cancelled before it was executed.
+    pub(crate) fn cancel(&self) -> bool {
+        self.0
+            .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    pub(crate) fn is_pending(&self) -> bool {
+        self.0.load(Ordering::SeqCst) == PENDING
+    }
+}
+// the heap is a max-heap, so the order is reversed to get the earliest entry first
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (other.at, other.seq).cmp(&(self.at, self.seq))
+    }
+}
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+impl Eq for Entry {}
+
+// Schedules the task for execution at the given point in time.
+pub(crate) fn schedule(at: Instant, task: Task) -> TimerHandle {
+    let mut created = false;
+    let timer = TIMER.get_or_init(|| {
+        created = true;
+        Timer {
+            queue: CondSync::new(BinaryHeap::new()),
+            next_seq: AtomicU64::new(0),
+        }
+    });
+    if created {
+        thread::Builder::new()
+            .name("cond_sync-timer".to_string())
+            .spawn(move || run(timer))
+            .expect("failed to spawn the timer thread");
+    }
+    let state = Arc::new(AtomicU8::new(PENDING));
+    let entry = Entry {
+        at,
+        seq: timer.next_seq.fetch_add(1, Ordering::Relaxed),
+        task,
+        state: Arc::clone(&state),
+    };
+    let i = &timer.queue.0;
+    let mut queue = i.lock_unpoisoned();
+    let generation = i.apply(&mut queue, |queue| queue.push(entry));
+    i.notify(Other::One);
+    drop(queue);
+    i.modified(generation, Other::One);
+    TimerHandle(state)
+}
+
+fn run(timer: &'static Timer) {
+    let i = &timer.queue.0;
+    loop {
+        // the point in time of the earliest entry, as seen by the last check
+        let next_at = Cell::new(None);
+        let mut waited = i
+            .wait_for(
+                i.lock_unpoisoned(),
+                |queue| {
+                    next_at.set(queue.peek().map(|entry| entry.at));
+                    next_at.get().filter(|at| *at <= Instant::now()).map(|_| ())
+                },
+                || {
+                    next_at
+                        .get()
+                        .map(|at| at.saturating_duration_since(Instant::now()))
+                },
+            )
+            .expect("no code that can panic runs under the lock of the timer queue");
+        if waited.outcome.is_none() {
+            // the earliest entry became due right after it was checked
+            waited.finish();
+            continue;
+        }
+        let mut entry = None;
+        let generation = i.apply(&mut waited.mtx_guard, |queue| entry = queue.pop());
+        waited.finish();
+        i.modified(generation, None);
+
+        if let Some(entry) = entry {
+            if entry
+                .state
+                .compare_exchange(PENDING, EXECUTED, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                // a panicking task must not stop the timer
+                catch_unwind(AssertUnwindSafe(entry.task)).ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{CondSync, Other, PoisonedError};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash, Hasher},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A map of values that threads can wait for, each by its key.
+///
+/// [`WaitMap::wait`] blocks until another thread provides the value for the given key
+/// with [`WaitMap::complete`], and takes it out of the map.
+/// Each key has its own [`CondSync`], so completing one key does not wake up the threads
+/// that wait for other keys.
+///
+/// The order of `wait` and `complete` does not matter: a value that is completed before
+/// anyone waits for it is kept until it is fetched, or until it is discarded with
+/// [`WaitMap::remove`].
+/// If all waits for a key run into their timeout, the entry for that key is removed again,
+/// so abandoned waits leave nothing behind.
+///
+/// The map is sharded by the hash of the keys, so that threads working with unrelated keys
+/// rarely contend for the same lock, even with many thousands of keys in flight.
+///
+/// Clones share the same map.
+///
+/// ## Example
+///
+/// ```rust
+/// use cond_sync::WaitMap;
+/// use std::thread;
+///
+/// let responses = WaitMap::<u64, String>::new();
+///
+/// let responses_t = responses.clone();
+/// thread::spawn(move || {
+///     for request_id in 0..3 {
+///         responses_t.complete(request_id, format!("response {request_id}")).unwrap();
+///     }
+/// });
+///
+/// assert_eq!(responses.wait(&2).unwrap(), "response 2");
+/// ```
+pub struct WaitMap<K, V>(Arc<Shards<K, V>>);
+
+type Shard<K, V> = Mutex<HashMap<K, CondSync<Option<V>>>>;
+
+struct Shards<K, V> {
+    shards: Box<[Shard<K, V>]>,
+    hasher: RandomState,
+}
+
+// Number of shards of `WaitMap::new`.
+const DEFAULT_SHARDS: usize = 16;
+
+impl<K, V> WaitMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Construct a new, empty instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARDS)
+    }
+
+    /// Construct a new, empty instance, with the given number of shards.
+    ///
+    /// More shards reduce the contention between threads that work with different keys.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `shards` is zero.
+    #[must_use]
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "a WaitMap needs at least one shard");
+        Self(Arc::new(Shards {
+            shards: (0..shards).map(|_| Mutex::new(HashMap::new())).collect(),
+            hasher: RandomState::new(),
+        }))
+    }
+
+    /// Provides the value for the given key, and wakes up the threads that wait for it.
+    ///
+    /// A value that was completed before for the same key, and was not fetched yet,
+    /// is replaced.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if an internally used mutex is poisoned.
+    // the key is taken by value, like in `HashMap::insert`
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn complete(&self, key: K, value: V) -> Result<(), PoisonedError> {
+        let entry = self.entry(key.clone())?;
+        let mut value = Some(value);
+        entry.modify_and_notify(|v| *v = value.take(), Other::All)?;
+        self.release(&key, entry)
+    }
+
+    /// Blocks the current thread until the value for the given key is provided,
+    /// and takes it out of the map.
+    ///
+    /// If several threads wait for the same key, only one of them gets the value;
+    /// the others continue to wait for the next one.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if an internally used mutex is poisoned.
+    pub fn wait(&self, key: &K) -> Result<V, PoisonedError> {
+        let entry = self.entry(key.clone())?;
+        let value = entry.wait_drain(Option::is_some)?;
+        self.release(key, entry)?;
+        Ok(value.unwrap_or_else(|| unreachable!("waited for the value")))
+    }
+
+    /// Like [`WaitMap::wait`], but waits no longer than the given duration.
+    ///
+    /// ## Returns
+    ///
+    /// The value, or `None` if the timeout was reached.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if an internally used mutex is poisoned.
+    pub fn wait_timeout(&self, key: &K, duration: Duration) -> Result<Option<V>, PoisonedError> {
+        let entry = self.entry(key.clone())?;
+        let value = entry.wait_drain_or_timeout(Option::is_some, duration)?;
+        self.release(key, entry)?;
+        Ok(value.flatten())
+    }
+
+    /// Removes the entry for the given key, and returns its value, if it was completed
+    /// but not yet fetched.
+    ///
+    /// Threads that currently wait for the key are not affected.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if an internally used mutex is poisoned.
+    pub fn remove(&self, key: &K) -> Result<Option<V>, PoisonedError> {
+        let entry = self.shard(key).lock()?.remove(key);
+        match entry {
+            Some(entry) => Ok(entry.0.lock()?.take()),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the number of keys that are waited for, or that have a value
+    /// which was not yet fetched.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn len(&self) -> Result<usize, PoisonedError> {
+        let mut len = 0;
+        for shard in &*self.0.shards {
+            len += shard.lock()?.len();
+        }
+        Ok(len)
+    }
+
+    /// Returns `true` if no key is waited for, and no value is waiting to be fetched.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex is poisoned.
+    pub fn is_empty(&self) -> Result<bool, PoisonedError> {
+        for shard in &*self.0.shards {
+            if !shard.lock()?.is_empty() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn shard(&self, key: &K) -> &Shard<K, V> {
+        let mut hasher = self.0.hasher.build_hasher();
+        key.hash(&mut hasher);
+        let shards = &self.0.shards;
+        #[allow(clippy::cast_possible_truncation)] // only the lower bits matter
+        let index = hasher.finish() as usize % shards.len();
+        &shards[index]
+    }
+
+    fn entry(&self, key: K) -> Result<CondSync<Option<V>>, PoisonedError> {
+        Ok(self
+            .shard(&key)
+            .lock()?
+            .entry(key)
+            .or_insert_with(|| CondSync::new(None))
+            .clone())
+    }
+
+    // Gives back an entry that was obtained with `entry`, and removes it from the map
+    // if it is empty and not in use by any other thread.
+    // Entries are only given back under the lock of the map, so that the last user
+    // reliably sees that it is the last one.
+    fn release(&self, key: &K, entry: CondSync<Option<V>>) -> Result<(), PoisonedError> {
+        let mut map = self.shard(key).lock()?;
+        drop(entry);
+        if let Some(entry) = map.get(key) {
+            if Arc::strong_count(&entry.0) == 1 && entry.0.lock()?.is_none() {
+                map.remove(key);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> Clone for WaitMap<K, V> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K, V> Default for WaitMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
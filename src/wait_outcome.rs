@@ -0,0 +1,111 @@
+use crate::{remaining_until, CondSync, OperationKind, PoisonedError, Reason};
+use std::time::{Duration, Instant};
+
+/// Describes in detail how a wait ended, for profiling the wakeup behavior.
+///
+/// Is returned by [`CondSync::wait_until_detailed`] and
+/// [`CondSync::wait_until_or_timeout_detailed`].
+///
+/// Only available with feature `generations`.
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitOutcome {
+    pub(crate) reason: Reason,
+    pub(crate) wakeups: usize,
+    pub(crate) spurious_wakeups: usize,
+    pub(crate) last_wakeup_spurious: bool,
+    pub(crate) generation: u64,
+}
+impl WaitOutcome {
+    /// Why the wait ended.
+    #[must_use]
+    pub fn reason(&self) -> Reason {
+        self.reason
+    }
+    /// How often the thread was woken up, including wakeups at the end of internal time slices.
+    #[must_use]
+    pub fn wakeups(&self) -> usize {
+        self.wakeups
+    }
+    /// How often the thread was woken up although the wrapped variable was not modified
+    /// in the meantime, e.g. spuriously by the operating system, or by a notification
+    /// without modification.
+    #[must_use]
+    pub fn spurious_wakeups(&self) -> usize {
+        self.spurious_wakeups
+    }
+    /// Returns `true` if the last wakeup was spurious, in the sense of
+    /// [`WaitOutcome::spurious_wakeups`].
+    #[must_use]
+    pub fn is_last_wakeup_spurious(&self) -> bool {
+        self.last_wakeup_spurious
+    }
+    /// The number of modifications of the wrapped variable when the wait ended,
+    /// i.e. the generation at which the condition held (or the timeout was reached).
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "generations")))]
+impl<T> CondSync<T> {
+    /// Like [`CondSync::wait_until`], but returns details about the wait.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// Only available with feature `generations`.
+    #[track_caller]
+    pub fn wait_until_detailed<F>(&self, condition: F) -> Result<WaitOutcome, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0
+            .intercept(OperationKind::Wait, "wait_until_detailed", || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(mtx_guard, |v| condition(v).then_some(()), || None)?
+                    .into_wait_outcome())
+            })
+    }
+
+    /// Like [`CondSync::wait_until_or_timeout`], but returns details about the wait.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return an error if the internally used mutex being waited on is
+    /// poisoned when this thread re-acquires the lock.
+    /// For more information, see information about poisoning on the Mutex type.
+    ///
+    /// Only available with feature `generations`.
+    #[track_caller]
+    pub fn wait_until_or_timeout_detailed<F>(
+        &self,
+        condition: F,
+        duration: Duration,
+    ) -> Result<WaitOutcome, PoisonedError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.0.intercept(
+            OperationKind::Wait,
+            "wait_until_or_timeout_detailed",
+            || {
+                let mtx_guard = self.0.lock()?;
+                Ok(self
+                    .0
+                    .wait_for(
+                        mtx_guard,
+                        |v| condition(v).then_some(()),
+                        remaining_until(Instant::now().checked_add(duration)),
+                    )?
+                    .into_wait_outcome())
+            },
+        )
+    }
+}
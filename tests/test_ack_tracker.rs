@@ -0,0 +1,43 @@
+use cond_sync::AckTracker;
+use std::{panic, thread, time::Duration};
+
+#[test]
+fn test() {
+    let tracker = AckTracker::new(["a", "b", "c"]);
+    assert!(tracker
+        .wait_acked_up_to(1, Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+
+    thread::scope(|scope| {
+        for (follower, last) in [("a", 100), ("b", 50), ("c", 70)] {
+            let tracker = &tracker;
+            scope.spawn(move || {
+                for seq in 1..=last {
+                    tracker.ack(follower, seq).unwrap();
+                }
+            });
+        }
+        assert!(tracker
+            .wait_acked_up_to(50, Duration::from_secs(5))
+            .unwrap()
+            .is_condition());
+    });
+    assert_eq!(tracker.acked_up_to().unwrap(), 50);
+
+    // acknowledgments never go back
+    tracker.ack("a", 10).unwrap();
+    assert_eq!(
+        tracker.progress().unwrap(),
+        vec![
+            ("a".to_string(), 100),
+            ("b".to_string(), 50),
+            ("c".to_string(), 70)
+        ]
+    );
+
+    // an unknown follower is a bug, but it does not poison the tracker
+    assert!(panic::catch_unwind(panic::AssertUnwindSafe(|| tracker.ack("d", 1))).is_err());
+    tracker.ack("b", 70).unwrap();
+    assert_eq!(tracker.acked_up_to().unwrap(), 70);
+}
@@ -0,0 +1,39 @@
+use cond_sync::{Backoff, CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(false);
+    let backoff = Backoff::new(
+        Duration::from_millis(2),
+        2.0,
+        Duration::from_millis(10),
+        Duration::from_millis(50),
+    );
+
+    // at most 2 + 4 + 8 + 10 + 10 + 10 + 6 ms
+    let mut evaluations = 0;
+    let outcome = cond_sync
+        .wait_until_with_backoff(
+            |v| {
+                evaluations += 1;
+                *v
+            },
+            &backoff,
+        )
+        .unwrap();
+    assert!(outcome.reason().is_timeout());
+    assert!(outcome.intervals() <= 7);
+    assert!(outcome.elapsed() >= Duration::from_millis(50));
+    assert!(evaluations >= 8);
+
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(5));
+        cond_sync_t
+            .modify_and_notify(|v| *v = true, Other::All)
+            .unwrap();
+    });
+    let outcome = cond_sync.wait_until_with_backoff(|v| *v, &backoff).unwrap();
+    assert!(outcome.reason().is_condition());
+}
@@ -0,0 +1,50 @@
+use cond_sync::{BarrierTopology, CondBarrier};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+const NO_OF_THREADS: usize = 23;
+const NO_OF_PHASES: usize = 20;
+
+fn run_phases(topology: BarrierTopology) {
+    let barrier = CondBarrier::new(NO_OF_THREADS, topology);
+    let counter = Arc::new(AtomicUsize::new(0));
+    let leaders = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let barrier_t = barrier.clone();
+            let counter_t = Arc::clone(&counter);
+            let leaders_t = Arc::clone(&leaders);
+            thread::spawn(move || {
+                for phase in 0..NO_OF_PHASES {
+                    counter_t.fetch_add(1, Ordering::SeqCst);
+                    if barrier_t.wait().unwrap() {
+                        leaders_t.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // all threads have incremented the counter in this phase
+                    assert!(counter_t.load(Ordering::SeqCst) >= (phase + 1) * NO_OF_THREADS);
+                    barrier_t.wait().unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(leaders.load(Ordering::SeqCst), NO_OF_PHASES);
+}
+
+#[test]
+fn test_flat() {
+    run_phases(BarrierTopology::Flat);
+}
+
+#[test]
+fn test_tree() {
+    run_phases(BarrierTopology::Tree { fan_in: 2 });
+    run_phases(BarrierTopology::Tree { fan_in: 4 });
+}
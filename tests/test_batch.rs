@@ -0,0 +1,29 @@
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let batcher = CondSync::new(Vec::new());
+
+    let batcher_t = batcher.clone();
+    let producer = thread::spawn(move || {
+        for i in 0..10 {
+            batcher_t.push_and_notify(i, Other::All).unwrap();
+        }
+    });
+    assert_eq!(batcher.wait_batch(4).unwrap(), vec![0, 1, 2, 3]);
+    assert_eq!(batcher.wait_batch(4).unwrap(), vec![4, 5, 6, 7]);
+    producer.join().unwrap();
+
+    // the incomplete batch is flushed after the timeout
+    assert_eq!(
+        batcher
+            .wait_batch_or_timeout(4, Duration::from_millis(10))
+            .unwrap(),
+        vec![8, 9]
+    );
+    assert!(batcher
+        .wait_batch_or_timeout(4, Duration::from_millis(10))
+        .unwrap()
+        .is_empty());
+}
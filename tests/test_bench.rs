@@ -0,0 +1,19 @@
+#![cfg(feature = "bench")]
+use cond_sync::{bench_fan_in, bench_ping_pong, BenchOptions};
+
+#[test]
+fn test() {
+    let options = BenchOptions::new()
+        .rounds(500)
+        .threads(3)
+        .configure(|builder| builder.counting_notifications(true));
+
+    let ping_pong = bench_ping_pong(&options);
+    assert_eq!(ping_pong.operations(), 1_000);
+    assert!(ping_pong.mean() <= ping_pong.elapsed());
+    assert!(ping_pong.per_second() > 0.0);
+
+    let fan_in = bench_fan_in(&options);
+    assert_eq!(fan_in.operations(), 1_500);
+    assert!(fan_in.to_string().starts_with("1500 operations in "));
+}
@@ -0,0 +1,50 @@
+use cond_sync::{Broadcast, BroadcastError};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let broadcast = Broadcast::new(1_000);
+    let subscribers: Vec<_> = (0..4).map(|_| broadcast.subscribe().unwrap()).collect();
+
+    thread::scope(|scope| {
+        for subscriber in &subscribers {
+            scope.spawn(move || {
+                // every subscriber sees every version, in order
+                for expected in 1..=1_000_u64 {
+                    let (version, value) = subscriber.next_version().unwrap();
+                    assert_eq!(version, expected);
+                    assert_eq!(value, expected * 10);
+                }
+            });
+        }
+        for i in 1..=1_000_u64 {
+            assert_eq!(broadcast.publish(i * 10).unwrap(), i);
+        }
+    });
+}
+
+#[test]
+fn test_lagged() {
+    let broadcast = Broadcast::new(2);
+    let subscriber = broadcast.subscribe().unwrap();
+    assert_eq!(
+        subscriber
+            .next_version_timeout(Duration::from_millis(10))
+            .unwrap(),
+        None
+    );
+
+    for value in ["a", "b", "c", "d", "e"] {
+        broadcast.publish(value).unwrap();
+    }
+    assert_eq!(subscriber.pending().unwrap(), 2);
+    assert_eq!(subscriber.next_version(), Err(BroadcastError::Lagged(3)));
+    assert_eq!(subscriber.next_version(), Ok((4, "d")));
+    assert_eq!(subscriber.next_version(), Ok((5, "e")));
+
+    // a late subscriber only sees what is published after subscribing
+    let late = broadcast.subscribe().unwrap();
+    broadcast.publish("f").unwrap();
+    assert_eq!(late.next_version(), Ok((6, "f")));
+    assert_eq!(subscriber.next_version(), Ok((6, "f")));
+}
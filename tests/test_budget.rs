@@ -0,0 +1,33 @@
+use cond_sync::{Budget, BudgetError, CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let first = CondSync::new(false);
+    let second = CondSync::new(false);
+    let budget = Budget::new(Duration::from_millis(100));
+
+    let first_t = first.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        first_t
+            .modify_and_notify(|v| *v = true, Other::All)
+            .unwrap();
+    });
+    first
+        .wait_until_within_budget("first", |v| *v, &budget)
+        .unwrap();
+    assert!(budget.remaining() < Duration::from_millis(95));
+    assert!(!budget.is_exhausted());
+
+    assert_eq!(
+        second.wait_until_within_budget("second", |v| *v, &budget),
+        Err(BudgetError::Exhausted { step: "second" })
+    );
+    assert!(budget.is_exhausted());
+
+    // an exhausted budget still allows waits whose condition is already fulfilled
+    first
+        .wait_until_within_budget("third", |v| *v, &budget)
+        .unwrap();
+}
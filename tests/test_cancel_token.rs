@@ -0,0 +1,42 @@
+use cond_sync::{CancelToken, CondSync, Other};
+use std::{thread, time::Duration};
+const NO_OF_THREADS: usize = 3;
+
+#[test]
+fn test() {
+    let root = CancelToken::new();
+    let pipeline = root.child();
+    let cond_sync = CondSync::new(0_usize);
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let (token, cond_sync_t) = (pipeline.child(), cond_sync.clone());
+            thread::spawn(move || cond_sync_t.wait_until_or_cancelled(|v| *v == 100, &token))
+        })
+        .collect();
+
+    // cancelling a child does not affect the parent
+    let other = root.child();
+    other.cancel();
+    assert!(!root.is_cancelled());
+    thread::sleep(Duration::from_millis(10));
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+
+    root.cancel();
+    for handle in handles {
+        assert!(handle.join().unwrap().unwrap().is_cancelled());
+    }
+    assert!(pipeline.is_cancelled());
+    assert!(pipeline.child().is_cancelled());
+
+    // the condition takes precedence
+    cond_sync
+        .modify_and_notify(|v| *v = 100, Other::All)
+        .unwrap();
+    let reason = cond_sync
+        .wait_until_or_cancelled(|v| *v == 100, &pipeline)
+        .unwrap();
+    assert!(reason.is_condition());
+}
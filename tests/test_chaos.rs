@@ -0,0 +1,98 @@
+#![cfg(feature = "testing")]
+use cond_sync::{explore_interleavings, Chaos, CondSync, Other};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    explore_interleavings(20, |seed| {
+        let cond_sync = CondSync::builder(0_usize)
+            .chaos(
+                Chaos::new(seed)
+                    .notify_delay(Duration::from_millis(2))
+                    .notify_all_probability(0.5),
+            )
+            .build();
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let cond_sync_t = cond_sync.clone();
+                thread::spawn(move || {
+                    cond_sync_t
+                        .modify_and_notify(|v| *v += 1, Other::One)
+                        .unwrap();
+                })
+            })
+            .collect();
+        cond_sync.wait_until(|v| *v == 3).unwrap();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_failing_seed_is_reported() {
+    let message = panic::catch_unwind(AssertUnwindSafe(|| {
+        explore_interleavings(10, |seed| assert!(seed != 7, "bad seed"));
+    }))
+    .unwrap_err()
+    .downcast::<String>()
+    .unwrap();
+    assert_eq!(*message, "run with seed 7 failed: bad seed");
+}
+
+#[test]
+fn test_lost_notifications() {
+    let cond_sync = CondSync::builder(0_usize)
+        .chaos(Chaos::new(1).lost_notify_one_probability(1.0))
+        .build();
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t
+            .wait_until_or_timeout(|v| *v == 1, Duration::from_millis(200))
+            .unwrap()
+    });
+    thread::sleep(Duration::from_millis(50));
+    cond_sync.modify_and_notify(|v| *v = 1, Other::One).unwrap();
+    // the waiter only notices the modification with its timeout
+    assert!(handle.join().unwrap().is_condition());
+}
+
+#[test]
+fn test_poison() {
+    let cond_sync = CondSync::new(0_usize);
+    cond_sync.poison();
+    assert!(cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .is_err());
+    assert!(cond_sync.wait_until(|v| *v == 0).is_err());
+}
+
+#[test]
+fn test_spurious_wakeups() {
+    let cond_sync = CondSync::builder(0_usize)
+        .chaos(Chaos::new(2).spurious_wakeups(Duration::from_millis(2)))
+        .build();
+
+    // nobody notifies; the spurious wakeups are not mistaken for notifications
+    assert!(cond_sync
+        .wait_timeout(Duration::from_millis(20))
+        .unwrap()
+        .is_timeout());
+
+    let evaluations = std::cell::Cell::new(0);
+    let reason = cond_sync
+        .wait_until_or_timeout(
+            |_| {
+                evaluations.set(evaluations.get() + 1);
+                false
+            },
+            Duration::from_millis(50),
+        )
+        .unwrap();
+    assert!(reason.is_timeout());
+    assert!(evaluations.get() > 2);
+}
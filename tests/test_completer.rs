@@ -0,0 +1,45 @@
+use cond_sync::{AlreadyCompleted, AwaitError, Completer};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let (completer, mut awaiter) = Completer::new();
+    assert_eq!(
+        awaiter.wait_timeout(Duration::from_millis(10)),
+        Err(AwaitError::Timeout)
+    );
+
+    let other_completer = completer.clone();
+    let handle = thread::spawn(move || completer.complete("first"));
+    handle.join().unwrap().unwrap();
+    assert!(other_completer.is_completed());
+    assert_eq!(
+        other_completer.complete("second"),
+        Err(AlreadyCompleted("second"))
+    );
+    assert_eq!(awaiter.wait(), Ok("first"));
+}
+
+#[test]
+fn test_abandoned() {
+    let (completer, awaiter) = Completer::<usize>::new();
+    let other_completer = completer.clone();
+    thread::spawn(move || {
+        drop(completer);
+        thread::sleep(Duration::from_millis(10));
+        drop(other_completer);
+    });
+    assert_eq!(awaiter.wait(), Err(AwaitError::Abandoned));
+}
+
+#[test]
+fn test_already_taken() {
+    let (completer, mut awaiter) = Completer::new();
+    completer.complete(42).unwrap();
+    assert_eq!(awaiter.wait_timeout(Duration::from_millis(10)), Ok(42));
+    assert_eq!(
+        awaiter.wait_timeout(Duration::from_millis(10)),
+        Err(AwaitError::AlreadyTaken)
+    );
+    assert_eq!(awaiter.wait(), Err(AwaitError::AlreadyTaken));
+}
@@ -0,0 +1,46 @@
+use cond_sync::Completion;
+use std::{thread, time::Duration};
+
+#[test]
+fn test_subtree() {
+    let pipeline = Completion::new();
+    let stage_1 = pipeline.child().unwrap();
+    let stage_2 = pipeline.child().unwrap();
+    pipeline.complete().unwrap();
+
+    let mut sub_tasks = Vec::new();
+    for _ in 0..3 {
+        sub_tasks.push(stage_1.child().unwrap());
+    }
+    stage_1.complete().unwrap();
+    stage_2.complete().unwrap();
+
+    // stage 1 waits for its sub-tasks
+    assert!(!stage_1.is_complete());
+    assert!(stage_1
+        .wait_until_complete_or_timeout(Duration::from_millis(20))
+        .unwrap()
+        .is_timeout());
+    assert!(stage_2.is_complete());
+
+    for (i, sub_task) in sub_tasks.into_iter().enumerate() {
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10 * i as u64));
+            sub_task.complete().unwrap();
+        });
+    }
+
+    pipeline.wait_until_complete().unwrap();
+    assert!(stage_1.is_complete());
+}
+
+#[test]
+fn test_complete_twice() {
+    let parent = Completion::new();
+    let child = parent.child().unwrap();
+    child.complete().unwrap();
+    child.complete().unwrap(); // <- no effect
+    assert!(!parent.is_complete());
+    parent.complete().unwrap();
+    assert!(parent.is_complete());
+}
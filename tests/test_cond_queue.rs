@@ -0,0 +1,136 @@
+use cond_sync::CondQueue;
+use std::{thread, time::Duration};
+const NO_OF_PRODUCERS: usize = 4;
+const NO_OF_CONSUMERS: usize = 4;
+const ITEMS_PER_PRODUCER: usize = 1000;
+
+#[test]
+fn test() {
+    let queue = CondQueue::bounded(8);
+
+    let producers: Vec<_> = (0..NO_OF_PRODUCERS)
+        .map(|_| {
+            let queue_t = queue.clone();
+            thread::spawn(move || {
+                for i in 0..ITEMS_PER_PRODUCER {
+                    queue_t.push(i).unwrap();
+                }
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..NO_OF_CONSUMERS)
+        .map(|_| {
+            let queue_t = queue.clone();
+            thread::spawn(move || {
+                let mut sum = 0;
+                while let Some(item) = queue_t.pop_timeout(Duration::from_millis(200)).unwrap() {
+                    sum += item;
+                }
+                sum
+            })
+        })
+        .collect();
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    let sum: usize = consumers.into_iter().map(|c| c.join().unwrap()).sum();
+    assert_eq!(
+        sum,
+        NO_OF_PRODUCERS * (0..ITEMS_PER_PRODUCER).sum::<usize>()
+    );
+    assert!(queue.is_empty().unwrap());
+}
+
+#[test]
+fn test_full() {
+    let queue = CondQueue::bounded(2);
+    assert_eq!(queue.try_push(1).unwrap(), None);
+    assert_eq!(queue.try_push(2).unwrap(), None);
+    assert_eq!(queue.try_push(3).unwrap(), Some(3));
+    assert_eq!(
+        queue.push_timeout(4, Duration::from_millis(10)).unwrap(),
+        Some(4)
+    );
+    assert_eq!(queue.len().unwrap(), queue.capacity());
+    assert_eq!(queue.try_pop().unwrap(), Some(1));
+    assert_eq!(queue.pop().unwrap(), 2);
+    assert_eq!(queue.try_pop().unwrap(), None);
+}
+
+#[test]
+fn test_fair_order() {
+    let queue = CondQueue::bounded_fair(1);
+    assert!(queue.is_fair());
+    queue.push(0).unwrap();
+
+    thread::scope(|scope| {
+        // the producers queue up one after the other, while the queue is full
+        for i in 1..=5 {
+            let queue = &queue;
+            scope.spawn(move || queue.push(i).unwrap());
+            thread::sleep(Duration::from_millis(20));
+        }
+        // a producer that gives up does not block the line
+        assert_eq!(
+            queue.push_timeout(99, Duration::from_millis(10)).unwrap(),
+            Some(99)
+        );
+        let order: Vec<_> = (0..=5).map(|_| queue.pop().unwrap()).collect();
+        assert_eq!(order, vec![0, 1, 2, 3, 4, 5]);
+    });
+
+    // the same for consumers, while the queue is empty
+    thread::scope(|scope| {
+        let consumers: Vec<_> = (0..5)
+            .map(|_| {
+                let queue = &queue;
+                let consumer = scope.spawn(move || queue.pop().unwrap());
+                thread::sleep(Duration::from_millis(20));
+                consumer
+            })
+            .collect();
+        for i in 0..5 {
+            queue.push(i).unwrap();
+        }
+        for (i, consumer) in consumers.into_iter().enumerate() {
+            assert_eq!(consumer.join().unwrap(), i);
+        }
+    });
+}
+
+#[test]
+fn test_fair_stress() {
+    // all items arrive, also if threads leave their line because of timeouts
+    let queue = CondQueue::bounded_fair(2);
+    thread::scope(|scope| {
+        for _ in 0..NO_OF_PRODUCERS {
+            scope.spawn(|| {
+                for i in 0..ITEMS_PER_PRODUCER {
+                    let mut item = i;
+                    while let Some(rejected) =
+                        queue.push_timeout(item, Duration::from_micros(50)).unwrap()
+                    {
+                        item = rejected;
+                    }
+                }
+            });
+        }
+        let consumers: Vec<_> = (0..NO_OF_CONSUMERS)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut sum = 0;
+                    while let Some(item) = queue.pop_timeout(Duration::from_millis(200)).unwrap() {
+                        sum += item;
+                    }
+                    sum
+                })
+            })
+            .collect();
+        let sum: usize = consumers.into_iter().map(|c| c.join().unwrap()).sum();
+        assert_eq!(
+            sum,
+            NO_OF_PRODUCERS * (0..ITEMS_PER_PRODUCER).sum::<usize>()
+        );
+    });
+}
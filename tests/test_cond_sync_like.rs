@@ -0,0 +1,38 @@
+use cond_sync::{CondSync, CondSyncLike, Other};
+use std::thread;
+const NO_OF_THREADS: usize = 5;
+
+fn run_workers<C>(cond_sync: &C) -> C::WaitResult<'_, fn(&usize) -> bool>
+where
+    C: CondSyncLike<usize> + Send + 'static,
+{
+    for _ in 0..NO_OF_THREADS {
+        let cond_sync_t = cond_sync.clone_handle();
+        thread::spawn(move || {
+            let _ = cond_sync_t.modify_and_notify(|v| *v += 1, Other::One);
+        });
+    }
+    let condition: fn(&usize) -> bool = |v| *v == NO_OF_THREADS;
+    cond_sync.wait_until(condition)
+}
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    assert!(run_workers(&cond_sync).unwrap().is_condition());
+    assert_eq!(cond_sync.try_clone_inner().unwrap(), NO_OF_THREADS);
+}
+
+#[test]
+fn test_fn_once() {
+    fn hand_over<C: CondSyncLike<Vec<u8>>>(
+        cond_sync: &C,
+        batch: Vec<u8>,
+    ) -> C::ModifyResult<'_, impl FnOnce(&mut Vec<u8>)> {
+        // the modification consumes the batch
+        cond_sync.modify_and_notify(move |v| *v = batch, Other::All)
+    }
+    let cond_sync = CondSync::new(Vec::new());
+    hand_over(&cond_sync, vec![1, 2]).unwrap();
+    assert_eq!(cond_sync.clone_inner(), vec![1, 2]);
+}
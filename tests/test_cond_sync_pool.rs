@@ -0,0 +1,80 @@
+use cond_sync::{CondSyncPool, Other};
+use std::thread;
+
+#[test]
+fn test() {
+    let pool = CondSyncPool::new(2, || 0_usize);
+    assert_eq!(pool.idle(), 0);
+
+    for _ in 0..5 {
+        let counter = pool.checkout();
+        // recycled instances start from the initial value
+        assert_eq!(counter.try_clone_inner().unwrap(), 0);
+        let counter_t = (*counter).clone();
+        let handle = thread::spawn(move || {
+            counter_t
+                .modify_and_notify(|v| *v += 1, Other::All)
+                .unwrap();
+        });
+        counter.wait_until(|v| *v == 1).unwrap();
+        handle.join().unwrap();
+    }
+    assert_eq!(pool.idle(), 1);
+
+    // instances with living clones are not recycled
+    let counter = pool.checkout();
+    let clone = (*counter).clone();
+    drop(counter);
+    assert_eq!(pool.idle(), 0);
+    drop(clone);
+
+    // no more than max_idle instances are kept
+    let checked_out: Vec<_> = (0..3).map(|_| pool.checkout()).collect();
+    drop(checked_out);
+    assert_eq!(pool.idle(), 2);
+}
+
+#[cfg(feature = "observers")]
+#[test]
+fn test_observers_are_not_carried_over() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let pool = CondSyncPool::new(1, || 0_usize);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let first = pool.checkout();
+    let calls_t = Arc::clone(&calls);
+    first
+        .on_modify(move |_| {
+            calls_t.fetch_add(1, Ordering::SeqCst);
+        })
+        .detach();
+    first.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    drop(first);
+    assert_eq!(pool.idle(), 1);
+
+    // the recycled instance does not call the observer of the previous checkout
+    let second = pool.checkout();
+    assert_eq!(pool.idle(), 0);
+    second.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_referenced_instances_are_not_recycled() {
+    use cond_sync::CancelToken;
+
+    let pool = CondSyncPool::new(1, || 0_usize);
+    let token = CancelToken::new();
+    let cond_sync = pool.checkout();
+    cond_sync
+        .wait_until_or_cancelled(|v| *v == 0, &token)
+        .unwrap();
+    // the token still refers to the instance, and would wake its next user
+    drop(cond_sync);
+    assert_eq!(pool.idle(), 0);
+}
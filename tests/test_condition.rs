@@ -0,0 +1,66 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+const NO_OF_THREADS: usize = 20;
+
+#[test]
+fn test_coalesced_evaluation() {
+    let cond_sync = CondSync::new(false);
+    let evaluations = Arc::new(AtomicUsize::new(0));
+    let evaluations_c = Arc::clone(&evaluations);
+    let ready = cond_sync.condition("ready", move |v| {
+        evaluations_c.fetch_add(1, Ordering::SeqCst);
+        *v
+    });
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let ready = ready.clone();
+            thread::spawn(move || ready.wait().unwrap())
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(100)); // let the threads start waiting
+
+    cond_sync
+        .modify_and_notify(|v| *v = true, Other::All)
+        .unwrap();
+    for handle in handles {
+        assert!(handle.join().unwrap().is_condition());
+    }
+    // once for the initial state, once after the modification
+    assert_eq!(evaluations.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_wait_timeout() {
+    let cond_sync = CondSync::new(0_usize);
+    let positive = cond_sync.condition("positive", |v| *v > 0);
+    assert!(positive
+        .wait_timeout(Duration::from_millis(20))
+        .unwrap()
+        .is_timeout());
+
+    cond_sync.modify_and_notify(|v| *v = 1, Other::All).unwrap();
+    assert!(positive
+        .wait_timeout(Duration::from_millis(20))
+        .unwrap()
+        .is_condition());
+}
+
+#[test]
+fn test_is_met() {
+    let cond_sync = CondSync::new(0_usize);
+    let positive = cond_sync.condition("positive", |v| *v > 0);
+    assert_eq!(positive.name(), "positive");
+    assert!(!positive.is_met().unwrap());
+
+    cond_sync.modify_and_notify(|v| *v = 1, Other::All).unwrap();
+    assert!(positive.is_met().unwrap());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "stats")]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        for _ in 0..10 {
+            cond_sync_t
+                .modify_and_notify(|v| *v += 1, Other::All)
+                .unwrap();
+        }
+        // a slow modification
+        cond_sync_t
+            .modify_and_notify(|_| thread::sleep(Duration::from_millis(20)), Other::All)
+            .unwrap();
+    });
+    cond_sync.wait_until(|v| *v == 10).unwrap();
+    handle.join().unwrap();
+
+    let stats = cond_sync.contention_stats();
+    println!("{stats:#?}");
+    assert_eq!(stats.modification().count(), 11);
+    assert!(stats.modification().max() >= Duration::from_millis(20));
+    assert!(stats.modification().percentile(50.0) < Duration::from_millis(20));
+    assert!(stats.condition().count() >= 1);
+    assert!(stats.lock_acquisition().count() >= 12);
+
+    cond_sync.reset_contention_stats();
+    for ms in [1, 2, 4, 30] {
+        cond_sync
+            .wait_until_or_timeout(|_| false, Duration::from_millis(ms))
+            .unwrap();
+    }
+    let wait = cond_sync.contention_stats().wait().clone();
+    assert_eq!(wait.count(), 4);
+    assert!(wait.percentile(99.0) >= Duration::from_millis(30));
+    let histogram: Vec<_> = wait.histogram().collect();
+    assert_eq!(histogram.iter().map(|(_, count)| count).sum::<u64>(), 4);
+    assert!(histogram.windows(2).all(|w| w[0].0 < w[1].0));
+    let stats = cond_sync.contention_stats();
+    assert_eq!(stats.timeouts(), 4);
+    assert_eq!(stats.waiters(), 0);
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    let exposition = cond_sync.contention_stats().to_prometheus("test \"1\"");
+    println!("{exposition}");
+    assert!(exposition.contains("cond_sync_notifications_total{cond_sync=\"test \\\"1\\\"\"} 1\n"));
+    assert!(exposition.contains("cond_sync_timeouts_total{cond_sync=\"test \\\"1\\\"\"} 4\n"));
+    assert!(exposition
+        .contains("cond_sync_wait_seconds_bucket{cond_sync=\"test \\\"1\\\"\",le=\"+Inf\"} 4\n"));
+
+    cond_sync.reset_contention_stats();
+    assert_eq!(cond_sync.contention_stats().modification().count(), 0);
+}
@@ -0,0 +1,60 @@
+use cond_sync::{CoordScope, ScopeError};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let finished = AtomicUsize::new(0);
+    CoordScope::run(|scope| {
+        for i in 0..5 {
+            let finished = &finished;
+            scope.spawn(format!("worker-{i}"), move || {
+                thread::sleep(Duration::from_millis(10));
+                finished.fetch_add(1, Ordering::SeqCst);
+            })?;
+        }
+        scope.wait_all(Duration::from_secs(10))
+    })
+    .unwrap();
+    assert_eq!(finished.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn test_panic() {
+    let result = CoordScope::run(|scope| {
+        scope.spawn("slow", || thread::sleep(Duration::from_millis(100)))?;
+        scope.spawn("failing", || panic!("failure {}", 42))?;
+        scope.wait_all(Duration::from_secs(10))
+    });
+    assert_eq!(
+        result,
+        Err(ScopeError::Panicked {
+            worker: "failing".to_string(),
+            message: "failure 42".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_panic_without_wait() {
+    // the panic is reported even if the closure does not wait for the workers
+    let result = CoordScope::run(|scope| {
+        scope.spawn("failing", || panic!("failure"))?;
+        Ok(17)
+    });
+    assert!(matches!(result, Err(ScopeError::Panicked { worker, .. }) if worker == "failing"));
+}
+
+#[test]
+fn test_timeout() {
+    let result = CoordScope::run(|scope| {
+        scope.spawn("slow", || thread::sleep(Duration::from_millis(100)))?;
+        let timed_out = scope.wait_all(Duration::from_millis(10));
+        assert_eq!(timed_out, Err(ScopeError::Timeout { running: 1 }));
+        Ok("joined anyway")
+    });
+    assert_eq!(result, Ok("joined anyway"));
+}
@@ -0,0 +1,169 @@
+use cond_sync::{CoordinationError, Coordinator};
+use std::{thread, time::Duration};
+const NO_OF_THREADS: usize = 5;
+
+#[test]
+fn test() {
+    let coordinator = Coordinator::<String>::new();
+    for _ in 0..NO_OF_THREADS {
+        let participant = coordinator.add_participant();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(5));
+            participant.done();
+        });
+    }
+    coordinator.wait_until_all_ok().unwrap();
+}
+
+#[test]
+fn test_failure() {
+    let coordinator = Coordinator::<String>::new();
+    let mut silent = None;
+    for i in 0..NO_OF_THREADS {
+        let participant = coordinator.add_participant();
+        if i == 3 {
+            // worker 3 never reports
+            silent = Some(participant);
+            continue;
+        }
+        thread::spawn(move || {
+            if i == 2 {
+                participant.fail(format!("worker {i} failed"));
+            } else {
+                participant.done();
+            }
+        });
+    }
+    match coordinator.wait_until_all_ok() {
+        Err(CoordinationError::Failed { participant, error }) => {
+            assert_eq!(participant.index(), 2);
+            assert_eq!(error, "worker 2 failed");
+        }
+        _ => panic!("failure expected"),
+    }
+    drop(silent);
+}
+
+#[test]
+fn test_timeout() {
+    let coordinator = Coordinator::<String>::new();
+    let participants: Vec<_> = (0..NO_OF_THREADS)
+        .map(|i| coordinator.add_named_participant(format!("worker-{i}")))
+        .collect();
+    let unnamed = coordinator.add_participant();
+    // the silent participants are kept, as dropping them would count as a failure
+    let (ready, silent): (Vec<_>, Vec<_>) = participants
+        .into_iter()
+        .partition(|participant| participant.id().index() % 2 == 0);
+    for participant in ready {
+        participant.done();
+    }
+    match coordinator.wait_until_all_ok_or_timeout(Duration::from_millis(10)) {
+        Err(CoordinationError::Timeout { outstanding }) => {
+            let outstanding: Vec<String> = outstanding.iter().map(ToString::to_string).collect();
+            assert_eq!(outstanding, vec!["worker-1", "worker-3", "participant #5"]);
+        }
+        _ => panic!("timeout expected"),
+    }
+    assert_eq!(unnamed.id().name(), None);
+    drop(silent);
+}
+
+#[test]
+fn test_all_done() {
+    let coordinator = Coordinator::<String>::new();
+    for i in 0..NO_OF_THREADS {
+        let participant = coordinator.add_participant();
+        thread::spawn(move || {
+            if i % 2 == 1 {
+                participant.fail(format!("worker {i} failed"));
+            } else {
+                participant.done();
+            }
+        });
+    }
+    let report = coordinator.wait_until_all_done().unwrap();
+    assert!(!report.is_success());
+    assert!(report.outstanding().is_empty());
+    let mut failed: Vec<_> = report
+        .into_failures()
+        .into_iter()
+        .map(|(participant, error)| (participant.index(), error))
+        .collect();
+    failed.sort();
+    assert_eq!(
+        failed,
+        vec![
+            (1, "worker 1 failed".to_string()),
+            (3, "worker 3 failed".to_string())
+        ]
+    );
+
+    let late = coordinator.add_participant();
+    assert_eq!(coordinator.outstanding().unwrap(), vec![late.id().clone()]);
+    let report = coordinator
+        .wait_until_all_done_or_timeout(Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(report.outstanding(), &[late.id().clone()]);
+    late.done();
+    // the earlier failures are still reported
+    let report = coordinator.wait_until_all_done().unwrap();
+    assert!(report.outstanding().is_empty());
+    assert_eq!(report.failures().len(), 2);
+}
+
+#[test]
+fn test_all_done_then_all_ok() {
+    let coordinator = Coordinator::<String>::new();
+    coordinator.add_participant().fail("failed".to_string());
+    coordinator.add_participant().done();
+
+    assert_eq!(
+        coordinator.wait_until_all_done().unwrap().failures().len(),
+        1
+    );
+    // the report did not consume the failure
+    assert!(matches!(
+        coordinator.wait_until_all_ok(),
+        Err(CoordinationError::Failed { .. })
+    ));
+}
+
+#[test]
+fn test_wait_twice_after_failure() {
+    let coordinator = Coordinator::<String>::new();
+    let failing = coordinator.add_participant();
+    let succeeding = coordinator.add_participant();
+    failing.fail("failed".to_string());
+    succeeding.done();
+
+    // the failure is reported to every wait, also to clones
+    let clone = coordinator.clone();
+    for coordinator in [&coordinator, &clone, &coordinator] {
+        assert!(matches!(
+            coordinator.wait_until_all_ok(),
+            Err(CoordinationError::Failed { error, .. }) if error == "failed"
+        ));
+    }
+}
+
+#[test]
+fn test_participant_dropped() {
+    let coordinator = Coordinator::<String>::new();
+    let participant = coordinator.add_participant();
+    let id = participant.id().clone();
+    thread::spawn(move || {
+        let _participant = participant;
+        panic!("worker panics before reporting");
+    });
+    assert_eq!(
+        coordinator.wait_until_all_ok(),
+        Err(CoordinationError::Abandoned {
+            participant: id.clone()
+        })
+    );
+    assert_eq!(
+        coordinator.wait_until_all_done().unwrap().abandoned(),
+        &[id]
+    );
+}
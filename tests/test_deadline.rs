@@ -0,0 +1,81 @@
+use cond_sync::{CondSync, Other};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn test_extend() {
+    let cond_sync = CondSync::new(false);
+    let deadline = cond_sync.deadline(Duration::from_millis(100));
+
+    let cond_sync_t = cond_sync.clone();
+    let deadline_t = deadline.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        deadline_t.extend(Duration::from_millis(500)); // <- allow more time
+        thread::sleep(Duration::from_millis(200));
+        cond_sync_t
+            .modify_and_notify(|v| *v = true, Other::One)
+            .unwrap();
+    });
+
+    assert!(cond_sync
+        .wait_until_or_deadline(|v| *v, &deadline)
+        .unwrap()
+        .is_condition());
+}
+
+#[test]
+fn test_expire_now() {
+    let cond_sync = CondSync::new(false);
+    let deadline = cond_sync.deadline(Duration::from_secs(60));
+
+    let deadline_t = deadline.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        deadline_t.expire_now(); // <- give up early
+    });
+
+    let start = Instant::now();
+    assert!(cond_sync
+        .wait_until_or_deadline(|v| *v, &deadline)
+        .unwrap()
+        .is_timeout());
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(deadline.remaining(), Duration::ZERO);
+}
+
+#[cfg(feature = "notifications")]
+#[test]
+fn test_extend_is_no_notification() {
+    let cond_sync = CondSync::new(false);
+    let deadline = cond_sync.deadline(Duration::from_secs(60));
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || cond_sync_t.wait_timeout(Duration::from_millis(200)));
+    thread::sleep(Duration::from_millis(20));
+    deadline.extend(Duration::from_secs(1)); // <- wakes up the waiting thread
+    assert!(handle.join().unwrap().unwrap().is_timeout());
+}
+
+#[test]
+fn test_far_future() {
+    let cond_sync = CondSync::new(false);
+    let deadline = cond_sync.deadline(Duration::MAX);
+    assert_eq!(deadline.remaining(), Duration::MAX);
+    deadline.extend(Duration::from_secs(1));
+    assert_eq!(deadline.remaining(), Duration::MAX);
+
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        cond_sync_t
+            .modify_and_notify(|v| *v = true, Other::One)
+            .unwrap();
+    });
+    assert!(cond_sync
+        .wait_until_or_deadline(|v| *v, &deadline)
+        .unwrap()
+        .is_condition());
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "debounce")]
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+const NO_OF_MODIFICATIONS: usize = 1000;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::builder(0_usize)
+        .debounce(Duration::from_millis(20), Duration::from_millis(200))
+        .build();
+    let evaluations = Arc::new(AtomicUsize::new(0));
+
+    let cond_sync_t = cond_sync.clone();
+    let evaluations_t = Arc::clone(&evaluations);
+    let handle = thread::spawn(move || {
+        cond_sync_t
+            .wait_until(|v| {
+                evaluations_t.fetch_add(1, Ordering::SeqCst);
+                *v == NO_OF_MODIFICATIONS
+            })
+            .unwrap();
+    });
+    thread::sleep(Duration::from_millis(10));
+
+    for _ in 0..NO_OF_MODIFICATIONS {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    }
+    handle.join().unwrap();
+    // the waiter was not woken up with every modification
+    assert!(evaluations.load(Ordering::SeqCst) < 10);
+}
+
+#[test]
+fn test_max_latency() {
+    let cond_sync = CondSync::builder(0_usize)
+        .debounce(Duration::from_millis(50), Duration::from_millis(100))
+        .build();
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t
+            .wait_until_or_timeout(|v| *v > 0, Duration::from_secs(10))
+            .unwrap()
+    });
+    thread::sleep(Duration::from_millis(10));
+
+    // notifications keep coming within the window, but the max latency forces the wakeup
+    let start = Instant::now();
+    while !handle.is_finished() {
+        assert!(start.elapsed() < Duration::from_secs(5));
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::One)
+            .unwrap();
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(handle.join().unwrap().is_condition());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "diagnostics")]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(false);
+    let cond_sync_t = cond_sync.clone();
+    let line = line!() + 4;
+    let handle = thread::Builder::new()
+        .name("flag-waiter".to_string())
+        .spawn(move || {
+            cond_sync_t.wait_until_named("flag", |v| *v).unwrap();
+        })
+        .unwrap();
+
+    while cond_sync.waiting().is_empty() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let waiting = cond_sync.waiting();
+    assert_eq!(waiting.len(), 1);
+    assert_eq!(waiting[0].method(), "wait_until");
+    assert_eq!(waiting[0].label(), Some("flag"));
+    assert_eq!(waiting[0].thread_name(), Some("flag-waiter"));
+    assert_eq!(waiting[0].thread_id(), handle.thread().id());
+    assert_eq!(waiting[0].location().file(), file!());
+    assert_eq!(waiting[0].location().line(), line);
+    let description = waiting[0].to_string();
+    assert!(description.starts_with("thread 'flag-waiter' waiting "));
+    assert!(description.contains(" in wait_until (\"flag\") at tests/test_diagnostics.rs"));
+
+    cond_sync
+        .modify_and_notify(|v| *v = true, Other::All)
+        .unwrap();
+    handle.join().unwrap();
+    assert!(cond_sync.waiting().is_empty());
+}
@@ -0,0 +1,66 @@
+use cond_sync::{DispatchError, Dispatcher};
+use std::{sync::mpsc, thread, time::Duration};
+const NO_OF_THREADS: usize = 5;
+
+#[test]
+fn test() {
+    let dispatcher = Dispatcher::new();
+    let main_thread = thread::current().id();
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|i| {
+            let dispatcher_t = dispatcher.clone();
+            thread::spawn(move || {
+                assert!(!dispatcher_t.is_owner_thread());
+                dispatcher_t
+                    .invoke_and_wait(move || {
+                        assert_eq!(thread::current().id(), main_thread);
+                        i * 2
+                    })
+                    .unwrap()
+            })
+        })
+        .collect();
+
+    let mut run = 0;
+    while run < NO_OF_THREADS {
+        run += dispatcher.wait_and_run().unwrap();
+    }
+    let results: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(results, (0..NO_OF_THREADS).map(|i| i * 2).sum());
+
+    // on the owning thread, the closure is executed directly
+    assert_eq!(dispatcher.invoke_and_wait(|| 7).unwrap(), 7);
+    assert_eq!(
+        dispatcher
+            .wait_and_run_or_timeout(Duration::from_millis(10))
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_abandoned() {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let dispatcher = Dispatcher::new();
+        sender.send(dispatcher.clone()).unwrap();
+        // the closure panics on this thread, so the invoking thread is abandoned
+        while dispatcher.wait_and_run_or_timeout(Duration::ZERO).unwrap() == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+    });
+    let dispatcher = receiver.recv().unwrap();
+    let result = dispatcher.invoke_and_wait(|| panic!("dropped"));
+    assert!(matches!(result, Err(DispatchError::Abandoned)));
+    assert!(handle.join().is_err());
+}
+
+#[test]
+fn test_not_owner() {
+    let dispatcher = Dispatcher::new();
+    let dispatcher_t = dispatcher.clone();
+    // panics right away rather than after something was invoked
+    let result = thread::spawn(move || dispatcher_t.wait_and_run()).join();
+    assert!(result.is_err());
+}
@@ -0,0 +1,34 @@
+use cond_sync::DoubleBuffer;
+use std::{thread, time::Duration};
+const NO_OF_FRAMES: usize = 50;
+
+#[test]
+fn test() {
+    let (mut producer, mut reader) = DoubleBuffer::new(Vec::<usize>::new(), Vec::new());
+
+    let consumer = thread::spawn(move || {
+        let mut last = 0;
+        loop {
+            let frame = reader.wait_fresh().unwrap();
+            // a frame is always complete, and frames are never seen twice or out of order
+            assert_eq!(frame.len(), frame[0]);
+            assert!(frame[0] > last);
+            last = frame[0];
+            if last == NO_OF_FRAMES {
+                break;
+            }
+        }
+        assert!(reader
+            .wait_fresh_or_timeout(Duration::from_millis(10))
+            .unwrap()
+            .is_none());
+    });
+
+    for n in 1..=NO_OF_FRAMES {
+        let back = producer.back_mut();
+        back.clear();
+        back.extend(std::iter::repeat(n).take(n));
+        producer.publish().unwrap();
+    }
+    consumer.join().unwrap();
+}
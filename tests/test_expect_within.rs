@@ -0,0 +1,29 @@
+#![cfg(feature = "testing")]
+use cond_sync::{CondSync, Other};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(5));
+        cond_sync_t
+            .modify_and_notify(|v| *v = 3, Other::All)
+            .unwrap();
+    });
+    cond_sync.expect_within(Duration::from_secs(10), |v| *v == 3);
+
+    let message = panic::catch_unwind(AssertUnwindSafe(|| {
+        cond_sync.expect_within(Duration::from_millis(10), |v| *v == 4);
+    }))
+    .unwrap_err()
+    .downcast::<String>()
+    .unwrap();
+    assert!(message.starts_with("expect_within at tests/test_expect_within.rs:"));
+    assert!(message.ends_with("state: 3"));
+}
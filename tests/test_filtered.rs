@@ -0,0 +1,62 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::Duration,
+};
+const NO_OF_BATCHES: usize = 4;
+const BATCH_SIZE: usize = 3;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new((Vec::<usize>::new(), false));
+    let cond_sync_t = cond_sync.clone();
+    let started = Arc::new(Barrier::new(2));
+    let started_t = Arc::clone(&started);
+    let consumer = thread::spawn(move || {
+        let mut batches = Vec::new();
+        let filtered = cond_sync_t
+            .filtered(|(batch, _)| batch.len() == BATCH_SIZE)
+            .until(|(batch, shutdown)| *shutdown && batch.is_empty());
+        started_t.wait();
+        for (batch, _) in filtered {
+            cond_sync_t
+                .modify_and_notify(|(batch, _)| batch.clear(), Other::All)
+                .unwrap();
+            batches.push(batch);
+        }
+        batches
+    });
+    started.wait();
+
+    for i in 0..NO_OF_BATCHES * BATCH_SIZE {
+        cond_sync
+            .wait_until(|(batch, _)| batch.len() < BATCH_SIZE)
+            .unwrap();
+        cond_sync
+            .modify_and_notify(|(batch, _)| batch.push(i), Other::All)
+            .unwrap();
+    }
+    cond_sync.wait_until(|(batch, _)| batch.is_empty()).unwrap();
+    cond_sync
+        .modify_and_notify(|(_, shutdown)| *shutdown = true, Other::All)
+        .unwrap();
+
+    let batches = consumer.join().unwrap();
+    assert_eq!(batches.len(), NO_OF_BATCHES);
+    assert_eq!(batches[1], vec![3, 4, 5]);
+}
+
+#[test]
+fn test_timeout() {
+    let cond_sync = CondSync::new(5_usize);
+    // the current state is not new, so it is not yielded
+    assert_eq!(
+        cond_sync
+            .filtered(|v| *v == 5)
+            .timeout(Duration::from_millis(20))
+            .next(),
+        None
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "interceptors")]
+use cond_sync::{
+    multi_modify, set_global_interceptor, BarrierTopology, Broadcast, CondBarrier, CondSync,
+    CoordScope, Coordinator, InitOnce, Interceptor, Operation, OperationKind, Other,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<(OperationKind, &'static str)>>);
+impl Interceptor for Recorder {
+    fn before(&self, operation: &Operation) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((operation.kind(), operation.method()));
+    }
+}
+
+#[test]
+fn test_helpers() {
+    let recorder = Arc::new(Recorder::default());
+    let local = Arc::new(Recorder::default());
+    let a = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&local) as Arc<dyn Interceptor>)
+        .build();
+    let b = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&local) as Arc<dyn Interceptor>)
+        .build();
+    set_global_interceptor(Some(Arc::clone(&recorder) as Arc<dyn Interceptor>));
+
+    let init_once = InitOnce::new();
+    assert_eq!(init_once.get_or_init_blocking(|| 42).unwrap(), 42);
+    let broadcast = Broadcast::<u8>::new(1);
+    drop(broadcast.subscribe().unwrap());
+    multi_modify((&a, &b), |a, b| *a = *b + 1, Other::All).unwrap();
+    let coordinator = Coordinator::<()>::new();
+    coordinator.wait_until_all_ok().unwrap();
+    coordinator.wait_until_all_done().unwrap();
+    CoordScope::run(|scope| scope.wait_all(Duration::from_secs(1))).unwrap();
+    assert!(CondBarrier::new(1, BarrierTopology::Flat).wait().unwrap());
+
+    set_global_interceptor(None);
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            (OperationKind::Wait, "get_or_init_blocking"),
+            (OperationKind::Modify, "modify_and_notify"),
+            (OperationKind::Modify, "subscribe"),
+            (OperationKind::Modify, "unsubscribe"),
+            // once, although two instances are involved
+            (OperationKind::Modify, "multi_modify"),
+            (OperationKind::Wait, "wait_until_all_ok"),
+            (OperationKind::Wait, "wait_until_all_done"),
+            (OperationKind::Wait, "wait_all"),
+            (OperationKind::Wait, "wait"),
+            (OperationKind::Modify, "modify_and_notify"),
+        ]
+    );
+    // each instance reports to its own interceptor
+    assert_eq!(
+        *local.0.lock().unwrap(),
+        vec![
+            (OperationKind::Modify, "multi_modify"),
+            (OperationKind::Modify, "multi_modify"),
+        ]
+    );
+}
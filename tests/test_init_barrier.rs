@@ -0,0 +1,32 @@
+use cond_sync::{init_barrier, CoordinationError};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let (barrier, workers) = init_barrier(5);
+    let handles: Vec<_> = workers
+        .into_iter()
+        .map(|worker| thread::spawn(move || worker.ready()))
+        .collect();
+    barrier.wait_all().unwrap();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // missing workers are reported
+    let (barrier, mut workers) = init_barrier(3);
+    let lazy = workers.pop().unwrap();
+    for worker in workers {
+        worker.ready();
+    }
+    match barrier.wait_all_or_timeout(Duration::from_millis(10)) {
+        Err(CoordinationError::Timeout { outstanding }) => {
+            assert_eq!(outstanding, vec![lazy.id().clone()]);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    lazy.ready();
+    barrier
+        .wait_all_or_timeout(Duration::from_millis(10))
+        .unwrap();
+}
@@ -0,0 +1,80 @@
+use cond_sync::InitOnce;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+const NO_OF_THREADS: usize = 10;
+
+#[test]
+fn test() {
+    let init_once = InitOnce::new();
+    let initializations = AtomicUsize::new(0);
+    assert_eq!(init_once.get().unwrap(), None);
+
+    thread::scope(|scope| {
+        for _ in 0..NO_OF_THREADS {
+            scope.spawn(|| {
+                let value = init_once
+                    .get_or_init_blocking(|| {
+                        initializations.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(50));
+                        42
+                    })
+                    .unwrap();
+                assert_eq!(value, 42);
+            });
+        }
+    });
+    assert_eq!(initializations.load(Ordering::SeqCst), 1);
+    assert_eq!(init_once.get().unwrap(), Some(42));
+}
+
+#[test]
+fn test_timeout() {
+    let init_once = InitOnce::new();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            init_once
+                .get_or_init_blocking(|| {
+                    thread::sleep(Duration::from_millis(200));
+                    1
+                })
+                .unwrap()
+        });
+        thread::sleep(Duration::from_millis(50)); // let the initialization start
+        assert_eq!(
+            init_once
+                .get_or_init_blocking_timeout(|| 2, Duration::from_millis(10))
+                .unwrap(),
+            None
+        );
+    });
+    assert_eq!(
+        init_once
+            .get_or_init_blocking_timeout(|| 2, Duration::from_millis(10))
+            .unwrap(),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_initializer_panics() {
+    let init_once = InitOnce::new();
+    thread::scope(|scope| {
+        let failing = scope.spawn(|| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                init_once.get_or_init_blocking(|| {
+                    thread::sleep(Duration::from_millis(50));
+                    panic!("initialization failed");
+                })
+            }))
+        });
+        thread::sleep(Duration::from_millis(10)); // let the first initialization start
+                                                  // the waiting thread takes over
+        assert_eq!(init_once.get_or_init_blocking(|| 7).unwrap(), 7);
+        assert!(failing.join().unwrap().is_err());
+    });
+    assert_eq!(init_once.get().unwrap(), Some(7));
+}
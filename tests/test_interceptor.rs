@@ -0,0 +1,68 @@
+#![cfg(feature = "interceptors")]
+use cond_sync::{CondSync, Interceptor, Operation, OperationKind, Other, Outcome};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[derive(Default)]
+struct Recorder(Mutex<Vec<String>>);
+impl Interceptor for Recorder {
+    fn before(&self, operation: &Operation) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("before {}", operation.method()));
+    }
+    fn after(&self, operation: &Operation, outcome: &Outcome) {
+        assert!(!outcome.is_poisoned());
+        let kind = match operation.kind() {
+            OperationKind::Wait => "wait",
+            OperationKind::Modify => "modify",
+        };
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("after {kind} {:?}", outcome.reason()));
+    }
+}
+
+#[test]
+fn test() {
+    let recorder = Arc::new(Recorder::default());
+    let cond_sync = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&recorder) as Arc<dyn Interceptor>)
+        .build();
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    cond_sync.wait_until(|v| *v == 1).unwrap();
+    cond_sync
+        .wait_until_or_timeout(|v| *v == 2, Duration::from_millis(1))
+        .unwrap();
+    #[cfg(feature = "generations")]
+    assert_eq!(
+        cond_sync
+            .filtered(|v| *v == 2)
+            .timeout(Duration::from_millis(1))
+            .next(),
+        None
+    );
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            "before modify_and_notify",
+            "after modify None",
+            "before wait_until",
+            "after wait Some(Condition)",
+            "before wait_until_or_timeout",
+            "after wait Some(Timeout)",
+            #[cfg(feature = "generations")]
+            "before next",
+            #[cfg(feature = "generations")]
+            "after wait Some(Timeout)",
+        ]
+    );
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "snapshots")]
+use cond_sync::{CondSync, Other};
+use std::{sync::Arc, thread};
+const NO_OF_MODIFICATIONS: usize = 1000;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::builder(vec![0_usize; 100]).snapshots().build();
+    let first = cond_sync.latest();
+
+    let cond_sync_t = cond_sync.clone();
+    let reader = thread::spawn(move || {
+        let mut last = 0;
+        while last < NO_OF_MODIFICATIONS {
+            let snapshot = cond_sync_t.latest();
+            // snapshots are consistent, and never go back in time
+            assert!(snapshot.iter().all(|v| *v == snapshot[0]));
+            assert!(snapshot[0] >= last);
+            last = snapshot[0];
+        }
+    });
+
+    for _ in 0..NO_OF_MODIFICATIONS {
+        cond_sync
+            .modify_and_notify(|v| v.iter_mut().for_each(|e| *e += 1), Other::All)
+            .unwrap();
+    }
+    reader.join().unwrap();
+
+    // older snapshots are not affected by modifications
+    assert_eq!(first[0], 0);
+    let latest = cond_sync.latest();
+    assert!(Arc::ptr_eq(&latest, &cond_sync.latest()));
+}
+
+#[test]
+fn test_without_snapshots() {
+    let cond_sync = CondSync::new(String::from("abc"));
+    assert_eq!(*cond_sync.latest(), "abc");
+}
@@ -0,0 +1,34 @@
+use cond_sync::Limiter;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+const MAX_IN_FLIGHT: usize = 3;
+const NO_OF_THREADS: usize = 10;
+
+#[test]
+fn test() {
+    let limiter = Limiter::new(MAX_IN_FLIGHT);
+    let max_seen = Arc::new(AtomicUsize::new(0));
+
+    for i in 0..NO_OF_THREADS {
+        let guard = limiter.enter().unwrap(); // <- blocks while MAX_IN_FLIGHT threads are active
+        let limiter_t = limiter.clone();
+        let max_seen_t = Arc::clone(&max_seen);
+        thread::spawn(move || {
+            println!("Thread {i}: working ...");
+            max_seen_t.fetch_max(limiter_t.in_flight(), Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            drop(guard);
+        });
+    }
+    limiter.wait_idle().unwrap();
+
+    assert_eq!(limiter.in_flight(), 0);
+    assert!(max_seen.load(Ordering::SeqCst) <= MAX_IN_FLIGHT);
+    println!("Main: all threads are done");
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "lock_order")]
+use cond_sync::{lock_order_inversions, multi_modify, CondSync, Other};
+#[cfg(feature = "interceptors")]
+use cond_sync::{set_global_interceptor, Interceptor, LockOrderInversion};
+use std::thread;
+#[cfg(feature = "interceptors")]
+use std::{
+    sync::{Arc, Mutex},
+    thread::ThreadId,
+};
+
+// Records the threads that inverted a lock order.
+#[cfg(feature = "interceptors")]
+#[derive(Default)]
+struct Recorder(Mutex<Vec<ThreadId>>);
+#[cfg(feature = "interceptors")]
+impl Interceptor for Recorder {
+    fn lock_order_inversion(&self, inversion: &LockOrderInversion) {
+        self.0.lock().unwrap().push(inversion.inverted_by());
+    }
+}
+
+#[test]
+fn test() {
+    let a = CondSync::new(0);
+    let b = CondSync::new(0);
+
+    // multi_modify always locks in the same order
+    thread::scope(|scope| {
+        scope.spawn(|| multi_modify((&a, &b), |_, _| {}, Other::All).unwrap());
+        scope.spawn(|| multi_modify((&b, &a), |_, _| {}, Other::All).unwrap());
+    });
+    assert!(lock_order_inversions().is_empty());
+
+    // nested modifications in opposite orders, one after the other, so they can't deadlock
+    let a = CondSync::new(0);
+    let b = CondSync::new(0);
+    let nested = |outer: &CondSync<i32>, inner: &CondSync<i32>| {
+        outer
+            .modify_and_notify(
+                |_| inner.modify_and_notify(|v| *v += 1, Other::All).unwrap(),
+                Other::All,
+            )
+            .unwrap();
+    };
+    let first = thread::scope(|scope| scope.spawn(|| nested(&a, &b)).thread().id());
+    assert!(lock_order_inversions().is_empty());
+    #[cfg(feature = "interceptors")]
+    let recorder = {
+        let recorder = Arc::new(Recorder::default());
+        set_global_interceptor(Some(Arc::clone(&recorder) as Arc<dyn Interceptor>));
+        recorder
+    };
+    let second = thread::scope(|scope| scope.spawn(|| nested(&b, &a)).thread().id());
+    #[cfg(feature = "interceptors")]
+    {
+        set_global_interceptor(None);
+        assert_eq!(*recorder.0.lock().unwrap(), vec![second]);
+    }
+
+    let inversions = lock_order_inversions();
+    assert_eq!(inversions.len(), 1);
+    assert_eq!(inversions[0].established_by(), first);
+    assert_eq!(inversions[0].inverted_by(), second);
+    assert!(inversions[0]
+        .to_string()
+        .starts_with("lock-order inversion: instances "));
+
+    // the same inversion is reported only once
+    thread::scope(|scope| {
+        scope.spawn(|| nested(&b, &a));
+    });
+    assert_eq!(lock_order_inversions().len(), 1);
+}
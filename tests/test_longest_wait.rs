@@ -0,0 +1,39 @@
+#![cfg(all(feature = "stats", feature = "diagnostics"))]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(false);
+    assert!(cond_sync.contention_stats().longest_wait().is_none());
+    assert!(cond_sync.longest_waiting().is_none());
+
+    cond_sync
+        .wait_until_or_timeout(|_| false, Duration::from_millis(1))
+        .unwrap();
+    let line = line!() + 2;
+    cond_sync
+        .wait_until_or_timeout(|_| false, Duration::from_millis(20))
+        .unwrap();
+    cond_sync
+        .wait_until_or_timeout(|_| false, Duration::from_millis(2))
+        .unwrap();
+    let (longest, location) = cond_sync.contention_stats().longest_wait().unwrap();
+    assert!(longest >= Duration::from_millis(20));
+    assert_eq!((location.file(), location.line()), (file!(), line));
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || cond_sync_t.wait_until(|done| *done).unwrap());
+    while cond_sync.longest_waiting().is_none() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(cond_sync.longest_waiting().unwrap().method(), "wait_until");
+    cond_sync
+        .modify_and_notify(|done| *done = true, Other::All)
+        .unwrap();
+    handle.join().unwrap();
+    assert!(cond_sync.longest_waiting().is_none());
+
+    cond_sync.reset_contention_stats();
+    assert!(cond_sync.contention_stats().longest_wait().is_none());
+}
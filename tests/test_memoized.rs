@@ -0,0 +1,57 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+const NO_OF_THREADS: usize = 5;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let evaluations = Arc::new(AtomicUsize::new(0));
+    let evaluations_c = Arc::clone(&evaluations);
+    let condition = Arc::new(cond_sync.memoized(move |v| {
+        evaluations_c.fetch_add(1, Ordering::SeqCst);
+        *v == 3
+    }));
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let cond_sync_t = cond_sync.clone();
+            let condition_t = Arc::clone(&condition);
+            thread::spawn(move || {
+                assert!(cond_sync_t
+                    .wait_until_memoized_or_timeout(&condition_t, Duration::from_secs(10))
+                    .unwrap()
+                    .is_condition());
+            })
+        })
+        .collect();
+
+    for _ in 0..3 {
+        thread::sleep(Duration::from_millis(20));
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // at most one evaluation per modification (plus the initial one)
+    assert!(evaluations.load(Ordering::SeqCst) <= 4);
+}
+
+#[test]
+#[should_panic(expected = "another instance")]
+fn test_wrong_instance() {
+    let cond_sync_1 = CondSync::new(0_usize);
+    let cond_sync_2 = CondSync::new(0_usize);
+    let condition = cond_sync_1.memoized(|v| *v == 0);
+    cond_sync_2.wait_until_memoized(&condition).ok();
+}
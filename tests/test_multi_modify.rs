@@ -0,0 +1,63 @@
+use cond_sync::{multi_modify, CondSync, Other};
+use std::thread;
+
+#[test]
+fn test() {
+    let from = CondSync::new(1_000_i64);
+    let to = CondSync::new(0_i64);
+
+    // transfers in both directions, so the locks are requested in both orders
+    thread::scope(|scope| {
+        for i in 0..8 {
+            let (from, to) = if i % 2 == 0 {
+                (&from, &to)
+            } else {
+                (&to, &from)
+            };
+            scope.spawn(move || {
+                for _ in 0..1_000 {
+                    multi_modify(
+                        (from, to),
+                        |from, to| {
+                            *from -= 1;
+                            *to += 1;
+                        },
+                        Other::All,
+                    )
+                    .unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(from.clone_inner(), 1_000);
+    assert_eq!(to.clone_inner(), 0);
+}
+
+#[test]
+fn test_notifies_both() {
+    let counter = CondSync::new(0_u32);
+    let done = CondSync::new(false);
+
+    thread::scope(|scope| {
+        let waiter_1 = scope.spawn(|| counter.wait_until(|c| *c == 1).unwrap());
+        let waiter_2 = scope.spawn(|| done.wait_until(|d| *d).unwrap());
+        multi_modify(
+            (&counter, &done),
+            |counter, done| {
+                *counter += 1;
+                *done = true;
+            },
+            Other::All,
+        )
+        .unwrap();
+        waiter_1.join().unwrap();
+        waiter_2.join().unwrap();
+    });
+}
+
+#[test]
+#[should_panic(expected = "two different instances")]
+fn test_same_instance() {
+    let cond_sync = CondSync::new(0);
+    multi_modify((&cond_sync, &cond_sync.clone()), |_, _| {}, Other::All).unwrap();
+}
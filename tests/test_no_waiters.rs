@@ -0,0 +1,54 @@
+use cond_sync::{CondSync, Other};
+use std::thread;
+
+#[test]
+fn test() {
+    // modifications without waiters do not disturb a later waiter
+    let cond_sync = CondSync::new(0_usize);
+    for _ in 0..1000 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::One)
+            .unwrap();
+    }
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t.wait_until(|v| *v == 2000).unwrap();
+    });
+    for _ in 0..1000 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    }
+    handle.join().unwrap();
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_skipped_notifications() {
+    let cond_sync = CondSync::new(0_usize);
+    for _ in 0..100 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::One)
+            .unwrap();
+    }
+    let stats = cond_sync.contention_stats();
+    assert_eq!(stats.notifications(), 100);
+    assert_eq!(stats.skipped_notifications(), 100);
+
+    // with a waiting thread, the condition variable is signalled
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t.wait_until(|v| *v > 100).unwrap();
+    });
+    while cond_sync.contention_stats().waiters() == 0 {
+        thread::yield_now();
+    }
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    handle.join().unwrap();
+    let stats = cond_sync.contention_stats();
+    assert_eq!(stats.notifications(), 101);
+    assert_eq!(stats.skipped_notifications(), 100);
+}
@@ -0,0 +1,27 @@
+use cond_sync::{CondSync, Other};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(());
+    let start = Instant::now();
+    let scheduled = cond_sync.notify_after(Duration::from_millis(20), Other::All);
+    assert!(scheduled.is_pending());
+
+    let reason = cond_sync.wait_timeout(Duration::from_secs(10)).unwrap();
+    assert!(reason.is_notification());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert!(!scheduled.is_pending());
+    assert!(!scheduled.cancel());
+}
+
+#[test]
+fn test_cancel() {
+    let cond_sync = CondSync::new(());
+    let scheduled = cond_sync.notify_after(Duration::from_millis(20), Other::All);
+    assert!(scheduled.cancel());
+    assert!(!scheduled.is_pending());
+
+    let reason = cond_sync.wait_timeout(Duration::from_millis(100)).unwrap();
+    assert!(reason.is_timeout());
+}
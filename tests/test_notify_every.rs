@@ -0,0 +1,36 @@
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    // an "external resource" that is not managed by the CondSync
+    let external = Arc::new(AtomicUsize::new(0));
+    let cond_sync = CondSync::new(());
+
+    let ticker = cond_sync.notify_every(Duration::from_millis(5), Other::All);
+    let external_t = Arc::clone(&external);
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        external_t.store(1, Ordering::SeqCst);
+    });
+    // the periodic notifications let the condition be re-evaluated
+    let reason = cond_sync
+        .wait_until_or_timeout(
+            |()| external.load(Ordering::SeqCst) == 1,
+            Duration::from_secs(10),
+        )
+        .unwrap();
+    assert!(reason.is_condition());
+
+    drop(ticker);
+    // wait for a tick that might still be in flight
+    std::thread::sleep(Duration::from_millis(10));
+    let reason = cond_sync.wait_timeout(Duration::from_millis(50)).unwrap();
+    assert!(reason.is_timeout());
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "notifications")]
+use cond_sync::CondSync;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+const NO_OF_THREADS: usize = 4;
+const INTERVAL: Duration = Duration::from_millis(40);
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(false);
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let cond_sync_t = cond_sync.clone();
+            thread::spawn(move || {
+                cond_sync_t
+                    .wait_until_or_timeout(|v| *v, Duration::from_secs(10))
+                    .unwrap();
+                Instant::now()
+            })
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(50)); // let the threads start waiting
+    let start = Instant::now();
+    cond_sync
+        .modify_and_notify_staggered(|v| *v = true, INTERVAL)
+        .unwrap();
+
+    let mut delays: Vec<Duration> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap().duration_since(start))
+        .collect();
+    delays.sort();
+    println!("delays: {delays:?}");
+    assert!(delays[NO_OF_THREADS - 1] >= INTERVAL * (NO_OF_THREADS as u32 - 1));
+}
+
+#[test]
+fn test_fn_once() {
+    let cond_sync = CondSync::new(Vec::new());
+    let batch = vec![1, 2, 3];
+    // the modification can consume captured values
+    cond_sync
+        .modify_and_notify_staggered(move |v| *v = batch, INTERVAL)
+        .unwrap();
+    assert_eq!(cond_sync.clone_inner(), vec![1, 2, 3]);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "observers")]
+use cond_sync::{CondSync, Other};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let log_m = Arc::clone(&log);
+    let on_modify = cond_sync.on_modify(move |event| {
+        log_m
+            .lock()
+            .unwrap()
+            .push(format!("modify {}", event.generation()));
+    });
+    let log_n = Arc::clone(&log);
+    let on_notify = cond_sync.on_notify(move |event| {
+        log_n
+            .lock()
+            .unwrap()
+            .push(format!("notify {:?}", event.other().unwrap()));
+    });
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    drop(on_notify);
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    drop(on_modify);
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["modify 1", "notify One", "modify 2"]
+    );
+}
+
+#[test]
+fn test_detach() {
+    let cond_sync = CondSync::new(0_usize);
+    let counter = Arc::new(Mutex::new(0));
+    let counter_c = Arc::clone(&counter);
+    cond_sync
+        .on_modify(move |_| *counter_c.lock().unwrap() += 1)
+        .detach();
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    assert_eq!(*counter.lock().unwrap(), 2);
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "async")]
+use cond_sync::{CondSync, Other};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread,
+};
+
+struct CountingWaker {
+    wakeups: AtomicUsize,
+    thread: thread::Thread,
+}
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wakeups.fetch_add(1, Ordering::SeqCst);
+        self.thread.unpark();
+    }
+}
+
+struct WaitForThree(CondSync<usize>);
+impl Future for WaitForThree {
+    type Output = usize;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        self.0.poll_until(cx, |v| *v >= 3).map(|result| {
+            result.unwrap();
+            self.0.clone_inner()
+        })
+    }
+}
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let counting_waker = Arc::new(CountingWaker {
+        wakeups: AtomicUsize::new(0),
+        thread: thread::current(),
+    });
+    let waker = Waker::from(Arc::clone(&counting_waker));
+    let mut cx = Context::from_waker(&waker);
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        for _ in 0..3 {
+            cond_sync_t
+                .modify_and_notify(|v| *v += 1, Other::One)
+                .unwrap();
+        }
+    });
+
+    // a minimal executor: poll, and park until woken
+    let mut future = WaitForThree(cond_sync.clone());
+    let result = loop {
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(result) => break result,
+            Poll::Pending => thread::park(),
+        }
+    };
+    handle.join().unwrap();
+    assert_eq!(result, 3);
+    assert!(counting_waker.wakeups.load(Ordering::SeqCst) <= 3);
+
+    // registering the same waker twice has only one effect
+    cond_sync.register_waker(&waker);
+    cond_sync.register_waker(&waker);
+    let before = counting_waker.wakeups.load(Ordering::SeqCst);
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    assert_eq!(counting_waker.wakeups.load(Ordering::SeqCst), before + 1);
+}
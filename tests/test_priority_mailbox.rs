@@ -0,0 +1,49 @@
+use cond_sync::PriorityMailbox;
+use std::{cmp::Ordering, thread, time::Duration};
+
+// only the priority counts for the order
+#[derive(Debug)]
+struct Message(u8, &'static str);
+impl Ord for Message {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+impl PartialOrd for Message {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Eq for Message {}
+
+#[test]
+fn test() {
+    let mailbox = PriorityMailbox::new();
+    assert!(mailbox.try_recv().unwrap().is_none());
+    assert!(mailbox
+        .wait_recv_timeout(Duration::from_millis(10))
+        .unwrap()
+        .is_none());
+
+    // equal priorities are received in the order of sending
+    for (priority, text) in [(1, "a"), (3, "b"), (2, "c"), (3, "d"), (1, "e")] {
+        mailbox.send(Message(priority, text)).unwrap();
+    }
+    assert_eq!(mailbox.len().unwrap(), 5);
+    let received: Vec<_> = (0..5).map(|_| mailbox.wait_recv().unwrap().1).collect();
+    assert_eq!(received, vec!["b", "d", "c", "a", "e"]);
+    assert!(mailbox.is_empty().unwrap());
+
+    // a blocked receiver is woken by a sender
+    thread::scope(|scope| {
+        let receiver = scope.spawn(|| mailbox.wait_recv().unwrap());
+        thread::sleep(Duration::from_millis(20));
+        mailbox.send(Message(0, "z")).unwrap();
+        assert_eq!(receiver.join().unwrap().1, "z");
+    });
+}
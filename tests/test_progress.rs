@@ -0,0 +1,39 @@
+use cond_sync::Progress;
+use std::{thread, time::Duration};
+const NO_OF_THREADS: u64 = 4;
+const UNITS_PER_THREAD: u64 = 25;
+
+#[test]
+fn test() {
+    let progress = Progress::new(NO_OF_THREADS * UNITS_PER_THREAD);
+    assert!(progress
+        .wait_until_progress_or_timeout(0.1, Duration::from_millis(10))
+        .unwrap()
+        .is_none());
+
+    let progress_t = progress.clone();
+    let observer = thread::spawn(move || {
+        let mut last = 0;
+        for (count, snapshot) in progress_t.updates().enumerate() {
+            assert!(snapshot.done() > last || (count == 0 && snapshot.done() == 0));
+            last = snapshot.done();
+        }
+        last
+    });
+
+    for _ in 0..NO_OF_THREADS {
+        let progress_t = progress.clone();
+        thread::spawn(move || {
+            for _ in 0..UNITS_PER_THREAD {
+                thread::sleep(Duration::from_micros(100));
+                progress_t.report(1).unwrap();
+            }
+        });
+    }
+    let half = progress.wait_until_progress(0.5).unwrap();
+    assert!(half.fraction() >= 0.5);
+    let all = progress.wait_until_progress(1.0).unwrap();
+    assert!(all.is_complete());
+    assert!(all.rate() > 0.0);
+    assert_eq!(observer.join().unwrap(), NO_OF_THREADS * UNITS_PER_THREAD);
+}
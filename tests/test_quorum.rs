@@ -0,0 +1,35 @@
+use cond_sync::Quorum;
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let quorum = Quorum::new(["a", "b", "c", "d", "e"]);
+    assert_eq!(quorum.majority().unwrap(), 3);
+    assert_eq!(quorum.wait_for(1, Duration::from_millis(10)).unwrap(), None);
+
+    thread::scope(|scope| {
+        let waiter = scope.spawn(|| quorum.wait_for_majority(Duration::from_secs(5)).unwrap());
+        for (reporter, vote) in [("e", true), ("b", false), ("a", true)] {
+            quorum.report(reporter, vote).unwrap();
+        }
+        let reports = waiter.join().unwrap().unwrap();
+        assert_eq!(
+            reports,
+            vec![
+                ("a".to_string(), true),
+                ("b".to_string(), false),
+                ("e".to_string(), true)
+            ]
+        );
+        assert_eq!(reports.iter().filter(|(_, vote)| *vote).count(), 2);
+    });
+
+    // a later report replaces the value, but does not count twice
+    quorum.report("b", true).unwrap();
+    assert_eq!(quorum.reported().unwrap(), 3);
+    assert_eq!(quorum.wait_for(4, Duration::from_millis(10)).unwrap(), None);
+    assert_eq!(
+        quorum.wait_for(3, Duration::ZERO).unwrap().unwrap()[1],
+        ("b".to_string(), true)
+    );
+}
@@ -0,0 +1,49 @@
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread,
+};
+
+#[test]
+fn test() {
+    let raw = Arc::new((Mutex::new(0_usize), Condvar::new()));
+    let raw_clone = Arc::clone(&raw);
+    let Err(raw) = CondSync::from_raw(raw) else {
+        panic!("from_raw must fail while the primitives are shared");
+    };
+    drop(raw_clone);
+    let cond_sync = CondSync::from_raw(raw).unwrap();
+
+    // a thread that waits directly on the condvar is notified by the CondSync
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        let mtx_guard = cond_sync_t.as_mutex().lock().unwrap();
+        let mtx_guard = cond_sync_t
+            .as_condvar()
+            .wait_while(mtx_guard, |v| *v < 1)
+            .unwrap();
+        *mtx_guard
+    });
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    assert_eq!(handle.join().unwrap(), 1);
+
+    // modifications via the raw parts are seen by the CondSync
+    {
+        let mut mtx_guard = cond_sync.as_mutex().lock().unwrap();
+        *mtx_guard += 1;
+        cond_sync.as_condvar().notify_all();
+    }
+    cond_sync.wait_until(|v| *v == 2).unwrap();
+
+    let cond_sync_clone = cond_sync.clone();
+    let Err(cond_sync) = cond_sync.into_raw() else {
+        panic!("into_raw must fail while clones exist");
+    };
+    drop(cond_sync_clone);
+    let Ok(raw) = cond_sync.into_raw() else {
+        panic!("into_raw must succeed for the last reference");
+    };
+    assert_eq!(*raw.0.lock().unwrap(), 2);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "optimistic_reads")]
+use cond_sync::{CondSync, Other};
+use std::thread;
+const NO_OF_READERS: usize = 4;
+const NO_OF_MODIFICATIONS: u64 = 10_000;
+
+#[test]
+fn test() {
+    // the two halves are always modified together, so readers must never see them differ
+    let cond_sync = CondSync::builder((0_u64, 0_u64, false))
+        .optimistic_reads()
+        .build();
+
+    let readers: Vec<_> = (0..NO_OF_READERS)
+        .map(|_| {
+            let cond_sync_t = cond_sync.clone();
+            thread::spawn(move || loop {
+                let (a, b, done) = cond_sync_t.read_optimistic();
+                assert_eq!(a, b);
+                if done {
+                    return a;
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..NO_OF_MODIFICATIONS {
+        cond_sync
+            .modify_and_notify(|(a, b, _)| (*a, *b) = (*a + 1, *b + 1), Other::All)
+            .unwrap();
+    }
+    cond_sync
+        .modify_and_notify(|(_, _, done)| *done = true, Other::All)
+        .unwrap();
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), NO_OF_MODIFICATIONS);
+    }
+}
+
+#[test]
+fn test_without_mirror() {
+    let cond_sync = CondSync::new([1.5_f64, -2.0]);
+    assert_eq!(cond_sync.read_optimistic(), [1.5, -2.0]);
+}
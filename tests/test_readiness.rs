@@ -0,0 +1,33 @@
+#![cfg(all(unix, feature = "readiness"))]
+use cond_sync::{CondSync, Other};
+use std::{os::unix::io::AsRawFd, thread};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let handle = cond_sync.readiness_handle().unwrap();
+    assert!(handle.as_raw_fd() >= 0);
+    assert!(!handle.clear().unwrap());
+
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        cond_sync_t
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    })
+    .join()
+    .unwrap();
+    // all handles share the same socket
+    assert!(cond_sync.readiness_handle().unwrap().clear().unwrap());
+    assert!(!handle.clear().unwrap());
+    assert_eq!(cond_sync.clone_inner(), 1);
+
+    // many notifications don't block the notifying thread
+    for _ in 0..100_000 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::One)
+            .unwrap();
+    }
+    assert!(handle.clear().unwrap());
+    assert!(!handle.clear().unwrap());
+}
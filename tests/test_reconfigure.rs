@@ -0,0 +1,76 @@
+#![cfg(all(feature = "debounce", feature = "jitter"))]
+use cond_sync::{CondSync, Other, Reconfiguration, ReconfigureError};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn test_wakeup_jitter() {
+    let cond_sync = CondSync::builder(false)
+        .wakeup_jitter(Duration::from_secs(60))
+        .build();
+    cond_sync
+        .reconfigure(Reconfiguration::new().wakeup_jitter(Duration::ZERO))
+        .unwrap();
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t.wait_until(|v| *v).unwrap();
+        Instant::now()
+    });
+    thread::sleep(Duration::from_millis(50)); // let the thread start waiting
+    let start = Instant::now();
+    cond_sync
+        .modify_and_notify(|v| *v = true, Other::All)
+        .unwrap();
+    // without the reconfiguration, the waiter could sleep for up to a minute
+    assert!(handle.join().unwrap().duration_since(start) < Duration::from_secs(30));
+}
+
+#[test]
+fn test_debounce() {
+    let cond_sync = CondSync::builder(0_usize)
+        .debounce(Duration::ZERO, Duration::ZERO)
+        .build();
+    cond_sync
+        .reconfigure(
+            Reconfiguration::new().debounce(Duration::from_millis(20), Duration::from_millis(200)),
+        )
+        .unwrap();
+    let evaluations = Arc::new(AtomicUsize::new(0));
+
+    let cond_sync_t = cond_sync.clone();
+    let evaluations_t = Arc::clone(&evaluations);
+    let handle = thread::spawn(move || {
+        cond_sync_t
+            .wait_until(|v| {
+                evaluations_t.fetch_add(1, Ordering::SeqCst);
+                *v == 1000
+            })
+            .unwrap();
+    });
+    thread::sleep(Duration::from_millis(10));
+
+    for _ in 0..1000 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    }
+    handle.join().unwrap();
+    // the widened window merged the notifications
+    assert!(evaluations.load(Ordering::SeqCst) < 10);
+}
+
+#[test]
+fn test_debounce_not_configured() {
+    let cond_sync = CondSync::new(0_usize);
+    assert_eq!(
+        cond_sync.reconfigure(Reconfiguration::new().debounce(Duration::ZERO, Duration::ZERO)),
+        Err(ReconfigureError::NotDebounced)
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "lock_order")]
+use cond_sync::{Budget, BudgetError, CondSync, CondSyncError, Other};
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0);
+
+    // a modification that waits on the same instance
+    let mut inner = None;
+    cond_sync
+        .modify_and_notify(
+            |_| inner = Some(cond_sync.wait_until(|v| *v > 0)),
+            Other::All,
+        )
+        .unwrap();
+    let error = inner.unwrap().unwrap_err();
+    assert!(matches!(error, CondSyncError::WouldDeadlock { .. }));
+    assert!(error
+        .to_string()
+        .starts_with("cond_sync: re-entrant call would deadlock"));
+
+    // a condition that reads the same instance
+    let other = CondSync::new(0);
+    let inner = Cell::new(None);
+    other
+        .wait_until(|_| {
+            inner.set(Some(other.try_clone_inner()));
+            true
+        })
+        .unwrap();
+    assert!(matches!(
+        inner.take().unwrap(),
+        Err(CondSyncError::WouldDeadlock { .. })
+    ));
+
+    // the error types of the helpers carry the error, too
+    let budget = Budget::new(Duration::from_secs(1));
+    let inner = Cell::new(None);
+    other
+        .wait_until(|_| {
+            inner.set(Some(other.wait_until_within_budget(
+                "inner",
+                |_| true,
+                &budget,
+            )));
+            true
+        })
+        .unwrap();
+    assert!(matches!(
+        inner.take().unwrap(),
+        Err(BudgetError::WouldDeadlock { .. })
+    ));
+
+    // methods that cannot return the error panic with its description
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        other.wait_until(|_| other.clone_inner() == 0).ok();
+    }));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with("cond_sync: re-entrant call would deadlock"));
+
+    // nesting different instances is fine
+    let a = CondSync::new(0);
+    let b = CondSync::new(0);
+    a.wait_until(|_| b.clone_inner() == 0).unwrap();
+}
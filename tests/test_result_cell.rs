@@ -0,0 +1,63 @@
+use cond_sync::{ResultCell, ResultCellError};
+use std::{thread, time::Duration};
+const NO_OF_THREADS: usize = 5;
+
+#[test]
+fn test() {
+    let cell = ResultCell::<usize, String>::new();
+    assert_eq!(
+        cell.wait_timeout(Duration::from_millis(10)),
+        Err(ResultCellError::Timeout)
+    );
+    assert_eq!(cell.result(), Ok(None));
+
+    // the value is set, but an error takes precedence
+    assert!(cell.complete(7));
+    assert!(!cell.complete(8));
+    assert!(cell.fail("first".to_string()));
+    assert!(!cell.fail("second".to_string()));
+    assert!(!cell.complete(9));
+    assert_eq!(
+        cell.wait(),
+        Err(ResultCellError::Failed("first".to_string()))
+    );
+}
+
+#[test]
+fn test_workers() {
+    let cell = ResultCell::<usize, usize>::new();
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|i| {
+            let cell_t = cell.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(5));
+                if i == NO_OF_THREADS - 1 {
+                    cell_t.complete(i);
+                }
+            })
+        })
+        .collect();
+    assert_eq!(cell.wait(), Ok(NO_OF_THREADS - 1));
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_poisoned() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Unclonable;
+    impl Clone for Unclonable {
+        fn clone(&self) -> Self {
+            panic!("cannot be cloned");
+        }
+    }
+
+    let cell = ResultCell::<Unclonable, ()>::new();
+    assert!(cell.complete(Unclonable));
+    let cell_t = cell.clone();
+    // cloning the value panics while the lock is held
+    thread::spawn(move || cell_t.result()).join().unwrap_err();
+    assert_eq!(cell.result(), Err(ResultCellError::Poisoned));
+    assert_eq!(cell.wait(), Err(ResultCellError::Poisoned));
+}
@@ -0,0 +1,33 @@
+use cond_sync::{CondSync, Other, RetryError};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let state = CondSync::new(0_usize);
+
+    // every poke triggers an asynchronous increment, the third one satisfies the condition
+    let attempt = state
+        .retry_until(
+            5,
+            Duration::from_millis(100),
+            |_attempt| {
+                let state_t = state.clone();
+                thread::spawn(move || {
+                    state_t.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+                });
+            },
+            |v| *v == 3,
+        )
+        .unwrap();
+    assert_eq!(attempt, 3);
+
+    let mut pokes = 0;
+    match state.retry_until(3, Duration::from_millis(5), |_| pokes += 1, |v| *v == 10) {
+        Err(RetryError::Exhausted { attempts, elapsed }) => {
+            assert_eq!(attempts, 3);
+            assert!(elapsed >= Duration::from_millis(15));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(pokes, 3);
+}
@@ -0,0 +1,34 @@
+use cond_sync::{scatter_gather, GatherError};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    // outputs keep the order of the inputs, even if they finish in different order
+    let outputs = scatter_gather((0..20_u64).collect(), 4, Duration::from_secs(10), |i| {
+        thread::sleep(Duration::from_millis(20 - i));
+        i * 2
+    })
+    .unwrap();
+    assert_eq!(outputs, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+
+    let outputs: Vec<u64> =
+        scatter_gather(Vec::<u64>::new(), 4, Duration::from_secs(10), |i| i).unwrap();
+    assert!(outputs.is_empty());
+}
+
+#[test]
+fn test_timeout() {
+    let result = scatter_gather(vec![0, 1000], 2, Duration::from_millis(100), |ms| {
+        thread::sleep(Duration::from_millis(ms));
+    });
+    assert_eq!(result, Err(GatherError::Timeout { finished: 1 }));
+}
+
+#[test]
+fn test_panic() {
+    let result = scatter_gather(vec![1, 2, 3], 1, Duration::from_secs(10), |i| {
+        assert!(i != 2, "no twos");
+        i
+    });
+    assert_eq!(result, Err(GatherError::Panicked));
+}
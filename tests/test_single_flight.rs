@@ -0,0 +1,64 @@
+use cond_sync::SingleFlight;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Barrier,
+    },
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let single_flight = SingleFlight::<u32, String>::new();
+    let computations = AtomicUsize::new(0);
+    let barrier = Barrier::new(8);
+
+    let leaders: usize = thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    let (value, leader) = single_flight
+                        .do_or_wait(7, || {
+                            computations.fetch_add(1, Ordering::SeqCst);
+                            thread::sleep(Duration::from_millis(50));
+                            "seven".to_string()
+                        })
+                        .unwrap();
+                    assert_eq!(value, "seven");
+                    usize::from(leader)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+    assert_eq!(leaders, 1);
+    assert_eq!(computations.load(Ordering::SeqCst), 1);
+    assert_eq!(single_flight.in_flight().unwrap(), 0);
+
+    // after the flight, the next call computes again
+    let (value, leader) = single_flight.do_or_wait(7, || "again".to_string()).unwrap();
+    assert_eq!((value.as_str(), leader), ("again", true));
+}
+
+#[test]
+fn test_leader_panics() {
+    let single_flight = SingleFlight::<u32, u32>::new();
+    thread::scope(|scope| {
+        let leader = scope.spawn(|| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                single_flight.do_or_wait(1, || {
+                    thread::sleep(Duration::from_millis(50));
+                    panic!("computation failed");
+                })
+            }))
+        });
+        thread::sleep(Duration::from_millis(10));
+        let (value, leader_role) = single_flight.do_or_wait(1, || 42).unwrap();
+        assert_eq!(value, 42);
+        assert!(leader_role);
+        assert!(leader.join().unwrap().is_err());
+    });
+}
@@ -0,0 +1,10 @@
+use cond_sync::CondSync;
+use std::mem::size_of;
+
+#[test]
+fn test() {
+    // instrumentation lives behind the pointer, with any set of features
+    assert_eq!(size_of::<CondSync<u8>>(), size_of::<usize>());
+    assert_eq!(size_of::<CondSync<[u8; 1024]>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<CondSync<u8>>>(), size_of::<usize>());
+}
@@ -0,0 +1,23 @@
+use cond_sync::{Other, StaticCondSync};
+use std::{thread, time::Duration};
+
+static COUNTER: StaticCondSync<usize> = StaticCondSync::new(0);
+
+#[test]
+fn test() {
+    let handles: Vec<_> = (0..5)
+        .map(|_| {
+            thread::spawn(|| {
+                COUNTER.modify_and_notify(|v| *v += 1, Other::All).unwrap();
+            })
+        })
+        .collect();
+    assert!(COUNTER
+        .wait_until_or_timeout(|v| *v == 5, Duration::from_secs(10))
+        .unwrap()
+        .is_condition());
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(COUNTER.try_clone_inner().unwrap(), 5);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "notifications")]
+use cond_sync::{CondSync, Other};
+use std::time::Duration;
+
+#[test]
+fn test_sticky() {
+    let cond_sync = CondSync::builder(0_usize)
+        .sticky_notifications(true)
+        .build();
+
+    // notify before anyone waits
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+
+    // the pending notification is consumed by the first waiter ...
+    assert!(cond_sync
+        .wait_timeout(Duration::from_secs(5))
+        .unwrap()
+        .is_notification());
+    // ... and several notifications are merged into one
+    assert!(cond_sync
+        .wait_timeout(Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+}
+
+#[test]
+fn test_not_sticky() {
+    let cond_sync = CondSync::new(0_usize);
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::One)
+        .unwrap();
+    // the notification is lost
+    assert!(cond_sync
+        .wait_timeout(Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+}
+
+#[test]
+fn test_counting() {
+    let cond_sync = CondSync::builder(0_usize)
+        .counting_notifications(true)
+        .build();
+
+    for _ in 0..3 {
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::One)
+            .unwrap();
+    }
+    // each notification is consumed by one waiter
+    for _ in 0..3 {
+        assert!(cond_sync
+            .wait_timeout(Duration::from_secs(5))
+            .unwrap()
+            .is_notification());
+    }
+    assert!(cond_sync
+        .wait_timeout(Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+}
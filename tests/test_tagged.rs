@@ -0,0 +1,50 @@
+#![cfg(feature = "tags")]
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new((0_usize, 0_usize));
+    let readers_woken = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let reader = scope.spawn(|| {
+            cond_sync
+                .wait_until_tagged("readers", |(readers, _)| {
+                    readers_woken.fetch_add(1, Ordering::SeqCst);
+                    *readers > 0
+                })
+                .unwrap()
+        });
+        let writer = scope.spawn(|| {
+            cond_sync
+                .wait_until_tagged("writers", |(_, writers)| *writers > 0)
+                .unwrap()
+        });
+        thread::sleep(Duration::from_millis(20));
+        let evaluations = readers_woken.load(Ordering::SeqCst);
+
+        // neither untagged nor other tags' notifications wake the reader
+        cond_sync
+            .modify_and_notify(|(_, writers)| *writers = 1, Other::All)
+            .unwrap();
+        cond_sync.notify_tag("writers", Other::All).unwrap();
+        assert!(writer.join().unwrap().is_condition());
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(readers_woken.load(Ordering::SeqCst), evaluations);
+
+        cond_sync
+            .modify_and_notify_tag(|(readers, _)| *readers = 1, "readers", Other::One)
+            .unwrap();
+        assert!(reader.join().unwrap().is_condition());
+    });
+
+    assert!(cond_sync
+        .wait_until_or_timeout_tagged("writers", |_| false, Duration::from_millis(5))
+        .unwrap()
+        .is_timeout());
+}
@@ -0,0 +1,43 @@
+use cond_sync::ThreadPool;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+const NO_OF_WORKERS: usize = 3;
+const NO_OF_JOBS: usize = 20;
+
+#[test]
+fn test() {
+    let pool = ThreadPool::new(NO_OF_WORKERS);
+    assert_eq!(pool.size(), NO_OF_WORKERS);
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    for i in 0..NO_OF_JOBS {
+        let counter_t = Arc::clone(&counter);
+        pool.execute(move || {
+            thread::sleep(Duration::from_millis(1));
+            assert!(i % 7 != 3, "job {i} fails");
+            counter_t.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+    }
+    pool.wait_idle().unwrap();
+    let panicked = (0..NO_OF_JOBS).filter(|i| i % 7 == 3).count();
+    assert_eq!(pool.panicked_jobs(), panicked);
+    assert_eq!(counter.load(Ordering::SeqCst), NO_OF_JOBS - panicked);
+
+    // shutdown drains the queue
+    for _ in 0..NO_OF_JOBS {
+        let counter_t = Arc::clone(&counter);
+        pool.execute(move || {
+            counter_t.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+    }
+    pool.shutdown();
+    assert_eq!(counter.load(Ordering::SeqCst), 2 * NO_OF_JOBS - panicked);
+}
@@ -0,0 +1,42 @@
+use cond_sync::TickBarrier;
+use std::thread;
+const NO_OF_TICKS: u64 = 20;
+
+#[test]
+fn test() {
+    let tick_barrier = TickBarrier::new();
+    let mut worker = tick_barrier.worker();
+    let tick_barrier_t = tick_barrier.clone();
+    let handle = thread::spawn(move || {
+        let mut seen = 0;
+        while worker.last_seen() < NO_OF_TICKS {
+            let tick = worker.wait_next_tick().unwrap();
+            seen += 1;
+            // ticks are seen in order; missed ones are accounted for
+            assert_eq!(tick.number(), seen + worker.total_missed());
+            // let the driver wait for us from time to time
+            tick_barrier_t.wait_tick(tick.number()).unwrap();
+        }
+        (seen, worker.total_missed())
+    });
+
+    for n in 1..=NO_OF_TICKS {
+        assert_eq!(tick_barrier.tick().unwrap(), n);
+    }
+    let (seen, missed) = handle.join().unwrap();
+    assert_eq!(seen + missed, NO_OF_TICKS);
+    assert_eq!(tick_barrier.wait_tick(3).unwrap(), NO_OF_TICKS);
+}
+
+#[test]
+fn test_missed() {
+    let tick_barrier = TickBarrier::new();
+    let mut worker = tick_barrier.worker();
+    for _ in 0..3 {
+        tick_barrier.tick().unwrap();
+    }
+    let tick = worker.wait_next_tick().unwrap();
+    assert_eq!(tick.number(), 3);
+    assert_eq!(tick.missed(), 2);
+    assert!(tick.has_missed());
+}
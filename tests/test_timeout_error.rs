@@ -0,0 +1,37 @@
+use cond_sync::{CondSync, Other, WaitError};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(vec![1, 2]);
+
+    let line = line!() + 1;
+    let error = match cond_sync.wait_until_or_timeout_error(
+        Some("third element"),
+        |v| v.len() == 3,
+        Duration::from_millis(20),
+    ) {
+        Err(WaitError::Timeout(error)) => error,
+        other => panic!("unexpected result: {other:?}"),
+    };
+    assert_eq!(error.label(), Some("third element"));
+    assert_eq!(error.location().line(), line);
+    assert_eq!(error.duration(), Duration::from_millis(20));
+    assert!(error.elapsed() >= Duration::from_millis(20));
+    assert_eq!(error.state(), "[1, 2]");
+    assert!(error
+        .to_string()
+        .starts_with("wait_until_or_timeout_error (\"third element\") at tests/"));
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        cond_sync_t
+            .wait_until_or_timeout_error(None, |v| v.len() == 3, Duration::from_secs(10))
+            .unwrap();
+    });
+    thread::sleep(Duration::from_millis(10));
+    cond_sync
+        .modify_and_notify(|v| v.push(3), Other::All)
+        .unwrap();
+    handle.join().unwrap();
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "stats")]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+
+    // waits without time limit are not counted
+    cond_sync.wait_until(|v| *v == 0).unwrap();
+    assert_eq!(cond_sync.timing_stats().satisfied(), 0);
+
+    // satisfied immediately, with (almost) all the time to spare
+    cond_sync
+        .wait_until_or_timeout(|v| *v == 0, Duration::from_secs(10))
+        .unwrap();
+
+    // satisfied after a notification
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        cond_sync_t
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    });
+    cond_sync
+        .wait_until_or_timeout(|v| *v == 1, Duration::from_secs(10))
+        .unwrap();
+    handle.join().unwrap();
+
+    // expired
+    for _ in 0..2 {
+        assert!(cond_sync
+            .wait_until_or_timeout(|_| false, Duration::from_millis(5))
+            .unwrap()
+            .is_timeout());
+    }
+
+    let timing = cond_sync.timing_stats();
+    println!("{timing:#?}");
+    assert_eq!(timing.satisfied(), 2);
+    assert_eq!(timing.expired(), 2);
+    assert!((timing.miss_ratio() - 0.5).abs() < f64::EPSILON);
+    assert!(timing.spare().max() > Duration::from_secs(9));
+    assert!(timing.spare().max() <= Duration::from_secs(10));
+    assert!(timing.overshoot().max() < Duration::from_secs(1));
+
+    cond_sync.reset_contention_stats();
+    assert_eq!(cond_sync.timing_stats().expired(), 0);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "interceptors")]
+use cond_sync::{multi_modify, CondSync, Interceptor, Operation, Other, Outcome};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Locations(Mutex<Vec<(&'static str, u32)>>);
+impl Interceptor for Locations {
+    fn after(&self, operation: &Operation, _outcome: &Outcome) {
+        let location = operation.location();
+        self.0
+            .lock()
+            .unwrap()
+            .push((location.file(), location.line()));
+    }
+}
+
+#[test]
+fn test() {
+    let locations = Arc::new(Locations::default());
+    let cond_sync = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&locations) as Arc<dyn Interceptor>)
+        .build();
+
+    let line = line!();
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    cond_sync.wait_until(|v| *v == 1).unwrap();
+
+    assert_eq!(
+        *locations.0.lock().unwrap(),
+        vec![(file!(), line + 2), (file!(), line + 4)]
+    );
+}
+
+#[test]
+fn test_multi_modify() {
+    let locations = Arc::new(Locations::default());
+    let a = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&locations) as Arc<dyn Interceptor>)
+        .build();
+    let b = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&locations) as Arc<dyn Interceptor>)
+        .build();
+
+    let line = line!();
+    multi_modify((&a, &b), |a, b| *a = *b + 1, Other::All).unwrap();
+
+    // both instances report the operation
+    assert_eq!(
+        *locations.0.lock().unwrap(),
+        vec![(file!(), line + 1), (file!(), line + 1)]
+    );
+}
@@ -0,0 +1,21 @@
+use cond_sync::{CondSync, Other};
+use std::thread;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(vec![1, 2, 3]);
+    assert_eq!(cond_sync.try_clone_inner().unwrap(), vec![1, 2, 3]);
+
+    // poison the mutex by panicking in a closure
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        cond_sync_t
+            .modify_and_notify(|_| panic!("worker panicked"), Other::All)
+            .ok();
+    })
+    .join()
+    .unwrap_err();
+
+    assert!(cond_sync.try_clone_inner().is_err());
+    assert_eq!(cond_sync.clone_inner(), vec![1, 2, 3]);
+}
@@ -0,0 +1,35 @@
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        for _ in 0..10 {
+            thread::sleep(Duration::from_millis(5));
+            cond_sync_t
+                .modify_and_notify(|v| *v += 1, Other::All)
+                .unwrap();
+        }
+    });
+
+    let (idx, reason) = cond_sync
+        .wait_any_indexed(&[&|v| *v >= 100, &|v| *v >= 3, &|v| *v >= 2])
+        .unwrap();
+    assert!(reason.is_condition());
+    assert!(idx == 1 || idx == 2);
+
+    let (idx, reason) = cond_sync
+        .wait_any_indexed_or_timeout(&[&|v| *v >= 100, &|v| *v >= 10], Duration::from_secs(5))
+        .unwrap();
+    assert!(reason.is_condition());
+    assert_eq!(idx, Some(1));
+
+    let (idx, reason) = cond_sync
+        .wait_any_indexed_or_timeout(&[&|v| *v >= 100], Duration::from_millis(10))
+        .unwrap();
+    assert!(reason.is_timeout());
+    assert_eq!(idx, None);
+}
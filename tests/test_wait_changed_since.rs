@@ -0,0 +1,60 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let token = cond_sync.generation();
+
+    // a modification that happens before the wait is not missed
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    let generation = cond_sync.wait_changed_since(token).unwrap();
+    assert_eq!(generation.value(), token.value() + 1);
+    assert_eq!(generation, cond_sync.generation());
+
+    // a modification that happens during the wait ends it
+    thread::scope(|scope| {
+        let waiter = scope.spawn(|| cond_sync.wait_changed_since(generation).unwrap());
+        thread::sleep(Duration::from_millis(20));
+        assert!(!waiter.is_finished());
+        cond_sync
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+        assert!(waiter.join().unwrap() > generation);
+    });
+}
+
+#[test]
+fn test_wait_updates_since() {
+    let cond_sync = CondSync::new(0_usize);
+    let token = cond_sync.generation();
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    // one update is not enough
+    assert_eq!(
+        cond_sync
+            .wait_updates_since(token, 2, Duration::from_millis(20))
+            .unwrap(),
+        None
+    );
+
+    thread::scope(|scope| {
+        let waiter = scope.spawn(|| {
+            cond_sync
+                .wait_updates_since(token, 3, Duration::from_secs(10))
+                .unwrap()
+        });
+        for _ in 0..2 {
+            cond_sync
+                .modify_and_notify(|v| *v += 1, Other::All)
+                .unwrap();
+        }
+        let generation = waiter.join().unwrap().unwrap();
+        assert_eq!(generation.value(), token.value() + 3);
+    });
+}
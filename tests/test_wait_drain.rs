@@ -0,0 +1,40 @@
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+const NO_OF_THREADS: usize = 5;
+const ITEMS_PER_THREAD: usize = 100;
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(Vec::<usize>::new());
+    for _ in 0..NO_OF_THREADS {
+        let cond_sync_t = cond_sync.clone();
+        thread::spawn(move || {
+            for i in 0..ITEMS_PER_THREAD {
+                cond_sync_t
+                    .modify_and_notify(|v| v.push(i), Other::One)
+                    .unwrap();
+            }
+        });
+    }
+
+    let mut received = 0;
+    while received < NO_OF_THREADS * ITEMS_PER_THREAD {
+        let batch = cond_sync.wait_drain(|v| !v.is_empty()).unwrap();
+        assert!(!batch.is_empty());
+        received += batch.len();
+    }
+    assert_eq!(received, NO_OF_THREADS * ITEMS_PER_THREAD);
+    assert!(cond_sync.try_clone_inner().unwrap().is_empty());
+}
+
+#[test]
+fn test_timeout() {
+    let cond_sync = CondSync::new(vec![1_usize]);
+    assert_eq!(
+        cond_sync
+            .wait_drain_or_timeout(|v| v.len() > 1, Duration::from_millis(10))
+            .unwrap(),
+        None
+    );
+    assert_eq!(cond_sync.try_clone_inner().unwrap(), vec![1]);
+}
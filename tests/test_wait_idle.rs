@@ -0,0 +1,40 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        // a burst of modifications for about 100ms
+        scope.spawn(|| {
+            for _ in 0..20 {
+                cond_sync
+                    .modify_and_notify(|v| *v += 1, Other::All)
+                    .unwrap();
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+        thread::sleep(Duration::from_millis(10));
+
+        // the overall timeout is reached during the burst
+        assert!(cond_sync
+            .wait_idle(Duration::from_millis(100), Duration::from_millis(40))
+            .unwrap()
+            .is_timeout());
+        assert!(cond_sync.clone_inner() < 20);
+
+        // the quiet period is reached only after the burst
+        assert!(cond_sync
+            .wait_idle(Duration::from_millis(100), Duration::from_secs(5))
+            .unwrap()
+            .is_condition());
+        assert_eq!(cond_sync.clone_inner(), 20);
+        assert!(start.elapsed() >= Duration::from_millis(190));
+    });
+}
@@ -0,0 +1,52 @@
+use cond_sync::WaitMap;
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let responses = WaitMap::<usize, String>::new();
+
+    // many waiters, completed in reverse order
+    let handles: Vec<_> = (0..50)
+        .map(|id| {
+            let responses_t = responses.clone();
+            thread::spawn(move || responses_t.wait(&id).unwrap())
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(20));
+    for id in (0..50).rev() {
+        responses.complete(id, format!("response {id}")).unwrap();
+    }
+    for (id, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), format!("response {id}"));
+    }
+    assert!(responses.is_empty().unwrap());
+
+    // completed before the wait
+    responses.complete(7, "early".to_string()).unwrap();
+    assert_eq!(responses.len().unwrap(), 1);
+    assert_eq!(responses.wait(&7).unwrap(), "early");
+    assert!(responses.is_empty().unwrap());
+
+    // abandoned waits are cleaned up
+    assert_eq!(
+        responses
+            .wait_timeout(&8, Duration::from_millis(10))
+            .unwrap(),
+        None
+    );
+    assert!(responses.is_empty().unwrap());
+
+    // values that nobody fetches can be discarded
+    responses.complete(9, "late".to_string()).unwrap();
+    assert_eq!(responses.remove(&9).unwrap(), Some("late".to_string()));
+    assert!(responses.is_empty().unwrap());
+
+    // a single shard works as well
+    let responses = WaitMap::<usize, usize>::with_shards(1);
+    responses.complete(1, 10).unwrap();
+    responses.complete(2, 20).unwrap();
+    assert_eq!(responses.len().unwrap(), 2);
+    assert_eq!(responses.wait(&2).unwrap(), 20);
+    assert_eq!(responses.wait(&1).unwrap(), 10);
+    assert!(responses.is_empty().unwrap());
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "generations")]
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+
+    // no wakeup needed
+    let outcome = cond_sync.wait_until_detailed(|v| *v == 0).unwrap();
+    assert!(outcome.reason().is_condition());
+    assert_eq!(outcome.wakeups(), 0);
+    assert_eq!(outcome.generation(), 0);
+
+    // notifications without modification count as spurious wakeups
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        cond_sync_t
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+        thread::sleep(Duration::from_millis(20));
+        let _ticker = cond_sync_t.notify_every(Duration::from_millis(5), Other::All);
+        thread::sleep(Duration::from_millis(30));
+        cond_sync_t
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    });
+    let outcome = cond_sync.wait_until_detailed(|v| *v == 2).unwrap();
+    handle.join().unwrap();
+    println!("{outcome:?}");
+    assert!(outcome.reason().is_condition());
+    assert_eq!(outcome.generation(), 2);
+    assert!(outcome.wakeups() >= 2);
+    assert!(outcome.spurious_wakeups() >= 1);
+    assert!(outcome.spurious_wakeups() < outcome.wakeups());
+    assert!(!outcome.is_last_wakeup_spurious());
+
+    let outcome = cond_sync
+        .wait_until_or_timeout_detailed(|_| false, Duration::from_millis(10))
+        .unwrap();
+    assert!(outcome.reason().is_timeout());
+    assert_eq!(outcome.spurious_wakeups(), 0);
+    assert_eq!(outcome.generation(), 2);
+}
@@ -0,0 +1,76 @@
+use cond_sync::{CondSync, Other};
+#[cfg(feature = "observers")]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::thread;
+
+#[test]
+fn test() {
+    let queue = CondSync::new(Vec::<usize>::new());
+
+    let queue_t = queue.clone();
+    let consumer = thread::spawn(move || {
+        let mut item = None;
+        // take an item and wake up the producer
+        queue_t
+            .wait_until_and_modify(|v| !v.is_empty(), |v| item = v.pop(), Other::One)
+            .unwrap();
+        item
+    });
+    queue
+        .wait_until_and_modify(|_| true, |v| v.push(5), Other::All)
+        .unwrap();
+    assert!(queue.wait_until(Vec::is_empty).unwrap().is_condition());
+    assert_eq!(consumer.join().unwrap(), Some(5));
+
+    // without notification
+    queue
+        .wait_until_and_modify(|_| true, |v| v.push(6), None)
+        .unwrap();
+    assert_eq!(queue.clone_inner(), vec![6]);
+}
+
+#[cfg(feature = "observers")]
+#[test]
+fn test_observers() {
+    let queue = CondSync::new(Vec::<usize>::new());
+    let modified = Arc::new(AtomicUsize::new(0));
+    let unnotified = Arc::new(AtomicUsize::new(0));
+    let notified = Arc::new(AtomicUsize::new(0));
+    let modified_t = Arc::clone(&modified);
+    let unnotified_t = Arc::clone(&unnotified);
+    let _on_modify = queue.on_modify(move |event| {
+        if event.other().is_none() {
+            unnotified_t.fetch_add(1, Ordering::SeqCst);
+        }
+        modified_t.fetch_add(1, Ordering::SeqCst);
+    });
+    let notified_t = Arc::clone(&notified);
+    let _on_notify = queue.on_notify(move |event| {
+        assert!(event.other().is_some());
+        notified_t.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let queue_t = queue.clone();
+    let consumer = thread::spawn(move || {
+        queue_t
+            .wait_until_and_modify(|v| !v.is_empty(), |v| v.clear(), Other::One)
+            .unwrap();
+    });
+    queue
+        .wait_until_and_modify(|_| true, |v| v.push(5), Other::All)
+        .unwrap();
+    queue.wait_until(Vec::is_empty).unwrap();
+    consumer.join().unwrap();
+    assert_eq!(notified.load(Ordering::SeqCst), 2);
+
+    // without notification
+    queue
+        .wait_until_and_modify(|_| true, |v| v.push(6), None)
+        .unwrap();
+    assert_eq!(modified.load(Ordering::SeqCst), 3);
+    assert_eq!(unnotified.load(Ordering::SeqCst), 1);
+    assert_eq!(notified.load(Ordering::SeqCst), 2);
+}
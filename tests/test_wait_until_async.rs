@@ -0,0 +1,57 @@
+#![cfg(feature = "async")]
+use cond_sync::{CondSync, Other};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+struct ThreadWaker(Thread);
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// a minimal executor
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        for _ in 0..3 {
+            thread::sleep(Duration::from_millis(5));
+            cond_sync_t
+                .modify_and_notify(|v| *v += 1, Other::All)
+                .unwrap();
+        }
+    });
+
+    let reason = block_on(cond_sync.wait_until_async(|v| *v == 3)).unwrap();
+    assert!(reason.is_condition());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_timeout() {
+    let cond_sync = CondSync::new(0_usize);
+    let reason =
+        block_on(cond_sync.wait_until_or_timeout_async(|v| *v == 1, Duration::from_millis(20)))
+            .unwrap();
+    assert!(reason.is_timeout());
+}
@@ -0,0 +1,66 @@
+use cond_sync::{CondSync, Other};
+use std::thread;
+
+// (value, number of checks by the waiter)
+fn run(cond_sync: &CondSync<(u64, u64)>) {
+    thread::scope(|scope| {
+        let waiter = scope.spawn(|| {
+            cond_sync
+                .wait_until_mut(|(value, checks)| {
+                    *checks += 1;
+                    *value == 3
+                })
+                .unwrap()
+        });
+        for _ in 0..3 {
+            cond_sync
+                .modify_and_notify(|(value, _)| *value += 1, Other::All)
+                .unwrap();
+        }
+        assert!(waiter.join().unwrap().is_condition());
+    });
+}
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new((0_u64, 0_u64));
+    run(&cond_sync);
+
+    // the checks are visible
+    let (value, checks) = cond_sync.clone_inner();
+    assert_eq!(value, 3);
+    assert!(checks >= 1);
+}
+
+#[cfg(feature = "optimistic_reads")]
+#[test]
+fn test_optimistic_reads() {
+    let cond_sync = CondSync::builder((0_u64, 0_u64)).optimistic_reads().build();
+    run(&cond_sync);
+
+    // the checks are visible also to optimistic reads
+    let (value, checks) = cond_sync.read_optimistic();
+    assert_eq!(value, 3);
+    assert!(checks >= 1);
+    assert_eq!(cond_sync.clone_inner(), (value, checks));
+}
+
+#[cfg(feature = "observers")]
+#[test]
+fn test_observers() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let cond_sync = CondSync::new((0_u64, 0_u64));
+    let modifications = Arc::new(AtomicUsize::new(0));
+    let modifications_t = Arc::clone(&modifications);
+    let _handle = cond_sync.on_modify(move |_| {
+        modifications_t.fetch_add(1, Ordering::SeqCst);
+    });
+    run(&cond_sync);
+
+    // the checks are no modifications
+    assert_eq!(modifications.load(Ordering::SeqCst), 3);
+}
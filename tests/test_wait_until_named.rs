@@ -0,0 +1,74 @@
+use cond_sync::{CondSync, Other};
+#[cfg(feature = "interceptors")]
+use cond_sync::{Interceptor, Operation, Outcome};
+#[cfg(feature = "interceptors")]
+use std::sync::{Arc, Mutex};
+use std::{thread, time::Duration};
+
+#[cfg(feature = "interceptors")]
+#[derive(Default)]
+struct Labels(Mutex<Vec<(&'static str, Option<&'static str>)>>);
+#[cfg(feature = "interceptors")]
+impl Interceptor for Labels {
+    fn after(&self, operation: &Operation, _outcome: &Outcome) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((operation.method(), operation.label()));
+    }
+}
+
+#[test]
+fn test_waiting() {
+    let cond_sync = CondSync::new(0_usize);
+
+    let cond_sync_t = cond_sync.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        cond_sync_t
+            .modify_and_notify(|v| *v += 1, Other::All)
+            .unwrap();
+    });
+    assert!(cond_sync
+        .wait_until_named("first", |v| *v == 1)
+        .unwrap()
+        .is_condition());
+    handle.join().unwrap();
+    assert!(cond_sync
+        .wait_until_or_timeout_named("second", |v| *v == 1, Duration::from_millis(10))
+        .unwrap()
+        .is_condition());
+    assert!(cond_sync
+        .wait_until_or_timeout_named("third", |v| *v == 2, Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+}
+
+#[cfg(feature = "interceptors")]
+#[test]
+fn test_labels() {
+    let labels = Arc::new(Labels::default());
+    let cond_sync = CondSync::builder(0_usize)
+        .interceptor(Arc::clone(&labels) as Arc<dyn Interceptor>)
+        .build();
+
+    cond_sync
+        .modify_and_notify(|v| *v += 1, Other::All)
+        .unwrap();
+    cond_sync.wait_until_named("first", |v| *v == 1).unwrap();
+    assert!(cond_sync
+        .wait_until_or_timeout_named("second", |v| *v == 2, Duration::from_millis(10))
+        .unwrap()
+        .is_timeout());
+    cond_sync.wait_until(|v| *v == 1).unwrap();
+
+    assert_eq!(
+        *labels.0.lock().unwrap(),
+        vec![
+            ("modify_and_notify", None),
+            ("wait_until", Some("first")),
+            ("wait_until_or_timeout", Some("second")),
+            ("wait_until", None),
+        ]
+    );
+}
@@ -0,0 +1,44 @@
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    Pending,
+    Done,
+    Failed,
+}
+
+#[test]
+fn test_timeout() {
+    let cond_sync = CondSync::new(State::Pending);
+    assert!(cond_sync
+        .wait_until_or_else(
+            |v| *v == State::Done,
+            Duration::from_millis(20),
+            |v| *v = State::Failed
+        )
+        .unwrap()
+        .is_timeout());
+    assert_eq!(cond_sync.clone_inner(), State::Failed);
+}
+
+#[test]
+fn test_condition() {
+    let cond_sync = CondSync::new(State::Pending);
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        cond_sync_t
+            .modify_and_notify(|v| *v = State::Done, Other::One)
+            .unwrap();
+    });
+    assert!(cond_sync
+        .wait_until_or_else(
+            |v| *v == State::Done,
+            Duration::from_secs(10),
+            |v| *v = State::Failed
+        )
+        .unwrap()
+        .is_condition());
+    assert_eq!(cond_sync.clone_inner(), State::Done);
+}
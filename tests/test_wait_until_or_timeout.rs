@@ -1,4 +1,4 @@
-use cond_sync::{CondSync, Other};
+use cond_sync::{CondSync, Other, Reason};
 use std::{thread, time::Duration};
 const NO_OF_THREADS: usize = 5;
 
@@ -27,3 +27,29 @@ fn test() {
     println!("Main: All threads initialized");
     thread::sleep(Duration::from_millis(100)); // just to let the threads finish (better use join)}
 }
+
+#[test]
+fn test_into_result() {
+    let cond_sync = CondSync::new(0);
+    assert_eq!(
+        cond_sync
+            .wait_until_or_timeout(|v| *v == 0, Duration::from_millis(10))
+            .unwrap()
+            .into_result(),
+        Ok(Reason::Condition)
+    );
+    assert_eq!(
+        cond_sync
+            .wait_until_or_timeout(|v| *v == 1, Duration::from_millis(10))
+            .unwrap()
+            .into_result(),
+        Err(Reason::Timeout)
+    );
+}
+
+#[test]
+fn test_into_result_of_ending_reasons() {
+    for reason in [Reason::Timeout, Reason::Cancelled] {
+        assert_eq!(reason.into_result(), Err(reason));
+    }
+}
@@ -0,0 +1,46 @@
+use cond_sync::{CondSync, Other};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test() {
+    let registry = Mutex::new(0_usize);
+    let cond_sync = CondSync::new(false);
+
+    thread::scope(|scope| {
+        let registry_guard = registry.lock().unwrap();
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(20));
+            // needs the lock that the waiting thread held before the wait
+            *registry.lock().unwrap() += 1;
+            cond_sync
+                .modify_and_notify(|v| *v = true, Other::All)
+                .unwrap();
+        });
+        let registry_guard = cond_sync
+            .wait_until_releasing(&registry, registry_guard, |v| *v)
+            .unwrap();
+        assert_eq!(*registry_guard, 1);
+    });
+}
+
+#[test]
+fn test_poisoned_other() {
+    let other = Arc::new(Mutex::new(()));
+    let other_t = Arc::clone(&other);
+    thread::spawn(move || {
+        let _guard = other_t.lock().unwrap();
+        panic!("poisoning the other mutex");
+    })
+    .join()
+    .unwrap_err();
+    let other_guard = other.lock().unwrap_err().into_inner();
+
+    let cond_sync = CondSync::new(true);
+    assert!(cond_sync
+        .wait_until_releasing(&other, other_guard, |v| *v)
+        .is_err());
+}
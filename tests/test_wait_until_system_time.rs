@@ -0,0 +1,33 @@
+use cond_sync::{CondSync, Other};
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(false);
+
+    let cond_sync_t = cond_sync.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        cond_sync_t
+            .modify_and_notify(|v| *v = true, Other::One)
+            .unwrap();
+    });
+    assert!(cond_sync
+        .wait_until_system_time(|v| *v, SystemTime::now() + Duration::from_secs(5))
+        .unwrap()
+        .is_condition());
+
+    assert!(cond_sync
+        .wait_until_system_time(|v| !*v, SystemTime::now() + Duration::from_millis(20))
+        .unwrap()
+        .is_timeout());
+
+    // deadline in the past
+    assert!(cond_sync
+        .wait_until_system_time(|v| !*v, SystemTime::now() - Duration::from_secs(5))
+        .unwrap()
+        .is_timeout());
+}
@@ -0,0 +1,38 @@
+use cond_sync::{CondSync, Other};
+use std::{thread, time::Duration};
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::new(0_usize);
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            cond_sync.modify_and_notify(|v| *v = 1, Other::All).unwrap();
+        });
+
+        // the lock is not held while yielding, so other work can modify the value
+        let mut yields = 0;
+        let reason = cond_sync
+            .wait_until_yielding(
+                |v| *v == 2,
+                Duration::from_millis(2),
+                || {
+                    yields += 1;
+                    cond_sync
+                        .modify_and_notify(|v| *v = if *v == 1 { 2 } else { *v }, Other::All)
+                        .unwrap();
+                },
+            )
+            .unwrap();
+        assert!(reason.is_condition());
+        assert!(yields >= 10);
+    });
+
+    // no yield if the condition holds already
+    let mut yields = 0;
+    cond_sync
+        .wait_until_yielding(|v| *v == 2, Duration::from_millis(2), || yields += 1)
+        .unwrap();
+    assert_eq!(yields, 0);
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "jitter")]
+use cond_sync::{CondSync, Other};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+const NO_OF_THREADS: usize = 5;
+const MAX_JITTER: Duration = Duration::from_millis(30);
+
+#[test]
+fn test() {
+    let cond_sync = CondSync::builder(false)
+        .wakeup_jitter(MAX_JITTER)
+        .jitter_seed(42)
+        .build();
+
+    let handles: Vec<_> = (0..NO_OF_THREADS)
+        .map(|_| {
+            let cond_sync_t = cond_sync.clone();
+            thread::spawn(move || {
+                cond_sync_t.wait_until(|v| *v).unwrap();
+                Instant::now()
+            })
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(50)); // let the threads start waiting
+    let start = Instant::now();
+    cond_sync
+        .modify_and_notify(|v| *v = true, Other::All)
+        .unwrap();
+
+    let mut delays: Vec<Duration> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap().duration_since(start))
+        .collect();
+    delays.sort();
+    println!("delays: {delays:?}");
+    // the wakeups are spread
+    assert!(delays[0] < delays[NO_OF_THREADS - 1]);
+}
+
+#[test]
+fn test_no_jitter_without_wait() {
+    let cond_sync = CondSync::builder(true)
+        .wakeup_jitter(Duration::from_secs(60))
+        .build();
+    // the condition is already fulfilled, so there is no wakeup and no jitter
+    let start = Instant::now();
+    cond_sync.wait_until(|v| *v).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(30));
+}